@@ -129,7 +129,7 @@ fn main() -> gonfig::Result<()> {
 
     let config_custom: OrchestratorConfig = ConfigBuilder::new()
         .with_defaults(custom_defaults)?
-        .with_env("ORCHESTRATOR")
+        .with_env_flat("ORCHESTRATOR")
         .build()?;
 
     print_config(&config_custom);
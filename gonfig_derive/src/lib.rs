@@ -4,29 +4,150 @@ use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(gonfig, Gonfig))]
+#[darling(attributes(gonfig, Gonfig), forward_attrs(serde))]
 struct GonfigOpts {
     ident: syn::Ident,
     generics: syn::Generics,
+    attrs: Vec<syn::Attribute>,
     data: darling::ast::Data<(), GonfigField>,
 
     #[darling(default)]
     env_prefix: Option<String>,
 
+    #[darling(default)]
+    fallback_prefixes: Option<String>,
+
     #[darling(default)]
     allow_cli: bool,
 
     #[darling(default)]
     allow_config: bool,
+
+    #[darling(default)]
+    sources: Option<String>,
+
+    #[darling(default)]
+    wrap_scalars: bool,
+
+    #[darling(default)]
+    deny_unknown_fields: bool,
+
+    #[darling(default)]
+    rename_all: Option<String>,
+
+    #[darling(default)]
+    clap: bool,
+
+    /// Deprecated name for `env_separator`.
+    #[darling(default)]
+    separator: Option<String>,
+
+    #[darling(default)]
+    env_separator: Option<String>,
+}
+
+impl GonfigOpts {
+    /// Resolve which sources the generated code wires up: `(env, cli, config)`.
+    ///
+    /// Without `sources`, environment variables are always enabled. With it, only
+    /// the listed sources are used (plus any enabled through `allow_cli` /
+    /// `allow_config`).
+    fn enabled_sources(&self) -> darling::Result<(bool, bool, bool)> {
+        let Some(sources) = &self.sources else {
+            return Ok((true, self.allow_cli, self.allow_config));
+        };
+
+        let (mut env, mut cli, mut config) = (false, self.allow_cli, self.allow_config);
+        for source in sources.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match source {
+                "env" => env = true,
+                "cli" => cli = true,
+                "config" => config = true,
+                other => {
+                    return Err(darling::Error::custom(format!(
+                        "unknown source `{other}` in `sources`; expected `env`, `cli` or `config`"
+                    ))
+                    .with_span(&self.ident))
+                }
+            }
+        }
+        Ok((env, cli, config))
+    }
+
+    /// Name a field, returning `(env stem, CLI flag)`.
+    ///
+    /// The env stem is always the snake_case field name, uppercased at runtime
+    /// before the prefix is added, so variables stay valid shell names. Flags
+    /// follow `rename_all`, and are kebab-case without it.
+    fn field_names(&self, field: &str) -> darling::Result<(String, String)> {
+        let Some(rule) = &self.rename_all else {
+            return Ok((field.to_string(), field.replace('_', "-")));
+        };
+
+        let renamed = match rule.as_str() {
+            "snake_case" | "kebab-case" | "SCREAMING_SNAKE_CASE" | "camelCase" => {
+                rename_field(rule, field)
+            }
+            _ => None,
+        };
+        let Some(renamed) = renamed else {
+            return Err(darling::Error::custom(format!(
+                "unknown `rename_all` rule `{rule}`; expected `kebab-case`, \
+                 `SCREAMING_SNAKE_CASE`, `camelCase` or `snake_case`"
+            ))
+            .with_span(&self.ident));
+        };
+        Ok((field.to_string(), renamed))
+    }
+
+    /// Key serde reads `field` from: its `#[serde(rename)]`, or the name after
+    /// the container's `#[serde(rename_all)]`.
+    fn serde_key(&self, field: &GonfigField) -> String {
+        let name = field.ident.as_ref().unwrap().to_string();
+        if let Some(renamed) = serde_name(&field.attrs, "rename") {
+            return renamed;
+        }
+        serde_name(&self.attrs, "rename_all")
+            .and_then(|rule| rename_field(&rule, &name))
+            .unwrap_or(name)
+    }
+}
+
+/// Apply a serde `rename_all` rule to a snake_case field name.
+///
+/// Returns `None` for a rule serde does not know either.
+fn rename_field(rule: &str, field: &str) -> Option<String> {
+    let capitalize = |part: &str| {
+        let mut chars = part.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+    let parts = || field.split('_').filter(|part| !part.is_empty());
+
+    Some(match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_uppercase(),
+        "PascalCase" => parts().map(capitalize).collect(),
+        "camelCase" => {
+            let mut parts = parts();
+            let first = parts.next().unwrap_or_default().to_string();
+            first + &parts.map(capitalize).collect::<String>()
+        }
+        _ => return None,
+    })
 }
 
 #[derive(Debug, FromField)]
-#[darling(attributes(gonfig, skip_gonfig, skip))]
+#[darling(attributes(gonfig), forward_attrs(doc, skip, skip_gonfig, serde))]
 struct GonfigField {
     ident: Option<syn::Ident>,
 
-    // Reserved for future use (flatten feature)
-    #[allow(dead_code)]
+    attrs: Vec<syn::Attribute>,
+
     ty: syn::Type,
 
     #[darling(default)]
@@ -35,24 +156,117 @@ struct GonfigField {
     #[darling(default)]
     cli_name: Option<String>,
 
+    #[darling(default)]
+    cli_short: Option<char>,
+
     #[darling(default)]
     skip_gonfig: bool,
 
     #[darling(default)]
     skip: bool,
 
-    // Reserved for future use (flatten feature)
-    #[allow(dead_code)]
     #[darling(default)]
     flatten: bool,
 
-    // Reserved for future use (nested configuration feature)
-    #[allow(dead_code)]
     #[darling(default)]
     nested: bool,
 
     #[darling(default)]
     default: Option<String>,
+
+    #[darling(default)]
+    default_env: Option<String>,
+
+    #[darling(default)]
+    default_variant: Option<String>,
+
+    #[darling(default)]
+    example: Option<String>,
+
+    #[darling(default)]
+    trim_default: bool,
+
+    #[darling(default)]
+    path: Option<String>,
+
+    #[darling(default)]
+    sensitive: bool,
+
+    #[darling(default)]
+    try_from: Option<String>,
+
+    #[darling(default, multiple)]
+    conflicts_with: Vec<String>,
+
+    #[darling(default, multiple)]
+    env_alias: Vec<String>,
+
+    #[darling(default)]
+    presence: bool,
+
+    #[darling(default)]
+    merge: Option<String>,
+
+    #[darling(default)]
+    required: bool,
+
+    #[darling(default)]
+    value_name: Option<String>,
+
+    #[darling(default)]
+    parse_with: Option<String>,
+}
+
+impl GonfigField {
+    /// Whether the field is excluded via `#[skip]`, `#[skip_gonfig]` or `#[gonfig(skip)]`.
+    fn is_skipped(&self) -> bool {
+        self.skip
+            || self.skip_gonfig
+            || self
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("skip") || attr.path().is_ident("skip_gonfig"))
+    }
+
+    /// Whether the field is a `#[serde(flatten)]` catch-all.
+    fn is_serde_flatten(&self) -> bool {
+        has_serde_option(&self.attrs, &["flatten"])
+    }
+
+    /// Whether serde can deserialize the struct without a value for this field.
+    ///
+    /// True for `#[serde(skip)]`, `skip_deserializing` and `default` fields, and
+    /// for `Option` fields, which serde treats as `None` when missing.
+    fn serde_tolerates_missing(&self) -> bool {
+        option_inner(&self.ty).is_some()
+            || has_serde_option(&self.attrs, &["skip", "skip_deserializing", "default"])
+    }
+
+    /// Collect the field's doc comment lines into a single line.
+    fn doc(&self) -> Option<String> {
+        let lines: Vec<String> = self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(nv) => match &nv.value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join(" "))
+        }
+    }
 }
 
 /// Derive macro for the `Gonfig` trait, enabling declarative configuration management.
@@ -62,11 +276,18 @@ struct GonfigField {
 ///
 /// # Generated Methods
 ///
-/// The macro generates three public methods on your struct:
+/// The macro generates the following public methods on your struct:
 ///
 /// - `from_gonfig() -> Result<Self>` - Loads configuration from all enabled sources
-/// - `from_gonfig_with_builder(builder: ConfigBuilder) -> Result<Self>` - Advanced configuration with custom builder
+/// - `from_gonfig_with_builder(builder: ConfigBuilder) -> Result<Self>` - Advanced configuration with custom builder;
+///   the derived sources are added after the builder's, so the process's own flags win over a
+///   `Cli` registered on the builder
 /// - `gonfig_builder() -> ConfigBuilder` - Returns a pre-configured builder for advanced use cases
+/// - `gonfig_fields() -> Vec<FieldInfo>` - Static metadata for each configurable field
+/// - `gonfig_help() -> String` - `--help` style text listing flags, env vars, defaults and examples
+/// - `gonfig_env_example() -> String` - A `.env.example` file with each env var, its default and doc comment
+/// - `gonfig_example(format: ConfigFormat) -> String` - A template config file with nested structs as sections
+/// - `gonfig_clap_command() -> clap::Command` - A clap command for the flags (`clap` feature)
 ///
 /// # Container Attributes
 ///
@@ -84,6 +305,20 @@ struct GonfigField {
 /// }
 /// ```
 ///
+/// ## `#[Gonfig(fallback_prefixes = "OLDAPP")]`
+/// Also read fields under other prefixes, in the order listed, while `env_prefix`
+/// keeps priority; useful while renaming a prefix. Takes a comma-separated list,
+/// requires `env_prefix`, and is composed with a parent's prefix like `env_prefix`.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", fallback_prefixes = "OLDAPP")]
+/// struct Config {
+///     port: u16,  // APP_PORT, or OLDAPP_PORT when APP_PORT is unset
+/// }
+/// ```
+///
 /// ## `#[Gonfig(allow_cli)]`
 /// Enables CLI argument parsing. Field names are converted to kebab-case.
 ///
@@ -110,6 +345,123 @@ struct GonfigField {
 /// }
 /// ```
 ///
+/// ## `#[Gonfig(sources = "env,cli")]`
+/// Choose exactly which sources the generated code wires up, from `env`, `cli` and
+/// `config`. Without this attribute environment variables are always enabled; with it,
+/// a source that isn't listed is not read (`allow_cli` and `allow_config` still add
+/// their source). Default values always apply.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "TOOL", sources = "cli")]
+/// struct Config {
+///     verbose: bool,  // Only --verbose is read; TOOL_VERBOSE is ignored
+/// }
+/// ```
+///
+/// ## `#[Gonfig(wrap_scalars)]`
+/// Accept a single value for sequence fields (`Vec`, `VecDeque`, `HashSet`,
+/// `BTreeSet`, optionally inside `Option`): a scalar from any source is wrapped
+/// into a one-element list before deserialization.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", wrap_scalars)]
+/// struct Config {
+///     hosts: Vec<String>,  // APP_HOSTS=localhost → ["localhost"]
+/// }
+/// ```
+///
+/// ## `#[Gonfig(deny_unknown_fields)]`
+/// Reject keys from any source that no field of the struct reads, as
+/// `ConfigBuilder::deny_unknown_fields(true)` does. A `#[serde(flatten)]`
+/// catch-all map accepts every key, so combining the two is a compile error.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", allow_config, deny_unknown_fields)]
+/// struct Config {
+///     port: u16,  // `prot: 9090` in a config file fails the build
+/// }
+/// ```
+///
+/// ## `#[Gonfig(env_separator = "__")]`
+/// Join the prefix and field name with a custom separator instead of `_`. The
+/// separator is also used when a nested struct's prefix is composed with its
+/// parent's, and is passed to the generated `Environment`, so the keys in
+/// `gonfig_fields()` and the variables actually read always agree.
+/// `separator` is still accepted as a deprecated name for this option; setting
+/// both is an error.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", env_separator = "__")]
+/// struct Config {
+///     database_url: String,  // Environment variable: APP__DATABASE_URL
+/// }
+/// ```
+///
+/// ## `#[Gonfig(rename_all = "camelCase")]`
+/// Convert field names with one of `kebab-case`, `SCREAMING_SNAKE_CASE`,
+/// `camelCase` or `snake_case` before building CLI flags. Environment variable
+/// names keep the snake_case field name, uppercased, whatever the rule. Per-field
+/// `env_name` and `cli_name` take precedence.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", rename_all = "camelCase")]
+/// struct Config {
+///     max_connections: u32,  // --maxConnections, APP_MAX_CONNECTIONS
+/// }
+/// ```
+///
+/// ## `#[serde(rename)]` and `#[serde(rename_all)]`
+/// Collected values are stored under the key serde deserializes the field
+/// from, so serde's renames keep working. Environment variable and flag names
+/// are still built from the Rust field name; use gonfig's own `rename_all`,
+/// `env_name` or `cli_name` to change those.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// #[serde(rename_all = "camelCase")]
+/// struct Config {
+///     database_url: String,  // APP_DATABASE_URL, stored as `databaseUrl`
+///     #[serde(rename = "workers")]
+///     worker_count: u32,     // APP_WORKER_COUNT, stored as `workers`
+/// }
+/// ```
+///
+/// ## `#[Gonfig(clap)]`
+/// With gonfig's `clap` feature, every derived struct gets
+/// `gonfig_clap_command() -> clap::Command` with one argument per flag: the
+/// `cli_name` as long flag, `value_name`, the doc comment as help, the field
+/// type's value parser for primitives, `default` values shown in `--help`,
+/// `bool` fields as switches and `Vec` fields as repeatable flags. Sensitive,
+/// skipped and nested fields get no argument; flattened structs contribute
+/// theirs. The `clap` attribute makes `from_gonfig()` parse the command line
+/// with that command instead of the lenient built-in parser, so `--help`
+/// prints and exits and unknown or malformed flags are an `Error::Cli`.
+/// Defaults stay at the lowest precedence: a value clap only defaulted is not
+/// read from the command line.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", allow_cli, clap)]
+/// struct Config {
+///     /// Port to listen on
+///     #[gonfig(default = "8080")]
+///     port: u16,  // --port <PORT>, rejected by clap unless it is a u16
+/// }
+/// ```
+///
 /// # Field Attributes
 ///
 /// ## `#[gonfig(env_name = "CUSTOM_NAME")]`
@@ -125,6 +477,51 @@ struct GonfigField {
 /// }
 /// ```
 ///
+/// The name may contain `{placeholder}` segments resolved from the environment at
+/// load time: `{stage}` is replaced by the uppercased value of `STAGE`, with `-` and
+/// other characters not allowed in a variable name turned into `_`. Loading fails
+/// with `Error::Environment` when the placeholder's variable is not set, and an
+/// unclosed or empty `{}` is a compile error.
+///
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(env_name = "{stage}_DB_URL")]
+///     db_url: String,  // STAGE=prod reads PROD_DB_URL
+/// }
+/// ```
+///
+/// ## `#[gonfig(env_alias = "OLD_NAME")]`
+/// Also read the field from another variable, e.g. a name being phased out. May be
+/// repeated. The field's own variable wins, then aliases in order; when several are
+/// set to different values a warning is logged naming all of them.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(env_alias = "DATABASE_URL")]
+///     db_url: String,  // APP_DB_URL, falling back to DATABASE_URL
+/// }
+/// ```
+///
+/// ## `#[gonfig(cli_short = 'p')]`
+/// Also accept a single-character flag, so `-p 8080` sets the field like
+/// `--port 8080`. The long form keeps working and wins when both are passed.
+/// Two fields of one struct cannot share a short flag, and sensitive fields
+/// cannot have one. With the `clap` feature, `-h` is reserved for `--help`.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(allow_cli)]
+/// struct Config {
+///     #[gonfig(cli_short = 'p')]
+///     port: u16,  // -p 8080 or --port 8080
+/// }
+/// ```
+///
 /// ## `#[gonfig(cli_name = "custom-name")]`
 /// Override the CLI argument name for a specific field.
 ///
@@ -138,106 +535,396 @@ struct GonfigField {
 /// }
 /// ```
 ///
-/// ## `#[gonfig(default = "value")]`
-/// Specify a default value for a field. The value should be a JSON-compatible string.
+/// ## `#[gonfig(sensitive)]`
+/// Never read the field from the command line. Secrets passed as flags leak into
+/// process listings and shell history, so a sensitive field can only be set through
+/// environment variables, config files or other sources. Its flag is ignored and left
+/// out of the generated help.
 ///
 /// **Example:**
 /// ```rust,ignore
 /// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP", allow_cli)]
 /// struct Config {
-///     #[gonfig(default = "8080")]
-///     port: u16,
-///
-///     #[gonfig(default = r#"["localhost"]"#)]
-///     allowed_hosts: Vec<String>,
+///     #[gonfig(sensitive)]
+///     api_token: String,  // Only APP_API_TOKEN; --api-token is ignored
 /// }
 /// ```
 ///
-/// ## `#[gonfig(nested)]`
-/// Marks a field as a nested configuration struct that should be loaded automatically.
-///
-/// When a field is marked as nested, the macro automatically calls that field type's
-/// `from_gonfig()` method, allowing you to compose configuration from multiple structs
-/// with their own prefixes and loading logic.
-///
-/// **Requirements:**
-/// - Nested field types must derive `Gonfig`
-/// - Nested field types must implement `Default` or have `#[serde(default)]`
-/// - Parent struct must mark nested fields with `#[serde(default)]`
+/// ## `#[gonfig(presence)]`
+/// Read a `bool` field as `true` whenever its environment variable is set, whatever
+/// its value. `APP_FEATURE=` (exported but empty) turns the flag on; an unset
+/// variable leaves the field to other sources and its default.
 ///
 /// **Example:**
 /// ```rust,ignore
-/// use gonfig::Gonfig;
-/// use serde::{Deserialize, Serialize};
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(presence, default = "false")]
+///     feature: bool,  // APP_FEATURE= → true
+/// }
+/// ```
 ///
-/// #[derive(Debug, Deserialize, Gonfig)]
-/// #[Gonfig(env_prefix = "SERVER")]
-/// #[serde(default)]
-/// struct ServerConfig {
-///     #[gonfig(default = "127.0.0.1")]
-///     host: String,
+/// ## `#[gonfig(required)]`
+/// Fail with `Error::MissingRequired` when no source provides the field, naming the
+/// environment variable to set instead of reporting serde's "missing field" error.
+/// A field holding `null` counts as missing. Cannot be combined with `default`.
 ///
-///     #[gonfig(default = "8080")]
-///     port: u16,
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(required)]
+///     database_url: String,  // "Missing required field `database_url`; set APP_DATABASE_URL"
 /// }
+/// ```
 ///
-/// impl Default for ServerConfig {
-///     fn default() -> Self {
-///         Self { host: String::new(), port: 0 }
-///     }
+/// ## `#[gonfig(parse_with = "path::to::fn")]`
+/// Read the field's value through a custom parser, for human-friendly values such as
+/// `30s` or `10MB`. The function has the signature
+/// `fn(&str) -> Result<serde_json::Value, E>` with `E: Display`; it returns what the
+/// field deserializes from. The parser runs while each source is collected, through
+/// `ConfigBuilder::with_value_parser`: environment variables and CLI flags hand it their
+/// raw text, so `007` is not coerced into `7` first. Its error is reported as
+/// `Error::Parse` naming the field. A `default` is passed to the parser as written.
+///
+/// **Example:**
+/// ```rust,ignore
+/// fn parse_seconds(text: &str) -> Result<serde_json::Value, String> {
+///     let secs: u64 = text.trim_end_matches('s').parse().map_err(|e| format!("{e}"))?;
+///     Ok(serde_json::json!({ "secs": secs, "nanos": 0 }))
 /// }
 ///
-/// #[derive(Debug, Deserialize, Gonfig)]
+/// #[derive(Gonfig, Deserialize)]
 /// #[Gonfig(env_prefix = "APP")]
-/// struct AppConfig {
-///     #[gonfig(nested)]
-///     #[serde(default)]  // Required for nested fields
-///     server: ServerConfig,
-///
-///     #[gonfig(default = "production")]
-///     environment: String,
+/// struct Config {
+///     #[gonfig(parse_with = "parse_seconds", default = "30s")]
+///     timeout: std::time::Duration,  // APP_TIMEOUT=90s
 /// }
-///
-/// // Automatic loading - ServerConfig loads with SERVER_ prefix
-/// let config = AppConfig::from_gonfig()?;
-/// println!("Server: {}:{}", config.server.host, config.server.port);
 /// ```
 ///
-/// **Environment Variables:**
-/// - `APP_ENVIRONMENT` → AppConfig.environment
-/// - `SERVER_HOST` → ServerConfig.host (nested struct uses its own prefix)
-/// - `SERVER_PORT` → ServerConfig.port
-///
-/// ## `#[skip]` or `#[skip_gonfig]`
-/// Exclude a field from configuration loading. Useful for non-serializable fields or
-/// fields that should only be set at runtime.
+/// ## `#[gonfig(conflicts_with = "other_field")]`
+/// Declare that the field's CLI flag cannot be combined with another field's flag,
+/// like clap's `conflicts_with`. Passing both fails with `Error::Cli`. Repeat the
+/// attribute to list several conflicts.
 ///
 /// **Example:**
 /// ```rust,ignore
 /// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(allow_cli)]
 /// struct Config {
-///     database_url: String,
-///
-///     #[skip]
-///     #[serde(skip)]
-///     runtime_data: Option<String>,  // Not loaded from config sources
+///     #[gonfig(conflicts_with = "verbose")]
+///     quiet: bool,
+///     verbose: bool,  // --quiet --verbose is rejected
 /// }
 /// ```
 ///
-/// # Configuration Priority
+/// ## `#[gonfig(try_from = "RawType")]`
+/// Load a validated newtype by parsing the raw type and running the field type's
+/// `TryFrom<RawType>` conversion. A failed conversion is reported as
+/// `Error::Validation` naming the field, using the conversion error's `Display`;
+/// a value that is not a valid `RawType` is an `Error::Deserialize` pointing at
+/// the field. The newtype still needs a `Deserialize` impl, typically `#[serde(try_from = "RawType")]`.
 ///
-/// Configuration sources are merged in the following priority order (later sources override earlier ones):
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// #[serde(try_from = "u16")]
+/// struct Port(u16);  // with `impl TryFrom<u16> for Port` rejecting 0
 ///
-/// 1. Default values (from `#[gonfig(default)]` attributes)
-/// 2. Configuration files (if `allow_config` is set)
-/// 3. Environment variables (always enabled)
-/// 4. CLI arguments (if `allow_cli` is set)
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(try_from = "u16")]
+///     port: Port,  // PORT=0 fails with a validation error
+/// }
+/// ```
 ///
-/// # Complete Example
+/// ## `#[gonfig(path = "dotted.path")]`
+/// Insert the field's environment value at a dotted path in the merged configuration
+/// instead of at the field's own key. Combine with `env_name` to decouple an external
+/// variable name from the internal structure.
 ///
+/// **Example:**
 /// ```rust,ignore
-/// use gonfig::Gonfig;
-/// use serde::Deserialize;
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(env_name = "DATABASE_URL", path = "database.url")]
+///     database: DatabaseConfig,  // DATABASE_URL populates database.url
+/// }
+/// ```
+///
+/// ## `#[gonfig(default = "value")]`
+/// Specify a default value for a field. The value should be a JSON-compatible string.
+/// Attribute defaults have the lowest precedence: a value passed through
+/// `ConfigBuilder::with_defaults` to `from_gonfig_with_builder` overrides them.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(default = "8080")]
+///     port: u16,
+///
+///     #[gonfig(default = r#"["localhost"]"#)]
+///     allowed_hosts: Vec<String>,
+/// }
+/// ```
+///
+/// `default = "null"` stores an explicit JSON `null`, so an `Option` field is
+/// deliberately `None` and shows up as `null` in `build_value` output. Merging
+/// keeps the `null` until a higher-priority source sets the field. A field
+/// without a default is simply absent when no source provides it.
+///
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(default = "null")]
+///     limit: Option<u32>,  // `"limit": null` in the merged value, `None`
+///
+///     #[gonfig(default = "42")]
+///     burst: Option<u32>,  // Some(42) unless a source overrides it
+/// }
+/// ```
+///
+/// ## `#[gonfig(default_variant = "Prod")]`
+/// Default an enum field (or `Option` of one) to a unit variant, named as in
+/// Rust. The variant is serialized through the enum's `Serialize` impl, so
+/// `#[serde(rename_all)]` and other renames are respected. The enum must
+/// implement `Serialize`, and a misspelled variant is a compile error. It
+/// cannot be combined with `default` or `default_env`.
+///
+/// ```rust,ignore
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Stage { Dev, Prod }
+///
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(default_variant = "Prod")]
+///     stage: Stage,  // Defaults to Stage::Prod, stored as "prod"
+/// }
+/// ```
+///
+/// ## `#[gonfig(merge = "append")]`
+/// Merge this field across sources with its own strategy: `append`, `deep`,
+/// `shallow` or `replace`. With `append`, a default array and the arrays from sources are
+/// concatenated instead of the highest-priority source replacing the rest.
+/// Fields without the attribute use the builder's strategy, which is `deep`
+/// for `from_gonfig()`.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(default = r#"["localhost"]"#, merge = "append")]
+///     allowed_hosts: Vec<String>,  // APP_ALLOWED_HOSTS='["example.com"]' → both hosts
+/// }
+/// ```
+///
+/// ## `#[gonfig(default_env = "VAR")]`
+/// Use the value the environment variable `VAR` has at compile time as the
+/// default, so build metadata such as a version or commit hash can become a
+/// runtime default. The value is treated like a `default` literal, and the
+/// derive fails to compile if `VAR` is not set. Cannot be combined with
+/// `default`.
+///
+/// Cargo does not track variables read by derive macros, so a build script
+/// providing `VAR` through `cargo:rustc-env` should also print
+/// `cargo:rerun-if-env-changed` for whatever it is derived from.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(default_env = "BUILD_VERSION")]
+///     version: String,
+/// }
+/// ```
+///
+/// ## `#[gonfig(trim_default)]`
+/// Trim surrounding whitespace from the field's `default` value before it is parsed.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     #[gonfig(default = "  my-service  ", trim_default)]
+///     service_name: String,  // Defaults to "my-service"
+/// }
+/// ```
+///
+/// ## `#[gonfig(example = "value")]`
+/// Provide an illustrative value shown in generated help, distinct from the default.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(example = "postgres://user:pass@db:5432/app")]
+///     database_url: String,
+/// }
+///
+/// // --database-url <DATABASE_URL>  [env: APP_DATABASE_URL] [example: postgres://user:pass@db:5432/app]
+/// println!("{}", Config::gonfig_help());
+/// ```
+///
+/// ## `#[gonfig(value_name = "NAME")]`
+/// Set the placeholder shown for the field's flag value in generated help. Defaults to
+/// the uppercased field name.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     #[gonfig(value_name = "SECONDS")]
+///     timeout: u64,  // --timeout <SECONDS>
+/// }
+/// ```
+///
+/// ## `#[gonfig(nested)]`
+/// Marks a field as a nested configuration struct that should be loaded automatically.
+///
+/// When a field is marked as nested, the macro automatically calls that field type's
+/// `from_gonfig()` method, allowing you to compose configuration from multiple structs
+/// with their own prefixes and loading logic.
+///
+/// **Requirements:**
+/// - Nested field types must derive `Gonfig`
+/// - Nested field types must implement `Default` or have `#[serde(default)]`
+/// - Parent struct must mark nested fields with `#[serde(default)]`
+///
+/// A nested field of type `Option<SubConfig>` is optional: it is `Some` only when the
+/// environment, CLI or config file provides at least one of `SubConfig`'s fields, and
+/// `None` otherwise. Attribute defaults alone don't make the section present.
+///
+/// **Example:**
+/// ```rust,ignore
+/// use gonfig::Gonfig;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, Gonfig)]
+/// #[Gonfig(env_prefix = "SERVER")]
+/// #[serde(default)]
+/// struct ServerConfig {
+///     #[gonfig(default = "127.0.0.1")]
+///     host: String,
+///
+///     #[gonfig(default = "8080")]
+///     port: u16,
+/// }
+///
+/// impl Default for ServerConfig {
+///     fn default() -> Self {
+///         Self { host: String::new(), port: 0 }
+///     }
+/// }
+///
+/// #[derive(Debug, Deserialize, Gonfig)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct AppConfig {
+///     #[gonfig(nested)]
+///     #[serde(default)]  // Required for nested fields
+///     server: ServerConfig,
+///
+///     #[gonfig(default = "production")]
+///     environment: String,
+/// }
+///
+/// // Automatic loading - ServerConfig loads with SERVER_ prefix
+/// let config = AppConfig::from_gonfig()?;
+/// println!("Server: {}:{}", config.server.host, config.server.port);
+/// ```
+///
+/// **Environment Variables:**
+/// - `APP_ENVIRONMENT` → AppConfig.environment
+/// - `SERVER_HOST` → ServerConfig.host (nested struct uses its own prefix)
+/// - `SERVER_PORT` → ServerConfig.port
+///
+/// ## `#[gonfig(flatten)]`
+/// Reads a nested struct's fields from the parent's namespace, without a segment
+/// for the field itself. The child is still deserialized into its own field.
+///
+/// Unlike `nested`, which loads the child separately and composes its prefix with
+/// the parent's, `flatten` registers the child's environment variables and flags
+/// on the parent's builder: `host` below is read from `APP_HOST`, not
+/// `APP_DATABASE_HOST`. The child's `#[gonfig(default)]` values still apply, and
+/// `env_name`/`cli_name` overrides on its fields are kept. The child type must
+/// derive `Gonfig`. Combining `flatten` with `nested` on the same field is a
+/// compile error, since the two disagree on where the child's variables live.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Debug, Deserialize, Gonfig)]
+/// struct DatabaseConfig {
+///     host: String,
+///     #[gonfig(default = "5432")]
+///     port: u16,
+/// }
+///
+/// #[derive(Debug, Deserialize, Gonfig)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct AppConfig {
+///     #[gonfig(flatten)]
+///     database: DatabaseConfig,  // APP_HOST, APP_PORT, --host, --port
+/// }
+/// ```
+///
+/// ## `#[skip]` or `#[skip_gonfig]`
+/// Exclude a field from configuration loading. Useful for non-serializable fields or
+/// fields that should only be set at runtime.
+///
+/// Serde must still be able to build the field without a value, so pair it with
+/// `#[serde(skip)]` or `#[serde(default)]` unless the field is an `Option` or the
+/// struct has `#[serde(default)]`. Otherwise the derive fails with a compile error.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// struct Config {
+///     database_url: String,
+///
+///     #[skip]
+///     #[serde(skip)]
+///     runtime_data: Option<String>,  // Not loaded from config sources
+/// }
+/// ```
+///
+/// ## `#[serde(flatten)]` catch-all
+/// A field marked `#[serde(flatten)]` gets no environment variable or flag of its
+/// own. Keys that no other field claims, such as extra prefixed environment
+/// variables (lowercased, prefix stripped), end up in it.
+///
+/// **Example:**
+/// ```rust,ignore
+/// #[derive(Gonfig, Deserialize)]
+/// #[Gonfig(env_prefix = "APP")]
+/// struct Config {
+///     port: u16,
+///
+///     #[serde(flatten)]
+///     extra: HashMap<String, serde_json::Value>,  // APP_FEATURE_X → "feature_x"
+/// }
+/// ```
+///
+/// # Configuration Priority
+///
+/// Configuration sources are merged in the following priority order (later sources override earlier ones):
+///
+/// 1. Default values (from `#[gonfig(default)]` attributes)
+/// 2. Configuration files (if `allow_config` is set)
+/// 3. Environment variables (enabled unless `sources` omits `env`)
+/// 4. CLI arguments (if `allow_cli` is set)
+///
+/// # Complete Example
+///
+/// ```rust,ignore
+/// use gonfig::Gonfig;
+/// use serde::Deserialize;
 ///
 /// #[derive(Debug, Deserialize, Gonfig)]
 /// #[Gonfig(env_prefix = "MYAPP", allow_cli, allow_config)]
@@ -291,19 +978,253 @@ pub fn derive_gonfig(input: TokenStream) -> TokenStream {
         Err(e) => return TokenStream::from(e.write_errors()),
     };
 
-    let expanded = generate_gonfig_impl(&opts);
-    TokenStream::from(expanded)
+    match generate_gonfig_impl(&opts) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(e) => TokenStream::from(e.write_errors()),
+    }
+}
+
+/// Whether `ty` (optionally inside `Option`) is a standard sequence type.
+fn is_sequence(ty: &syn::Type) -> bool {
+    let ty = option_inner(ty).unwrap_or(ty);
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path.path.segments.last().is_some_and(|segment| {
+        matches!(
+            segment.ident.to_string().as_str(),
+            "Vec" | "VecDeque" | "HashSet" | "BTreeSet"
+        )
+    })
+}
+
+/// The `T` of an `Option<T>` type, if `ty` is one.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Best-effort check that a `default` literal can deserialize into a primitive field type.
+///
+/// Only integer, float and `bool` types (optionally wrapped in `Option`) are checked;
+/// anything else is left to serde at runtime.
+fn check_default(ty: &syn::Type, default: &str) -> Result<(), String> {
+    let syn::Type::Path(type_path) = ty else {
+        return Ok(());
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return Ok(());
+    };
+
+    let type_name = segment.ident.to_string();
+    if type_name == "Option" {
+        if default.trim() == "null" {
+            return Ok(());
+        }
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                return check_default(inner, default);
+            }
+        }
+        return Ok(());
+    }
+
+    let value = default.trim();
+    let valid = match type_name.as_str() {
+        "u8" => value.parse::<u8>().is_ok(),
+        "u16" => value.parse::<u16>().is_ok(),
+        "u32" => value.parse::<u32>().is_ok(),
+        "u64" => value.parse::<u64>().is_ok(),
+        "u128" => value.parse::<u128>().is_ok(),
+        "usize" => value.parse::<usize>().is_ok(),
+        "i8" => value.parse::<i8>().is_ok(),
+        "i16" => value.parse::<i16>().is_ok(),
+        "i32" => value.parse::<i32>().is_ok(),
+        "i64" => value.parse::<i64>().is_ok(),
+        "i128" => value.parse::<i128>().is_ok(),
+        "isize" => value.parse::<isize>().is_ok(),
+        "f32" | "f64" => value.parse::<f64>().is_ok(),
+        "bool" => value == "true" || value == "false",
+        _ => true,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "default value `{default}` is not a valid `{type_name}`"
+        ))
+    }
+}
+
+/// Whether any `#[serde(...)]` attribute in `attrs` sets one of `options`.
+fn has_serde_option(attrs: &[syn::Attribute], options: &[&str]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .any(|attr| {
+            let mut found = false;
+            // Other serde options are not ours to validate
+            let _ = attr.parse_nested_meta(|meta| {
+                if options.iter().any(|option| meta.path.is_ident(option)) {
+                    found = true;
+                }
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::Group>()?;
+                }
+                Ok(())
+            });
+            found
+        })
+}
+
+/// The string a `#[serde(...)]` attribute in `attrs` gives `option`.
+///
+/// Reads both `option = "name"` and the deserialize half of
+/// `option(serialize = "...", deserialize = "name")`.
+fn serde_name(attrs: &[syn::Attribute], option: &str) -> Option<String> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(option) {
+                if meta.input.peek(syn::Token![=]) {
+                    found = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else {
+                    meta.parse_nested_meta(|inner| {
+                        let value = inner.value()?.parse::<syn::LitStr>()?.value();
+                        if inner.path.is_ident("deserialize") {
+                            found = Some(value);
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.input.parse::<proc_macro2::Group>()?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// How a field is exposed as a clap argument.
+enum ClapShape {
+    /// A plain `bool`, set to `true` by passing the flag.
+    Flag,
+    /// One value, parsed as the type when clap knows it.
+    Single(Option<syn::Type>),
+    /// A `Vec`, one element per occurrence of the flag.
+    Multiple(Option<syn::Type>),
+}
+
+/// Classify `ty` for the generated `clap::Command`.
+fn clap_shape(ty: &syn::Type) -> ClapShape {
+    let inner = |segment: &syn::PathSegment| match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner)) => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    // Types with a built-in clap value parser; anything else stays a string
+    let parsed = |ty: syn::Type| {
+        let syn::Type::Path(type_path) = &ty else {
+            return None;
+        };
+        let name = type_path.path.segments.last()?.ident.to_string();
+        matches!(
+            name.as_str(),
+            "u8" | "u16"
+                | "u32"
+                | "u64"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "isize"
+                | "f32"
+                | "f64"
+                | "bool"
+                | "char"
+                | "String"
+                | "PathBuf"
+        )
+        .then_some(ty)
+    };
+
+    let syn::Type::Path(type_path) = ty else {
+        return ClapShape::Single(None);
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return ClapShape::Single(None);
+    };
+    match segment.ident.to_string().as_str() {
+        "bool" => ClapShape::Flag,
+        "Option" => ClapShape::Single(inner(segment).and_then(parsed)),
+        "Vec" => ClapShape::Multiple(inner(segment).and_then(parsed)),
+        _ => ClapShape::Single(parsed(ty.clone())),
+    }
 }
 
-fn generate_gonfig_impl(opts: &GonfigOpts) -> proc_macro2::TokenStream {
+/// Quote an optional string as an `Option<String>` expression.
+fn option_tokens(value: &Option<String>) -> proc_macro2::TokenStream {
+    match value {
+        Some(v) => quote! { Some(#v.to_string()) },
+        None => quote! { None },
+    }
+}
+
+fn generate_gonfig_impl(opts: &GonfigOpts) -> darling::Result<proc_macro2::TokenStream> {
     let name = &opts.ident;
     let (impl_generics, ty_generics, where_clause) = opts.generics.split_for_impl();
 
-    let allow_env = true; // Always enable environment variables by default
-    let allow_cli = opts.allow_cli;
-    let allow_config = opts.allow_config;
+    let (allow_env, allow_cli, allow_config) = opts.enabled_sources()?;
 
     let env_prefix = opts.env_prefix.as_ref().cloned().unwrap_or_default();
+    let fallback_prefixes: Vec<String> = opts
+        .fallback_prefixes
+        .iter()
+        .flat_map(|prefixes| prefixes.split(','))
+        .map(str::trim)
+        .filter(|prefix| !prefix.is_empty())
+        .map(str::to_string)
+        .collect();
+    if !fallback_prefixes.is_empty() && env_prefix.is_empty() {
+        return Err(darling::Error::custom(
+            "`fallback_prefixes` replaces `env_prefix` for older variables; set `env_prefix` too",
+        )
+        .with_span(name));
+    }
+    let env_separator = match (&opts.separator, &opts.env_separator) {
+        (Some(_), Some(_)) => {
+            return Err(darling::Error::custom(
+                "`separator` is deprecated; set only `env_separator`",
+            )
+            .with_span(name))
+        }
+        (Some(separator), None) | (None, Some(separator)) => separator.as_str(),
+        (None, None) => "_",
+    };
+    if env_separator.is_empty() {
+        return Err(darling::Error::custom("`env_separator` must not be empty").with_span(name));
+    }
 
     let fields = opts
         .data
@@ -316,63 +1237,534 @@ fn generate_gonfig_impl(opts: &GonfigOpts) -> proc_macro2::TokenStream {
     let mut regular_mappings = Vec::new();
     let mut default_mappings = Vec::new();
     let mut nested_fields = Vec::new();
+    let mut flattened_fields = Vec::new();
     let mut all_fields = Vec::new(); // Track all fields for manual construction
+    let mut field_infos = Vec::new();
+    let mut example_infos = Vec::new();
+    let mut cli_mappings = Vec::new();
+    let mut sensitive_keys = Vec::new();
+    let mut presence_paths = Vec::new();
+    let mut env_aliases = Vec::new();
+    let mut sequence_paths = Vec::new();
+    let mut path_strategies = Vec::new();
+    let mut conversion_checks = Vec::new();
+    let mut required_checks = Vec::new();
+    let mut value_parsers = Vec::new();
+    let mut cli_keys = std::collections::HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut value_paths = Vec::new();
+    let mut catch_all_field = None;
+    let mut known_paths = Vec::new();
+    let mut clap_args = Vec::new();
+    let mut short_flags = std::collections::HashMap::new();
+    let sensitive_fields: Vec<String> = fields
+        .iter()
+        .filter(|f| f.sensitive)
+        .filter_map(|f| f.ident.as_ref().map(ToString::to_string))
+        .collect();
+
+    // A skipped field still has to deserialize; without a serde default it can't
+    let container_default = has_serde_option(&opts.attrs, &["default"]);
+    for f in fields.iter().filter(|f| f.is_skipped()) {
+        if !container_default && !f.serde_tolerates_missing() {
+            let field_name = f.ident.as_ref().unwrap();
+            return Err(darling::Error::custom(format!(
+                "`{field_name}` is skipped by gonfig but serde still expects a value for it, \
+                 so loading would fail at runtime; add `#[serde(skip)]` or `#[serde(default)]`"
+            ))
+            .with_span(field_name));
+        }
+    }
 
-    for f in fields.iter().filter(|f| !f.skip_gonfig && !f.skip) {
+    // Values are merged under the keys serde reads, which `#[serde(rename)]`
+    // and `#[serde(rename_all)]` may change from the field names
+    let serde_keys: std::collections::HashMap<String, String> = fields
+        .iter()
+        .filter_map(|f| Some((f.ident.as_ref()?.to_string(), opts.serde_key(f))))
+        .collect();
+    let mut nested_keys = Vec::new();
+
+    for f in fields.iter().filter(|f| !f.is_skipped()) {
         let field_name = f.ident.as_ref().unwrap();
         let field_str = field_name.to_string();
+        let key = serde_keys[&field_str].clone();
         let field_type = &f.ty;
 
+        if f.flatten {
+            if f.nested {
+                return Err(darling::Error::custom(
+                    "`flatten` and `nested` cannot be combined; `nested` composes prefixes \
+                     while `flatten` drops the field's own segment",
+                )
+                .with_span(field_name));
+            }
+            flattened_fields.push((key, field_type.clone()));
+            all_fields.push((field_name.clone(), false));
+            continue;
+        }
+
         // Collect nested fields for automatic loading
         if f.nested {
             nested_fields.push((field_name.clone(), field_type.clone()));
+            nested_keys.push(key);
             all_fields.push((field_name.clone(), true)); // Mark as nested
             continue;
         }
 
         all_fields.push((field_name.clone(), false)); // Mark as regular
 
-        // Note: flatten feature is not yet fully implemented
-        // For now, treat all fields as regular fields
+        // A `#[serde(flatten)]` catch-all receives the keys no other field claims,
+        // so it has no environment variable or flag of its own
+        if f.is_serde_flatten() {
+            catch_all_field = Some(field_name.clone());
+            continue;
+        }
+
+        // Every other field is read from its own environment variable and flag
         {
-            // Generate CLI argument name (kebab-case)
-            let cli_key = if let Some(custom_name) = &f.cli_name {
-                custom_name.clone()
-            } else {
-                field_str.replace('_', "-")
-            };
+            // Generate CLI argument name (kebab-case unless `rename_all` says otherwise)
+            let (env_stem, default_cli_key) = opts.field_names(&field_str)?;
+            let cli_key = f.cli_name.clone().unwrap_or(default_cli_key);
 
             // Store field info for runtime env key computation
             // We can't pre-compute env_key because it depends on composed_prefix
+            // `{placeholder}` segments are resolved at load time, so only their shape is checked here
+            if let Some(custom) = &f.env_name {
+                let mut rest = custom.as_str();
+                while let Some(start) = rest.find('{') {
+                    let end = rest[start..].find('}').ok_or_else(|| {
+                        darling::Error::custom(format!(
+                            "unterminated `{{` placeholder in `env_name = \"{custom}\"`"
+                        ))
+                        .with_span(field_name)
+                    })? + start;
+                    if rest[start + 1..end].trim().is_empty() {
+                        return Err(darling::Error::custom(format!(
+                            "empty `{{}}` placeholder in `env_name = \"{custom}\"`"
+                        ))
+                        .with_span(field_name));
+                    }
+                    rest = &rest[end + 1..];
+                }
+            }
             let custom_env_opt = if let Some(custom) = &f.env_name {
                 quote! { Some(#custom.to_string()) }
             } else {
                 quote! { None }
             };
 
+            cli_keys.insert(field_str.clone(), cli_key.clone());
+            for other in &f.conflicts_with {
+                conflicts.push((field_name.clone(), cli_key.clone(), other.clone()));
+            }
+
+            // Validated newtypes: parse the raw type, then run its `TryFrom` conversion
+            if let Some(raw) = &f.try_from {
+                let raw_ty: syn::Type = syn::parse_str(raw).map_err(|e| {
+                    darling::Error::custom(format!("invalid `try_from` type `{raw}`: {e}"))
+                        .with_span(field_name)
+                })?;
+                conversion_checks.push(quote! {
+                    if let Some(raw_value) = config_value.get(#key) {
+                        let raw: #raw_ty = ::gonfig::from_value_at(raw_value.clone(), #key)?;
+                        <#field_type as ::std::convert::TryFrom<#raw_ty>>::try_from(raw)
+                            .map_err(|e| ::gonfig::Error::Validation(
+                                format!("Invalid value for `{}`: {}", #field_str, e)
+                            ))?;
+                    }
+                });
+            }
+
+            if let Some(short) = f.cli_short {
+                if f.sensitive {
+                    return Err(darling::Error::custom(
+                        "`cli_short` cannot be used on a `sensitive` field, which has no flag",
+                    )
+                    .with_span(field_name));
+                }
+                // clap reserves `-h` for `--help`
+                if short == 'h' && cfg!(feature = "clap") {
+                    return Err(darling::Error::custom(
+                        "`-h` is reserved for `--help` by the generated clap command",
+                    )
+                    .with_span(field_name));
+                }
+                if let Some(other) = short_flags.insert(short, field_str.clone()) {
+                    return Err(darling::Error::custom(format!(
+                        "short flag `-{short}` is already used by `{other}`"
+                    ))
+                    .with_span(field_name));
+                }
+            }
+
+            // Sensitive fields never come from the command line, under either spelling
+            if f.sensitive {
+                sensitive_keys.push(cli_key.clone());
+                sensitive_keys.push(field_str.clone());
+            } else {
+                cli_mappings.push(quote! {
+                    cli = cli.with_field_mapping(field_path(#key), #cli_key);
+                });
+                if let Some(short) = f.cli_short {
+                    cli_mappings.push(quote! {
+                        cli = cli.with_short_flag(#short, #cli_key);
+                    });
+                }
+            }
+
+            // Dotted path in the merged value that the env var populates
+            let value_path = f.path.clone().unwrap_or_else(|| key.clone());
+            value_paths.push(value_path.clone());
+            known_paths.push(key.clone());
+            if value_path != key {
+                known_paths.push(value_path.clone());
+            }
+
+            if let Some(merge) = &f.merge {
+                let strategy = match merge.as_str() {
+                    "append" => quote! { ::gonfig::MergeStrategy::Append },
+                    "deep" => quote! { ::gonfig::MergeStrategy::Deep },
+                    "shallow" => quote! { ::gonfig::MergeStrategy::Shallow },
+                    "replace" => quote! { ::gonfig::MergeStrategy::Replace },
+                    other => {
+                        return Err(darling::Error::custom(format!(
+                        "unknown merge strategy `{other}`; expected `append`, `deep`, `shallow` or `replace`"
+                    ))
+                        .with_span(field_name))
+                    }
+                };
+                path_strategies.push(quote! {
+                    builder = builder.with_merge_strategy_at(#value_path, #strategy);
+                });
+            }
+
+            if let Some(parser) = &f.parse_with {
+                let parser: syn::ExprPath = syn::parse_str(parser).map_err(|e| {
+                    darling::Error::custom(format!("invalid `parse_with` function `{parser}`: {e}"))
+                        .with_span(field_name)
+                })?;
+                value_parsers.push(quote! {
+                    builder = builder.with_value_parser(#value_path, #parser);
+                });
+            }
+
+            if opts.wrap_scalars && is_sequence(field_type) {
+                sequence_paths.push(value_path.clone());
+            }
+
+            for alias in &f.env_alias {
+                env_aliases.push(quote! {
+                    env = env.with_field_alias(#value_path, #alias);
+                });
+            }
+
+            if f.presence {
+                let inner = option_inner(field_type).unwrap_or(field_type);
+                if !matches!(inner, syn::Type::Path(p) if p.path.is_ident("bool")) {
+                    return Err(darling::Error::custom(
+                        "`presence` can only be used on `bool` or `Option<bool>` fields",
+                    )
+                    .with_span(field_name));
+                }
+                presence_paths.push(value_path.clone());
+            }
+
+            if f.required {
+                if f.default.is_some() || f.default_env.is_some() || f.default_variant.is_some() {
+                    return Err(darling::Error::custom(
+                        "`required` cannot be combined with `default`; a default always provides the field",
+                    )
+                    .with_span(field_name));
+                }
+                required_checks.push(quote! {
+                    let present = #value_path
+                        .split('.')
+                        .try_fold(config_value, |current, part| current.get(part))
+                        .is_some_and(|value| !value.is_null());
+                    if !present {
+                        return Err(::gonfig::Error::MissingRequired {
+                            field: field_path(#field_str),
+                            env_key: Self::gonfig_composed_env_key(#env_stem, #custom_env_opt, composed_prefix),
+                            context: ::std::string::String::new(),
+                        });
+                    }
+                });
+            }
+
             regular_mappings.push(quote! {
                 (
-                    #field_str.to_string(),
+                    #env_stem.to_string(),
                     #custom_env_opt,
-                    #cli_key.to_string()
+                    #cli_key.to_string(),
+                    #value_path.to_string()
                 )
             });
 
-            // Handle default values
-            if let Some(default_value) = &f.default {
+            // Handle default values; `default_env` is read while the macro expands
+            let default = match (&f.default, &f.default_env) {
+                (Some(_), Some(_)) => {
+                    return Err(darling::Error::custom(
+                        "`default` and `default_env` cannot be combined",
+                    )
+                    .with_span(field_name));
+                }
+                (None, Some(var)) => Some(std::env::var(var).map_err(|_| {
+                    darling::Error::custom(format!(
+                        "`default_env` names `{var}`, which is not set at compile time"
+                    ))
+                    .with_span(field_name)
+                })?),
+                (default, None) => default.clone(),
+            };
+            let default = default.map(|default_value| {
+                if f.trim_default {
+                    default_value.trim().to_string()
+                } else {
+                    default_value
+                }
+            });
+            if let Some(default_value) = &default {
+                let default_json = if f.parse_with.is_some() {
+                    // A `parse_with` default is raw text for the parser
+                    quote! { ::serde_json::Value::String(#default_value.to_string()) }
+                } else {
+                    check_default(field_type, default_value)
+                        .map_err(|msg| darling::Error::custom(msg).with_span(field_name))?;
+                    // Try to parse as JSON first, otherwise use as string
+                    quote! {
+                        #default_value.parse::<::serde_json::Value>()
+                            .unwrap_or_else(|_| ::serde_json::Value::String(#default_value.to_string()))
+                    }
+                };
+                default_mappings.push(quote! {
+                    (#key.to_string(), #default_json)
+                });
+            }
+
+            // A `default_variant` is serialized through the enum's own `Serialize`
+            if let Some(variant_name) = &f.default_variant {
+                if default.is_some() {
+                    return Err(darling::Error::custom(
+                        "`default_variant` cannot be combined with `default` or `default_env`",
+                    )
+                    .with_span(field_name));
+                }
+                let variant = syn::parse_str::<syn::Ident>(variant_name.trim()).map_err(|_| {
+                    darling::Error::custom(format!(
+                        "`default_variant` must name a unit variant, got `{variant_name}`"
+                    ))
+                    .with_span(field_name)
+                })?;
+                let enum_ty = option_inner(field_type).unwrap_or(field_type);
                 default_mappings.push(quote! {
-                    (#field_str.to_string(), #default_value.to_string())
+                    (
+                        #key.to_string(),
+                        ::serde_json::to_value(<#enum_ty>::#variant).map_err(|e| {
+                            ::gonfig::Error::Serialization(format!(
+                                "Failed to serialize default for `{}`: {}", #field_str, e
+                            ))
+                        })?
+                    )
                 });
             }
+
+            // Static metadata for help and sample generation
+            let default_opt = option_tokens(&default.clone().or_else(|| f.default_variant.clone()));
+            let example_opt = option_tokens(&f.example);
+            let doc_opt = option_tokens(&f.doc());
+            let cli_key_opt = option_tokens(&(!f.sensitive).then(|| cli_key.clone()));
+            let cli_short_opt = match f.cli_short {
+                Some(short) => quote! { Some(#short) },
+                None => quote! { None },
+            };
+            let value_name = f
+                .value_name
+                .clone()
+                .unwrap_or_else(|| field_str.to_uppercase());
+            if !f.sensitive {
+                let (action, parser) = match clap_shape(field_type) {
+                    ClapShape::Flag => (quote! { SetTrue }, None),
+                    ClapShape::Single(parser) => (quote! { Set }, parser),
+                    ClapShape::Multiple(parser) => (quote! { Append }, parser),
+                };
+                let mut settings = Vec::new();
+                if let Some(short) = f.cli_short {
+                    settings.push(quote! { .short(#short) });
+                }
+                if let Some(parser) = parser {
+                    settings.push(quote! { .value_parser(::gonfig::clap::value_parser!(#parser)) });
+                }
+                if !matches!(clap_shape(field_type), ClapShape::Flag) {
+                    settings.push(quote! { .value_name(#value_name) });
+                }
+                // Shown in `--help`; `Cli::from_clap` ignores values clap defaulted
+                if let (ClapShape::Single(_), Some(default)) = (clap_shape(field_type), &default) {
+                    if default != "null" {
+                        settings.push(quote! { .default_value(#default) });
+                    }
+                }
+                if let Some(doc) = f.doc() {
+                    settings.push(quote! { .help(#doc) });
+                }
+                for other in f
+                    .conflicts_with
+                    .iter()
+                    .filter(|other| !sensitive_fields.contains(other))
+                {
+                    let other = serde_keys.get(other).unwrap_or(other);
+                    settings.push(quote! { .conflicts_with(field_path(#other)) });
+                }
+                clap_args.push(quote! {
+                    ::gonfig::clap::Arg::new(field_path(#key))
+                        .long(#cli_key)
+                        .action(::gonfig::clap::ArgAction::#action)
+                        #(#settings)*
+                });
+            }
+
+            field_infos.push(quote! {
+                ::gonfig::schema::FieldInfo {
+                    name: #key.to_string(),
+                    env_key: Self::gonfig_composed_env_key(#env_stem, #custom_env_opt, composed_prefix),
+                    cli_key: #cli_key_opt,
+                    default: #default_opt,
+                    example: #example_opt,
+                    doc: #doc_opt,
+                    value_name: Some(#value_name.to_string()),
+                    cli_short: #cli_short_opt,
+                }
+            });
+            // Same metadata keyed by the value path, for config file templates;
+            // custom names stay unresolved so rendering never reads the environment
+            example_infos.push(quote! {
+                ::gonfig::schema::FieldInfo {
+                    name: #value_path.to_string(),
+                    env_key: match #custom_env_opt {
+                        Some(custom) => custom,
+                        None => Self::gonfig_composed_env_key(#env_stem, None, composed_prefix),
+                    },
+                    cli_key: #cli_key_opt,
+                    default: #default_opt,
+                    example: #example_opt,
+                    doc: #doc_opt,
+                    value_name: Some(#value_name.to_string()),
+                    cli_short: #cli_short_opt,
+                }
+            });
         }
     }
 
+    // Resolve the conflict graph to CLI flag names
+    let mut conflict_pairs = Vec::new();
+    for (field_name, cli_key, other) in &conflicts {
+        let other_key = cli_keys.get(other).ok_or_else(|| {
+            darling::Error::custom(format!(
+                "`conflicts_with` refers to unknown field `{other}`"
+            ))
+            .with_span(field_name)
+        })?;
+        conflict_pairs.push(quote! {
+            cli = cli.with_conflict(#cli_key, #other_key);
+        });
+    }
+
     // Prepare nested field names and types for code generation
     let has_nested = !nested_fields.is_empty();
+    let has_conversions = !conversion_checks.is_empty();
+    if let (true, Some(field_name)) = (opts.deny_unknown_fields, &catch_all_field) {
+        return Err(darling::Error::custom(format!(
+            "`deny_unknown_fields` cannot be enforced: the `#[serde(flatten)]` catch-all \
+             `{field_name}` accepts every key"
+        ))
+        .with_span(field_name));
+    }
+    let has_catch_all = catch_all_field.is_some();
+    let deny_unknown_fields = opts.deny_unknown_fields;
+    let has_required = !required_checks.is_empty() || !flattened_fields.is_empty();
     let nested_field_names: Vec<_> = nested_fields.iter().map(|(name, _)| name).collect();
-    let nested_field_types: Vec<_> = nested_fields.iter().map(|(_, ty)| ty).collect();
+    let mut nested_inner_types = Vec::new();
+    let mut nested_loads = Vec::new();
+    for (name, ty) in &nested_fields {
+        // `Option<Sub>` is only loaded when one of its sources provides a value
+        match option_inner(ty) {
+            Some(inner) => {
+                nested_loads.push(quote! {
+                    let #name = <#inner>::gonfig_optional_with_parent_prefix(&composed_prefix)?;
+                });
+                nested_inner_types.push(inner.clone());
+            }
+            None => {
+                nested_loads.push(quote! {
+                    let #name = <#ty>::from_gonfig_with_parent_prefix(&composed_prefix)?;
+                });
+                nested_inner_types.push(ty.clone());
+            }
+        }
+    }
+
+    let flattened_names: Vec<_> = flattened_fields.iter().map(|(name, _)| name).collect();
+    let flattened_types: Vec<_> = flattened_fields.iter().map(|(_, ty)| ty).collect();
+
+    // Only generated when gonfig's `clap` feature enables ours, so non-clap
+    // builds never reference `::gonfig::clap`
+    if opts.clap && !cfg!(feature = "clap") {
+        return Err(
+            darling::Error::custom("`clap` requires the `clap` feature of gonfig").with_span(name),
+        );
+    }
+    let clap_methods = if cfg!(feature = "clap") {
+        quote! {
+            /// Clap arguments for this struct's flags, with ids under `path_prefix`.
+            #[doc(hidden)]
+            pub fn gonfig_clap_args(path_prefix: &str) -> Vec<::gonfig::clap::Arg> {
+                #[allow(unused_variables)]
+                let field_path = |field: &str| {
+                    if path_prefix.is_empty() {
+                        field.to_string()
+                    } else {
+                        format!("{}.{}", path_prefix, field)
+                    }
+                };
+                #[allow(unused_mut)]
+                let mut args = vec![#(#clap_args),*];
+                #(args.extend(<#flattened_types>::gonfig_clap_args(&field_path(#flattened_names)));)*
+                args
+            }
+
+            /// A `clap::Command` with one argument per flag, giving `--help`,
+            /// typed value parsing and clap's error messages.
+            pub fn gonfig_clap_command() -> ::gonfig::clap::Command {
+                ::gonfig::clap::Command::new(::std::env!("CARGO_PKG_NAME"))
+                    .args(Self::gonfig_clap_args(""))
+            }
+
+            /// Parse `args` with `gonfig_clap_command()` into a CLI source.
+            ///
+            /// `--help` prints the help and exits the process, like clap's
+            /// `get_matches`; other parse errors are returned as `Error::Cli`.
+            pub fn gonfig_clap_cli(args: Vec<String>) -> ::gonfig::Result<::gonfig::Cli> {
+                let command = Self::gonfig_clap_command();
+                match command.clone().try_get_matches_from(args) {
+                    Ok(matches) => Ok(::gonfig::Cli::from_clap(&command, &matches)),
+                    Err(e) if matches!(
+                        e.kind(),
+                        ::gonfig::clap::error::ErrorKind::DisplayHelp
+                            | ::gonfig::clap::error::ErrorKind::DisplayVersion
+                    ) => e.exit(),
+                    Err(e) => Err(::gonfig::Error::Cli(e.to_string())),
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let cli_source = if opts.clap {
+        quote! { Self::gonfig_clap_cli(::std::env::args().collect())? }
+    } else {
+        quote! { Self::gonfig_cli(::std::env::args().collect()) }
+    };
 
-    quote! {
+    Ok(quote! {
         impl #impl_generics #name #ty_generics #where_clause {
             pub fn from_gonfig() -> ::gonfig::Result<Self> {
                 Self::from_gonfig_with_parent_prefix("")
@@ -381,64 +1773,281 @@ fn generate_gonfig_impl(opts: &GonfigOpts) -> proc_macro2::TokenStream {
             /// Load configuration with a parent prefix for hierarchical composition.
             /// When used as a nested config, the parent prefix is automatically prepended.
             pub fn from_gonfig_with_parent_prefix(parent_prefix: &str) -> ::gonfig::Result<Self> {
-                Self::from_gonfig_with_builder_and_parent(::gonfig::ConfigBuilder::new(), parent_prefix)
+                let builder = ::gonfig::ConfigBuilder::new()
+                    .with_merge_strategy(::gonfig::MergeStrategy::Deep);
+                Self::from_gonfig_with_builder_and_parent(builder, parent_prefix)
             }
 
             pub fn from_gonfig_with_builder(builder: ::gonfig::ConfigBuilder) -> ::gonfig::Result<Self> {
                 Self::from_gonfig_with_builder_and_parent(builder, "")
             }
 
-            fn from_gonfig_with_builder_and_parent(mut builder: ::gonfig::ConfigBuilder, parent_prefix: &str) -> ::gonfig::Result<Self> {
-                // Compose prefix: parent_prefix + current env_prefix
-                let composed_prefix = if parent_prefix.is_empty() {
+            fn from_gonfig_with_builder_and_parent(builder: ::gonfig::ConfigBuilder, parent_prefix: &str) -> ::gonfig::Result<Self> {
+                let composed_prefix = Self::gonfig_compose_prefix(parent_prefix);
+                let builder = Self::gonfig_prepare_builder(builder, &composed_prefix)?;
+                Self::gonfig_build_prepared(builder, &composed_prefix)
+            }
+
+            /// Load this struct as an optional nested section: `None` unless a
+            /// source other than defaults sets one of its fields.
+            #[doc(hidden)]
+            pub fn gonfig_optional_with_parent_prefix(parent_prefix: &str) -> ::gonfig::Result<Option<Self>> {
+                let composed_prefix = Self::gonfig_compose_prefix(parent_prefix);
+                // The presence check and the build share one collection of the sources
+                let builder = ::gonfig::ConfigBuilder::new()
+                    .with_merge_strategy(::gonfig::MergeStrategy::Deep)
+                    .cache_sources(true);
+                let builder = Self::gonfig_prepare_builder(builder, &composed_prefix)?;
+
+                let present = Self::gonfig_provides_field(&builder.provided_paths()?)
+                    #(|| <#nested_inner_types>::gonfig_present_with_parent_prefix(&composed_prefix)?)*;
+                if !present {
+                    return Ok(None);
+                }
+                Self::gonfig_build_prepared(builder, &composed_prefix).map(Some)
+            }
+
+            /// Add this struct's sources, defaults and transformations to `builder`.
+            fn gonfig_prepare_builder(builder: ::gonfig::ConfigBuilder, composed_prefix: &str) -> ::gonfig::Result<::gonfig::ConfigBuilder> {
+                let composed_prefix = composed_prefix.to_string();
+                let mut builder = Self::gonfig_add_sources(builder, &composed_prefix)?;
+
+                // Default value mappings: (path, default_value)
+                let default_values = Self::gonfig_default_values()?;
+
+                // Apply default values
+                if !default_values.is_empty() {
+                    let mut defaults_json = ::serde_json::Value::Object(::serde_json::Map::new());
+                    for (path, value) in default_values {
+                        ::gonfig::transform::insert_path(&mut defaults_json, &path, value);
+                    }
+                    // Attribute defaults sit beneath any `with_defaults` value on the builder
+                    builder = builder.with_field_defaults(defaults_json);
+                }
+
+                // Fields with their own `#[gonfig(merge)]` strategy
+                #(#path_strategies)*
+
+                // Keys `deny_unknown_fields` accepts; a serde catch-all accepts any key,
+                // so no key can be rejected
+                builder = builder.with_known_fields(Self::gonfig_known_paths());
+                if #deny_unknown_fields {
+                    builder = builder.deny_unknown_fields(true);
+                }
+                if #has_catch_all && builder.denies_unknown_fields() {
+                    return Err(::gonfig::Error::Config(format!(
+                        "deny_unknown_fields cannot be enforced for {}: its #[serde(flatten)] \
+                         catch-all accepts every key",
+                        stringify!(#name)
+                    )));
+                }
+
+                // Fields read through their `#[gonfig(parse_with)]` function
+                #(#value_parsers)*
+
+                // Single values for sequence fields become one-element lists
+                let sequence_paths: &[&str] = &[#(#sequence_paths),*];
+                if !sequence_paths.is_empty() {
+                    builder = builder.transform_with(move |value| {
+                        for path in sequence_paths {
+                            ::gonfig::transform::wrap_scalar(value, path);
+                        }
+                        Ok(())
+                    });
+                }
+
+                // Missing `#[gonfig(required)]` fields name their env var instead of
+                // failing deserialization
+                if #has_required {
+                    let required_prefix = composed_prefix.clone();
+                    builder = builder.transform_with(move |value| {
+                        Self::gonfig_check_required(value, &required_prefix, "")
+                    });
+                }
+
+                Ok(builder)
+            }
+
+            /// Build a builder returned by `gonfig_prepare_builder` into `Self`.
+            #[allow(unused_variables)]
+            fn gonfig_build_prepared(builder: ::gonfig::ConfigBuilder, composed_prefix: &str) -> ::gonfig::Result<Self> {
+                if #has_nested {
+                    // Struct has nested fields - load them automatically with composed prefix
+                    // Each nested struct inherits and composes the parent's prefix
+                    #(#nested_loads)*
+
+                    // Build config value for regular fields (excluding nested fields to avoid conflicts)
+                    let mut config_value = builder.build_value()?;
+
+                    // Remove nested fields from config_value to avoid conflicts with regular field mapping
+                    if let ::serde_json::Value::Object(ref mut map) = config_value {
+                        #(
+                            map.remove(#nested_keys);
+                        )*
+                    }
+
+                    Self::gonfig_check_conversions(&config_value)?;
+
+                    // Deserialize into Self with nested fields temporarily set to default
+                    let mut result: Self = ::gonfig::from_value(config_value)?;
+
+                    // Replace nested fields with loaded values
+                    #(
+                        result.#nested_field_names = #nested_field_names;
+                    )*
+
+                    Ok(result)
+                } else if #has_conversions {
+                    // Run `try_from` conversions first so their errors surface as validation errors
+                    let config_value = builder.build_value()?;
+                    Self::gonfig_check_conversions(&config_value)?;
+                    ::gonfig::from_value(config_value)
+                } else {
+                    // No nested fields - use simple deserialization
+                    builder.build::<Self>()
+                }
+            }
+
+            /// Compose prefix: parent_prefix + current env_prefix
+            #[doc(hidden)]
+            pub fn gonfig_compose_prefix(parent_prefix: &str) -> String {
+                if parent_prefix.is_empty() {
                     #env_prefix.to_string()
                 } else if #env_prefix.is_empty() {
                     parent_prefix.to_string()
                 } else {
-                    format!("{}_{}", parent_prefix, #env_prefix)
-                };
+                    format!("{}{}{}", parent_prefix, #env_separator, #env_prefix)
+                }
+            }
+
+            /// Whether any source provides a value for this struct (defaults excluded).
+            /// Used to decide whether an optional nested section is loaded.
+            #[doc(hidden)]
+            pub fn gonfig_present_with_parent_prefix(parent_prefix: &str) -> ::gonfig::Result<bool> {
+                let composed_prefix = Self::gonfig_compose_prefix(parent_prefix);
+                let builder = ::gonfig::ConfigBuilder::new()
+                    .with_merge_strategy(::gonfig::MergeStrategy::Deep);
+                let provided = Self::gonfig_add_sources(builder, &composed_prefix)?.provided_paths()?;
 
-                // Regular field mappings: (field_name, custom_env_name, cli_key)
-                // env_key will be computed at runtime using composed_prefix
-                let field_mappings: Vec<(String, Option<String>, String)> = vec![#(#regular_mappings),*];
+                Ok(Self::gonfig_provides_field(&provided) #(|| <#nested_inner_types>::gonfig_present_with_parent_prefix(&composed_prefix)?)*)
+            }
 
-                // Default value mappings: (field_name, default_value)
-                let default_values: Vec<(String, String)> = vec![#(#default_mappings),*];
+            /// Whether `provided` (see `ConfigBuilder::provided_paths`) sets one of this struct's own fields.
+            fn gonfig_provides_field(provided: &[String]) -> bool {
+                let value_paths: &[&str] = &[#(#value_paths),*];
+                value_paths.iter().any(|path| {
+                    provided.iter().any(|provided| {
+                        provided
+                            .strip_prefix(path)
+                            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+                    })
+                })
+            }
 
-                if #allow_env {
-                    // Create custom environment source with field mappings
-                    let mut env = ::gonfig::Environment::new();
+            /// Dotted paths of every field, including nested and flattened structs.
+            /// A field with `#[gonfig(path)]` is known under its name and its path.
+            #[doc(hidden)]
+            pub fn gonfig_known_paths() -> Vec<String> {
+                #[allow(unused_mut)]
+                let mut paths: Vec<String> = vec![#(#known_paths.to_string()),*];
+                #(paths.push(#nested_keys.to_string());)*
+                #(
+                    for path in <#flattened_types>::gonfig_known_paths() {
+                        paths.push(format!("{}.{}", #flattened_names, path));
+                    }
+                )*
+                paths
+            }
 
-                    if !composed_prefix.is_empty() {
-                        env = env.with_prefix(&composed_prefix);
+            /// Field mappings `(env_stem, custom_env_name, cli_key, value_path)`,
+            /// including those of `#[gonfig(flatten)]` fields under their own path.
+            /// The env stem is the snake_case field name.
+            #[doc(hidden)]
+            pub fn gonfig_field_mappings() -> Vec<(String, Option<String>, String, String)> {
+                #[allow(unused_mut)]
+                let mut mappings: Vec<(String, Option<String>, String, String)> = vec![#(#regular_mappings),*];
+                #(
+                    for (field_name, custom_env_name, cli_key, value_path) in <#flattened_types>::gonfig_field_mappings() {
+                        mappings.push((field_name, custom_env_name, cli_key, format!("{}.{}", #flattened_names, value_path)));
                     }
+                )*
+                mappings
+            }
 
-                    // Apply field-level mappings for regular fields
-                    // Compute env_key at runtime using composed_prefix
-                    for (field_name, custom_env_name, _cli_key) in &field_mappings {
-                        let env_key = if let Some(custom) = custom_env_name {
-                            custom.clone()
-                        } else if !composed_prefix.is_empty() {
-                            format!("{}_{}", composed_prefix, field_name.to_uppercase())
-                        } else {
-                            field_name.to_uppercase()
-                        };
-                        env = env.with_field_mapping(field_name, &env_key);
+            /// Attribute defaults keyed by dotted path, including those of
+            /// `#[gonfig(flatten)]` fields.
+            #[doc(hidden)]
+            pub fn gonfig_default_values() -> ::gonfig::Result<Vec<(String, ::serde_json::Value)>> {
+                #[allow(unused_mut)]
+                let mut defaults: Vec<(String, ::serde_json::Value)> = vec![#(#default_mappings),*];
+                #(
+                    for (path, value) in <#flattened_types>::gonfig_default_values()? {
+                        defaults.push((format!("{}.{}", #flattened_names, path), value));
                     }
+                )*
+                Ok(defaults)
+            }
 
-                    builder = builder.with_env_custom(env);
+            /// Environment source reading this struct's fields under `composed_prefix`.
+            ///
+            /// An `env_name` whose placeholders cannot be resolved is mapped
+            /// verbatim and returned with the resolution error, so each caller
+            /// decides how to report it.
+            fn gonfig_env_source(composed_prefix: &str) -> (::gonfig::Environment, Vec<(String, ::gonfig::Error)>) {
+                let mut env = ::gonfig::Environment::new();
+                let mut unresolved = Vec::new();
+
+                if !composed_prefix.is_empty() {
+                    // Fallbacks take the place of `env_prefix` after any parent prefix
+                    let parent = composed_prefix.strip_suffix(#env_prefix).unwrap_or_default();
+                    let fallback_prefixes: &[&str] = &[#(#fallback_prefixes),*];
+                    env = env.with_prefixes(
+                        ::std::iter::once(composed_prefix.to_string())
+                            .chain(fallback_prefixes.iter().map(|prefix| format!("{parent}{prefix}"))),
+                    );
                 }
+                env = env.separator(#env_separator);
 
-                if #allow_cli {
-                    // Create custom CLI source with field mappings
-                    let mut cli = ::gonfig::Cli::from_args();
+                // Apply field-level mappings for regular fields
+                // Compute env_key at runtime using composed_prefix
+                for (field_name, custom_env_name, _cli_key, value_path) in &Self::gonfig_field_mappings() {
+                    let env_key = if let Some(custom) = custom_env_name {
+                        match ::gonfig::environment::resolve_env_name(custom) {
+                            Ok(resolved) => resolved,
+                            Err(e) => {
+                                unresolved.push((custom.clone(), e));
+                                custom.clone()
+                            }
+                        }
+                    } else if !composed_prefix.is_empty() {
+                        format!("{}{}{}", composed_prefix, #env_separator, field_name.to_uppercase())
+                    } else {
+                        field_name.to_uppercase()
+                    };
+                    env = env.with_field_mapping(value_path, &env_key);
+                }
 
-                    // Apply field-level CLI mappings for regular fields
-                    for (field_name, _custom_env_name, cli_key) in &field_mappings {
-                        cli = cli.with_field_mapping(field_name, cli_key);
+                // Flags that are on whenever their variable is set
+                #(env = env.presence_flag(#presence_paths);)*
+
+                // Deprecated or alternative names for mapped fields
+                #(#env_aliases)*
+
+                (env, unresolved)
+            }
+
+            /// Register the environment, CLI and config file sources for this struct.
+            fn gonfig_add_sources(mut builder: ::gonfig::ConfigBuilder, composed_prefix: &str) -> ::gonfig::Result<::gonfig::ConfigBuilder> {
+                if #allow_env {
+                    let (env, unresolved) = Self::gonfig_env_source(composed_prefix);
+                    if let Some((_, e)) = unresolved.into_iter().next() {
+                        return Err(e);
                     }
+                    builder = builder.with_env_custom(env);
+                }
 
-                    builder = builder.with_cli_custom(cli);
+                if #allow_cli {
+                    builder = builder.with_cli_custom(#cli_source);
                 }
 
                 if #allow_config {
@@ -464,96 +2073,169 @@ fn generate_gonfig_impl(opts: &GonfigOpts) -> proc_macro2::TokenStream {
                     }
                 }
 
-                // Apply default values
-                if !default_values.is_empty() {
-                    let mut defaults_json = ::serde_json::Map::new();
-                    for (field_name, default_value) in default_values {
-                        // Try to parse as JSON first, otherwise use as string
-                        let value = default_value.parse::<::serde_json::Value>()
-                            .unwrap_or_else(|_| ::serde_json::Value::String(default_value));
-                        defaults_json.insert(field_name, value);
-                    }
-                    builder = builder.with_defaults(::serde_json::Value::Object(defaults_json))?;
-                }
-
-                // Build the final configuration
-                if #has_nested {
-                    // Struct has nested fields - load them automatically with composed prefix
-                    // Each nested struct inherits and composes the parent's prefix
-                    #(
-                        let #nested_field_names = <#nested_field_types>::from_gonfig_with_parent_prefix(&composed_prefix)?;
-                    )*
+                Ok(builder)
+            }
 
-                    // Build config value for regular fields (excluding nested fields to avoid conflicts)
-                    let mut config_value = builder.build_value()?;
+            /// Check `#[gonfig(try_from = "...")]` fields by running their conversions.
+            #[allow(unused_variables)]
+            fn gonfig_check_conversions(config_value: &::serde_json::Value) -> ::gonfig::Result<()> {
+                #(#conversion_checks)*
+                Ok(())
+            }
 
-                    // Remove nested fields from config_value to avoid conflicts with regular field mapping
-                    if let ::serde_json::Value::Object(ref mut map) = config_value {
-                        #(
-                            map.remove(stringify!(#nested_field_names));
-                        )*
+            /// Fail with `Error::MissingRequired` for the first `#[gonfig(required)]`
+            /// field missing from `config_value`, including those of
+            /// `#[gonfig(flatten)]` fields. Field names are reported under `path_prefix`.
+            #[doc(hidden)]
+            #[allow(unused_variables)]
+            pub fn gonfig_check_required(
+                config_value: &::serde_json::Value,
+                composed_prefix: &str,
+                path_prefix: &str,
+            ) -> ::gonfig::Result<()> {
+                let field_path = |field: &str| {
+                    if path_prefix.is_empty() {
+                        field.to_string()
+                    } else {
+                        format!("{}.{}", path_prefix, field)
                     }
+                };
+                #(#required_checks)*
+                #(
+                    <#flattened_types>::gonfig_check_required(
+                        config_value.get(#flattened_names).unwrap_or(&::serde_json::Value::Null),
+                        composed_prefix,
+                        &field_path(#flattened_names),
+                    )?;
+                )*
+                Ok(())
+            }
 
-                    // Deserialize into Self with nested fields temporarily set to default
-                    let mut result: Self = ::serde_json::from_value(config_value)
-                        .map_err(|e| ::gonfig::Error::Serialization(
-                            format!("Failed to deserialize config: {}", e)
-                        ))?;
-
-                    // Replace nested fields with loaded values
-                    #(
-                        result.#nested_field_names = #nested_field_names;
-                    )*
-
-                    Ok(result)
+            /// Environment variable name for a field under `composed_prefix`,
+            /// as read by the environment source. Used for messages and help, so
+            /// an unresolvable `env_name` placeholder is shown as written.
+            fn gonfig_composed_env_key(field_name: &str, custom_env_name: Option<String>, composed_prefix: &str) -> String {
+                if let Some(custom) = custom_env_name {
+                    ::gonfig::environment::resolve_env_name(&custom).unwrap_or(custom)
+                } else if !composed_prefix.is_empty() {
+                    format!("{}{}{}", composed_prefix, #env_separator, field_name.to_uppercase())
                 } else {
-                    // No nested fields - use simple deserialization
-                    builder.build::<Self>()
+                    field_name.to_uppercase()
                 }
             }
 
-            pub fn gonfig_builder() -> ::gonfig::ConfigBuilder {
-                let mut builder = ::gonfig::ConfigBuilder::new();
+            /// CLI source over `args` with this struct's flag mappings applied.
+            /// Flags of `#[gonfig(sensitive)]` fields are ignored and
+            /// `#[gonfig(conflicts_with)]` pairs are rejected when both are passed.
+            pub fn gonfig_cli(args: Vec<String>) -> ::gonfig::Cli {
+                Self::gonfig_register_cli(::gonfig::Cli::from_vec(args), "")
+            }
 
-                // Regular field mappings: (field_name, custom_env_name, cli_key)
-                let field_mappings: Vec<(String, Option<String>, String)> = vec![#(#regular_mappings),*];
+            /// Apply this struct's flag mappings to `cli`, reporting values under
+            /// `path_prefix` (used for `#[gonfig(flatten)]` fields).
+            #[doc(hidden)]
+            pub fn gonfig_register_cli(mut cli: ::gonfig::Cli, path_prefix: &str) -> ::gonfig::Cli {
+                #[allow(unused_variables)]
+                let field_path = |field: &str| {
+                    if path_prefix.is_empty() {
+                        field.to_string()
+                    } else {
+                        format!("{}.{}", path_prefix, field)
+                    }
+                };
+                #(#cli_mappings)*
+                #(cli = cli.exclude(#sensitive_keys);)*
+                #(#conflict_pairs)*
+                #(cli = <#flattened_types>::gonfig_register_cli(cli, &field_path(#flattened_names));)*
+                cli
+            }
 
-                // Use env_prefix directly (no parent composition in builder method)
-                let prefix = #env_prefix;
+            #clap_methods
 
-                if #allow_env {
-                    // Create custom environment source with field mappings
-                    let mut env = ::gonfig::Environment::new();
+            /// Static metadata for every configurable (non-skipped, non-nested) field,
+            /// including those of `#[gonfig(flatten)]` fields under their dotted path.
+            pub fn gonfig_fields() -> Vec<::gonfig::schema::FieldInfo> {
+                Self::gonfig_fields_under(&Self::gonfig_compose_prefix(""))
+            }
 
-                    if !prefix.is_empty() {
-                        env = env.with_prefix(prefix);
+            /// `gonfig_fields` with environment variables under the already
+            /// composed `composed_prefix`.
+            #[doc(hidden)]
+            pub fn gonfig_fields_under(composed_prefix: &str) -> Vec<::gonfig::schema::FieldInfo> {
+                #[allow(unused_mut)]
+                let mut fields: Vec<::gonfig::schema::FieldInfo> = vec![#(#field_infos),*];
+                // Flattened fields are read under this struct's prefix
+                #(
+                    for mut field in <#flattened_types>::gonfig_fields_under(composed_prefix) {
+                        field.name = format!("{}.{}", #flattened_names, field.name);
+                        fields.push(field);
                     }
+                )*
+                fields
+            }
 
-                    // Apply field-level mappings for regular fields
-                    for (field_name, custom_env_name, _cli_key) in &field_mappings {
-                        let env_key = if let Some(custom) = custom_env_name {
-                            custom.clone()
-                        } else if !prefix.is_empty() {
-                            format!("{}_{}", prefix, field_name.to_uppercase())
-                        } else {
-                            field_name.to_uppercase()
-                        };
-                        env = env.with_field_mapping(field_name, &env_key);
+            /// Field metadata keyed by dotted value path, with environment variables
+            /// under the already composed `composed_prefix`, including nested and
+            /// flattened structs.
+            #[doc(hidden)]
+            pub fn gonfig_example_fields(composed_prefix: &str) -> Vec<::gonfig::schema::FieldInfo> {
+                #[allow(unused_mut)]
+                let mut fields: Vec<::gonfig::schema::FieldInfo> = vec![#(#example_infos),*];
+                // Flattened fields are read under this struct's prefix
+                #(
+                    for mut field in <#flattened_types>::gonfig_example_fields(composed_prefix) {
+                        field.name = format!("{}.{}", #flattened_names, field.name);
+                        fields.push(field);
+                    }
+                )*
+                #(
+                    let nested_prefix = <#nested_inner_types>::gonfig_compose_prefix(composed_prefix);
+                    for mut field in <#nested_inner_types>::gonfig_example_fields(&nested_prefix) {
+                        field.name = format!("{}.{}", #nested_keys, field.name);
+                        fields.push(field);
                     }
+                )*
+                fields
+            }
 
-                    builder = builder.with_env_custom(env);
-                }
+            /// Render a template config file in `format` listing every configurable
+            /// field, including nested structs as sections, with its default or a
+            /// placeholder and its environment variable. Does not read the environment.
+            pub fn gonfig_example(format: ::gonfig::ConfigFormat) -> String {
+                let fields = Self::gonfig_example_fields(&Self::gonfig_compose_prefix(""));
+                ::gonfig::schema::render_config_example(&fields, format)
+            }
 
-                if #allow_cli {
-                    // Create custom CLI source with field mappings
-                    let mut cli = ::gonfig::Cli::from_args();
+            /// Render `--help` style text describing every configurable field.
+            pub fn gonfig_help() -> String {
+                ::gonfig::schema::render_help(&Self::gonfig_fields())
+            }
+
+            /// Render a `.env.example` file listing every configurable field's
+            /// environment variable with its default and doc comment.
+            pub fn gonfig_env_example() -> String {
+                ::gonfig::schema::render_env_example(&Self::gonfig_fields())
+            }
 
-                    // Apply field-level CLI mappings for regular fields
-                    for (field_name, _custom_env_name, cli_key) in &field_mappings {
-                        cli = cli.with_field_mapping(field_name, cli_key);
+            pub fn gonfig_builder() -> ::gonfig::ConfigBuilder {
+                let mut builder = ::gonfig::ConfigBuilder::new()
+                    .with_merge_strategy(::gonfig::MergeStrategy::Deep);
+
+                if #allow_env {
+                    // Use env_prefix directly (no parent composition in builder method)
+                    let (env, unresolved) = Self::gonfig_env_source(#env_prefix);
+                    for (custom, _) in &unresolved {
+                        // Building fails naming the unset placeholder variables
+                        let vars = ::gonfig::environment::env_name_placeholders(custom)
+                            .unwrap_or_default();
+                        let vars: Vec<&str> = vars.iter().map(String::as_str).collect();
+                        builder = builder.with_required_env(&vars);
                     }
+                    builder = builder.with_env_custom(env);
+                }
 
-                    builder = builder.with_cli_custom(cli);
+                if #allow_cli {
+                    builder = builder.with_cli_custom(Self::gonfig_cli(::std::env::args().collect()));
                 }
 
                 // Note: Config file loading and defaults are not supported in gonfig_builder()
@@ -563,5 +2245,5 @@ fn generate_gonfig_impl(opts: &GonfigOpts) -> proc_macro2::TokenStream {
                 builder
             }
         }
-    }
+    })
 }
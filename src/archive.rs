@@ -0,0 +1,154 @@
+//! Configuration source reading a single entry from a zip or tar bundle.
+
+use crate::{
+    config::ConfigFormat,
+    error::{Error, Result},
+    source::{ConfigSource, Source},
+};
+use serde_json::Value;
+use std::any::Any;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Configuration source backed by one entry of an archive.
+///
+/// This lets a multi-file configuration pack be distributed as a single
+/// `.zip` or `.tar` artifact. The entry is read and parsed once, when the source
+/// is created, and then behaves like a configuration file.
+///
+/// Requires the `archive` feature.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gonfig::archive::ArchiveSource;
+/// use gonfig::ConfigFormat;
+///
+/// let source = ArchiveSource::from_path("bundle.zip", "config.yaml", ConfigFormat::Yaml)?;
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchiveSource {
+    entry: String,
+    data: Value,
+}
+
+impl ArchiveSource {
+    /// Load `entry` from the archive at `archive_path`.
+    ///
+    /// The archive type is detected from the extension: `.zip` for zip files and
+    /// `.tar` for uncompressed tarballs.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Config`] if the archive type is not recognized, the archive is
+    ///   corrupt, or the entry does not exist
+    /// - [`Error::Io`] if the archive cannot be opened
+    /// - [`Error::Serialization`] if the entry cannot be parsed
+    pub fn from_path(
+        archive_path: impl AsRef<Path>,
+        entry: impl Into<String>,
+        format: ConfigFormat,
+    ) -> Result<Self> {
+        let archive_path = archive_path.as_ref();
+        let extension = archive_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+
+        match extension.as_deref() {
+            Some("zip") => Self::from_zip_reader(open(archive_path)?, entry, format),
+            Some("tar") => Self::from_tar_reader(open(archive_path)?, entry, format),
+            _ => Err(Error::Config(format!(
+                "Unknown archive format for file: {archive_path:?}"
+            ))),
+        }
+    }
+
+    /// Load `entry` from a zip archive read from `reader`.
+    pub fn from_zip_reader<R: Read + Seek>(
+        reader: R,
+        entry: impl Into<String>,
+        format: ConfigFormat,
+    ) -> Result<Self> {
+        let entry = entry.into();
+        let mut archive = zip::ZipArchive::new(reader)
+            .map_err(|e| Error::Config(format!("Failed to open zip archive: {e}")))?;
+
+        let mut file = archive.by_name(&entry).map_err(|e| {
+            Error::Config(format!(
+                "Failed to read entry {entry} from zip archive: {e}"
+            ))
+        })?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        Self::parse(entry, &content, format)
+    }
+
+    /// Load `entry` from an uncompressed tar archive read from `reader`.
+    pub fn from_tar_reader<R: Read>(
+        reader: R,
+        entry: impl Into<String>,
+        format: ConfigFormat,
+    ) -> Result<Self> {
+        let entry = entry.into();
+        let mut archive = tar::Archive::new(reader);
+
+        for file in archive.entries()? {
+            let mut file = file?;
+            if file.path()?.as_ref() == Path::new(&entry) {
+                let mut content = String::new();
+                file.read_to_string(&mut content)?;
+                return Self::parse(entry, &content, format);
+            }
+        }
+
+        Err(Error::Config(format!(
+            "Entry {entry} not found in tar archive"
+        )))
+    }
+
+    fn parse(entry: String, content: &str, format: ConfigFormat) -> Result<Self> {
+        let data = format.parse(content)?;
+        Ok(Self { entry, data })
+    }
+
+    /// Name of the archive entry this source was loaded from.
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+}
+
+fn open(path: &Path) -> Result<File> {
+    File::open(path).map_err(|e| Error::io_at(path, e))
+}
+
+impl ConfigSource for ArchiveSource {
+    fn source_type(&self) -> Source {
+        Source::ConfigFile
+    }
+
+    fn collect(&self) -> Result<Value> {
+        Ok(self.data.clone())
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        key.split('.')
+            .try_fold(&self.data, |current, part| current.get(part))
+            .cloned()
+    }
+
+    fn describe(&self) -> String {
+        format!("archive entry {}", self.entry)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
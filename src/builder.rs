@@ -3,18 +3,44 @@
 use crate::{
     cli::Cli,
     config::{Config, ConfigFormat},
-    environment::Environment,
+    environment::{Case, Environment},
     error::{Error, Result},
-    merge::{ConfigMerger, MergeStrategy},
-    source::ConfigSource,
+    interpolate::Interpolation,
+    merge::{MergeStrategy, MergeTrace},
+    reload::ConfigDiff,
+    source::{ConfigSource, Source},
+    transform::ValueParser,
 };
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, DeserializeSeed};
 use serde_json::Value;
-use std::path::Path;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(feature = "watch")]
+use crate::watch::WatchedConfig;
+
+/// Environment variable listing configuration files to skip while loading.
+///
+/// Set it to a comma-separated list such as `base.yaml,override.yaml` to
+/// leave those files out of every build without changing code, e.g. to
+/// bisect which file introduces a bad value. An entry matches a file whose
+/// path ends with it, so `base.yaml` matches `config/base.yaml`, and
+/// `config/base.yaml` only matches the file in that directory. Skipped files
+/// are logged.
+pub const SKIP_FILES_VAR: &str = "GONFIG_SKIP_FILES";
 
 /// Type alias for validation functions to reduce complexity.
 type ValidationFn = Box<dyn Fn(&Value) -> Result<()>>;
 
+/// Type alias for transformations of the merged configuration.
+type TransformFn = Box<dyn Fn(&mut Value) -> Result<()>>;
+
+/// Type alias for callbacks run after a successful reload.
+type ReloadFn = Box<dyn Fn(&Value, &ConfigDiff) -> Result<()> + Send + Sync>;
+
 /// Builder for assembling configuration from multiple sources.
 ///
 /// The `ConfigBuilder` allows you to combine environment variables, config files,
@@ -35,16 +61,39 @@ type ValidationFn = Box<dyn Fn(&Value) -> Result<()>>;
 /// # fn example() -> gonfig::Result<()> {
 /// let config: Config = ConfigBuilder::new()
 ///     .with_merge_strategy(MergeStrategy::Deep)
-///     .with_env("APP")
+///     .with_env_flat("APP")
 ///     .with_cli()
 ///     .build()?;
 /// # Ok(())
 /// # }
 /// ```
 pub struct ConfigBuilder {
-    sources: Vec<Box<dyn ConfigSource>>,
+    sources: SourceList,
     merge_strategy: MergeStrategy,
     validate: Option<ValidationFn>,
+    transforms: Vec<TransformFn>,
+    resilient: bool,
+    env_over_cli: bool,
+    strict_types: bool,
+    env_prefix_case: Option<Case>,
+    fallbacks: Vec<ConfigBuilder>,
+    cache_sources: bool,
+    cached: Mutex<Option<Vec<(Value, Source)>>>,
+    /// Sources collected by [`build_as`](ConfigBuilder::build_as), kept apart
+    /// from `cached` so that they never stand in for a `cache_sources` build.
+    snapshot: Mutex<Option<Vec<(Value, Source)>>>,
+    interpolation: Option<Interpolation>,
+    required: Vec<String>,
+    required_env: Vec<String>,
+    deny_unknown: bool,
+    known_fields: Option<Vec<String>>,
+    path_strategies: Vec<(String, MergeStrategy)>,
+    value_parsers: Vec<(String, ValueParser)>,
+    source_strategies: Vec<(Source, MergeStrategy)>,
+    timeout: Option<Duration>,
+    reload_callbacks: Vec<ReloadFn>,
+    #[cfg(all(feature = "signal", unix))]
+    reload_on_sighup: bool,
 }
 
 impl Default for ConfigBuilder {
@@ -57,9 +106,30 @@ impl ConfigBuilder {
     /// Create a new configuration builder.
     pub fn new() -> Self {
         Self {
-            sources: Vec::new(),
+            sources: SourceList::default(),
             merge_strategy: MergeStrategy::Deep,
             validate: None,
+            transforms: Vec::new(),
+            resilient: false,
+            env_over_cli: false,
+            strict_types: false,
+            env_prefix_case: None,
+            fallbacks: Vec::new(),
+            cache_sources: false,
+            cached: Mutex::new(None),
+            snapshot: Mutex::new(None),
+            interpolation: None,
+            required: Vec::new(),
+            required_env: Vec::new(),
+            deny_unknown: false,
+            known_fields: None,
+            path_strategies: Vec::new(),
+            value_parsers: Vec::new(),
+            source_strategies: Vec::new(),
+            timeout: None,
+            reload_callbacks: Vec::new(),
+            #[cfg(all(feature = "signal", unix))]
+            reload_on_sighup: false,
         }
     }
 
@@ -78,6 +148,295 @@ impl ConfigBuilder {
         self
     }
 
+    /// The strategy sources are merged with, [`MergeStrategy::Deep`] unless
+    /// changed with [`with_merge_strategy`](ConfigBuilder::with_merge_strategy).
+    pub fn merge_strategy(&self) -> MergeStrategy {
+        self.merge_strategy
+    }
+
+    /// Use a different merge strategy for the value at a dotted `path`.
+    ///
+    /// The value at `path` is merged across all sources with `strategy`,
+    /// while everything else uses the builder's strategy. With
+    /// [`MergeStrategy::Append`], arrays from every source are concatenated in
+    /// priority order, so defaults are kept and sources add to them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, MergeStrategy};
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "hosts": ["a"], "ports": [80] }))?
+    ///     .with_env_custom(
+    ///         Environment::new()
+    ///             .override_with("HOSTS", r#"["b"]"#)
+    ///             .override_with("PORTS", "[443]"),
+    ///     )
+    ///     .with_merge_strategy_at("hosts", MergeStrategy::Append)
+    ///     .build_value()?;
+    ///
+    /// assert_eq!(value["hosts"], serde_json::json!(["a", "b"]));
+    /// assert_eq!(value["ports"], serde_json::json!([443]));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_merge_strategy_at(
+        mut self,
+        path: impl Into<String>,
+        strategy: MergeStrategy,
+    ) -> Self {
+        self.path_strategies.push((path.into(), strategy));
+        self
+    }
+
+    /// Use a different merge strategy for every source of one type.
+    ///
+    /// When a source of type `source` is merged into the configuration
+    /// collected from lower-priority sources, `strategy` is used instead of
+    /// the builder's [merge strategy](ConfigBuilder::with_merge_strategy).
+    /// Source types without their own strategy fall back to the builder's
+    /// strategy, and a later call for the same type replaces an earlier one.
+    /// Strategies set for a path with
+    /// [`with_merge_strategy_at`](ConfigBuilder::with_merge_strategy_at) still
+    /// take precedence at that path.
+    ///
+    /// Note that [`MergeStrategy::Replace`] replaces everything merged so far:
+    /// a CLI source using it becomes the whole configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, MergeStrategy, Source};
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "hosts": ["a"], "port": 80 }))?
+    ///     .with_env_custom(
+    ///         Environment::new()
+    ///             .with_prefix("APP")
+    ///             .override_with("APP_HOSTS", r#"["b"]"#),
+    ///     )
+    ///     .merge_strategy_for(Source::Environment, MergeStrategy::Append)
+    ///     .build_value()?;
+    ///
+    /// assert_eq!(value["hosts"], serde_json::json!(["a", "b"]));
+    /// assert_eq!(value["port"], 80);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn merge_strategy_for(mut self, source: Source, strategy: MergeStrategy) -> Self {
+        self.source_strategies.push((source, strategy));
+        self
+    }
+
+    /// Tolerate failures of remote sources.
+    ///
+    /// When enabled, a [`Source::Remote`] source (such as AWS SSM) that fails to
+    /// collect is logged with `tracing::warn!` and skipped, so the configuration is
+    /// built from the remaining sources. Failures of local sources (files,
+    /// environment, CLI) are always reported. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::new().resilient(true);
+    /// ```
+    pub fn resilient(mut self, resilient: bool) -> Self {
+        self.resilient = resilient;
+        self
+    }
+
+    /// Swap the precedence of environment variables and CLI arguments.
+    ///
+    /// By default CLI arguments have the highest priority and override
+    /// environment variables. When `env_wins` is `true`, environment variables
+    /// take the CLI's place at the top and CLI arguments drop to the
+    /// environment's slot, below remote sources. Other priorities are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// // Environment variables override CLI flags
+    /// let builder = ConfigBuilder::new()
+    ///     .with_env("APP")
+    ///     .with_cli()
+    ///     .with_cli_env_override(true);
+    /// ```
+    pub fn with_cli_env_override(mut self, env_wins: bool) -> Self {
+        self.env_over_cli = env_wins;
+        self
+    }
+
+    /// Collect each source at most once and reuse the values afterwards.
+    ///
+    /// Without caching, every call to [`collect_value`](ConfigBuilder::collect_value),
+    /// [`build_value`](ConfigBuilder::build_value) or [`build`](ConfigBuilder::build)
+    /// collects all sources again, which means scanning the whole process
+    /// environment each time. With caching enabled, the first call collects and
+    /// later calls reuse the result until [`clear_source_cache`] is called.
+    /// Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::new().with_env("APP").cache_sources(true);
+    /// let preview = builder.collect_value()?;
+    /// let value = builder.build_value()?; // reuses the collected sources
+    /// assert_eq!(preview, value);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`clear_source_cache`]: ConfigBuilder::clear_source_cache
+    pub fn cache_sources(mut self, cache: bool) -> Self {
+        self.cache_sources = cache;
+        self
+    }
+
+    /// Drop values cached by [`cache_sources`](ConfigBuilder::cache_sources)
+    /// and [`build_as`](ConfigBuilder::build_as), so the next build collects
+    /// every source again.
+    pub fn clear_source_cache(&self) {
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        *self.snapshot.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Require environment values to match field types without coercion.
+    ///
+    /// When enabled, every [`Environment`] source is collected with
+    /// [`Environment::strict_types`], so its values stay strings and a field
+    /// of another type fails to deserialize with an error naming the field
+    /// path. Applies to environment sources added before or after this call.
+    /// Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let err = ConfigBuilder::new()
+    ///     .with_env_custom(Environment::new().override_with("PORT", "8080"))
+    ///     .strict_types(true)
+    ///     .build::<Config>()
+    ///     .unwrap_err();
+    /// assert!(err.to_string().contains("port"));
+    /// ```
+    pub fn strict_types(mut self, strict: bool) -> Self {
+        self.strict_types = strict;
+        self
+    }
+
+    /// Case the prefix of every environment source independently of its keys.
+    ///
+    /// Applies [`Environment::prefix_case`] to [`Environment`] sources added
+    /// before or after this call, so a case-sensitive source configured with
+    /// a lowercase prefix can still match conventional uppercase variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::environment::Case;
+    /// use gonfig::{ConfigBuilder, Environment};
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_env_custom(
+    ///         Environment::new()
+    ///             .with_prefix("app")
+    ///             .case_sensitive(true)
+    ///             .override_with("APP_port", "8080"),
+    ///     )
+    ///     .with_env_prefix_case(Case::Upper)
+    ///     .build_value()?;
+    /// assert_eq!(value["port"], 8080);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_env_prefix_case(mut self, case: Case) -> Self {
+        self.env_prefix_case = Some(case);
+        self
+    }
+
+    /// Limit how long each remote source may take to collect.
+    ///
+    /// Remote sources (such as [`with_ssm`](Self::with_ssm) or a custom
+    /// [`Source::Remote`] source) are collected on a separate thread. When one
+    /// does not answer within `timeout`, collection fails with
+    /// [`Error::Timeout`], or skips that source when the builder is
+    /// [`resilient`](Self::resilient). Local sources are not affected.
+    ///
+    /// The thread of a source that times out is not joined or cancelled: it
+    /// keeps running until the source's `collect` returns, and its result is
+    /// then dropped. A source that can block indefinitely should also bound its
+    /// own calls, as the HTTP-based sources do.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_env("APP")
+    ///     .with_timeout(Duration::from_secs(5));
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Collect one source, applying builder-wide source settings.
+    fn collect_source(&self, source: &Arc<dyn ConfigSource>) -> Result<Value> {
+        let parsed = !self.value_parsers.is_empty();
+        if self.strict_types || self.env_prefix_case.is_some() || parsed {
+            if let Some(env) = source.as_any().downcast_ref::<Environment>() {
+                let mut env = env.clone();
+                if self.strict_types {
+                    env = env.strict_types(true);
+                }
+                if let Some(case) = self.env_prefix_case {
+                    env = env.prefix_case(case);
+                }
+                for (path, parser) in &self.value_parsers {
+                    env.set_value_parser(path.clone(), parser.clone());
+                }
+                return env.collect();
+            }
+        }
+        if let Some(cli) = source.as_any().downcast_ref::<Cli>() {
+            return cli.collect_fields(&self.value_parsers);
+        }
+        if let Some(dotenv) = source.as_any().downcast_ref::<crate::dotenv::DotEnv>() {
+            return dotenv.collect_parsed(&self.value_parsers);
+        }
+        let mut value = match self.timeout {
+            Some(timeout) if source.source_type() == Source::Remote => {
+                collect_with_timeout(Arc::clone(source), timeout)
+            }
+            _ => source.collect(),
+        }?;
+        for (path, parser) in &self.value_parsers {
+            parser.parse_at(&mut value, path)?;
+        }
+        Ok(value)
+    }
+
+    /// Merge priority of a source type under this builder's precedence policy.
+    fn priority_of(&self, source_type: Source) -> u8 {
+        match source_type {
+            Source::Environment if self.env_over_cli => Source::Cli.priority(),
+            Source::Cli if self.env_over_cli => Source::Environment.priority(),
+            other => other.priority(),
+        }
+    }
+
     /// Add a custom configuration source.
     ///
     /// This method allows you to add any type that implements the [`ConfigSource`] trait.
@@ -94,15 +453,59 @@ impl ConfigBuilder {
     ///     .add_source(Box::new(env_source));
     /// ```
     pub fn add_source(mut self, source: Box<dyn ConfigSource>) -> Self {
-        self.sources.push(source);
+        self.sources.push(Arc::from(source));
+        self.clear_source_cache();
         self
     }
 
-    /// Add environment variables with a prefix.
+    /// Add environment variables with a prefix, nested on the separator.
     ///
     /// This is a convenience method that creates an [`Environment`] source with the
-    /// specified prefix and default separator (`_`). Environment variables will be
-    /// matched using the pattern `{PREFIX}_{FIELD_NAME}`.
+    /// specified prefix, default separator (`_`) and
+    /// [`nested`](Environment::nested) mode, so `APP_HTTP_PORT` populates
+    /// `http.port` and overrides that key of a nested config file section.
+    ///
+    /// Field names that contain the separator are split too: `APP_DATABASE_URL`
+    /// becomes `database.url`. For structs with such fields, use
+    /// [`with_env_flat`](ConfigBuilder::with_env_flat), or
+    /// [`with_env_configured`](ConfigBuilder::with_env_configured) with a `__`
+    /// separator.
+    ///
+    /// # Migration
+    ///
+    /// Before nested mode became the default, `with_env` read every variable as
+    /// a single top-level key. Replace `with_env(prefix)` with
+    /// `with_env_flat(prefix)` to keep that behaviour.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Http {
+    ///     port: u16,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     http: Http,
+    /// }
+    ///
+    /// // This will look for APP_HTTP_PORT
+    /// let builder = ConfigBuilder::new()
+    ///     .with_env("APP");
+    /// ```
+    pub fn with_env(self, prefix: impl Into<String>) -> Self {
+        let env_source = Environment::new().with_prefix(prefix).nested(true);
+        self.add_source(Box::new(env_source))
+    }
+
+    /// Add environment variables with a prefix, each read as one top-level key.
+    ///
+    /// Environment variables are matched using the pattern
+    /// `{PREFIX}_{FIELD_NAME}`, so field names may contain underscores.
     ///
     /// # Examples
     ///
@@ -118,13 +521,85 @@ impl ConfigBuilder {
     ///
     /// // This will look for APP_DATABASE_URL and APP_PORT
     /// let builder = ConfigBuilder::new()
-    ///     .with_env("APP");
+    ///     .with_env_flat("APP");
     /// ```
-    pub fn with_env(self, prefix: impl Into<String>) -> Self {
+    pub fn with_env_flat(self, prefix: impl Into<String>) -> Self {
         let env_source = Environment::new().with_prefix(prefix);
         self.add_source(Box::new(env_source))
     }
 
+    /// Add environment variables with a prefix, configured inline by `configure`.
+    ///
+    /// Shorthand for building an [`Environment`] with
+    /// [`with_prefix`](Environment::with_prefix), adjusting it and passing it to
+    /// [`with_env_custom`](ConfigBuilder::with_env_custom).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// // APP__HTTP__PORT populates http.port
+    /// let builder = ConfigBuilder::new()
+    ///     .with_env_configured("APP", |env| env.separator("__").nested(true));
+    /// ```
+    pub fn with_env_configured<F>(self, prefix: impl Into<String>, configure: F) -> Self
+    where
+        F: FnOnce(Environment) -> Environment,
+    {
+        self.with_env_custom(configure(Environment::new().with_prefix(prefix)))
+    }
+
+    /// Add nested environment variables restricted to the fields of `T`.
+    ///
+    /// The leaf paths of `T` (see [`schema::field_paths`]) become the only
+    /// variables read, nested at their exact path. With prefix `APP`,
+    /// `APP_HTTP_PORT` overrides `http.port` and `APP_DATABASE_MAX_SIZE`
+    /// overrides `database.max_size`, while unrelated `APP_*` variables are
+    /// ignored. Nothing is collected until the configuration is built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database {
+    ///     host: String,
+    ///     max_size: u32,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     database: Database,
+    /// }
+    ///
+    /// std::env::set_var("AUTONEST_DATABASE_MAX_SIZE", "50");
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(json!({ "database": { "host": "localhost", "max_size": 10 } }))?
+    ///     .with_env_prefix_auto_nesting::<Config>("AUTONEST")
+    ///     .build_value()?;
+    /// assert_eq!(value["database"]["max_size"], 50);
+    /// assert_eq!(value["database"]["host"], "localhost");
+    /// # std::env::remove_var("AUTONEST_DATABASE_MAX_SIZE");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`schema::field_paths`]: crate::schema::field_paths
+    pub fn with_env_prefix_auto_nesting<T: DeserializeOwned>(
+        self,
+        prefix: impl Into<String>,
+    ) -> Self {
+        let env = Environment::new()
+            .with_prefix(prefix)
+            .nested(true)
+            .with_known_paths(crate::schema::field_paths::<T>());
+        self.add_source(Box::new(env))
+    }
+
     /// Add a custom environment configuration.
     ///
     /// Use this method when you need more control over environment variable parsing,
@@ -147,14 +622,73 @@ impl ConfigBuilder {
         self.add_source(Box::new(env))
     }
 
+    /// Substitute `${VAR}` placeholders in configuration files from `vars`.
+    ///
+    /// Applies to files added after this call through [`with_file`],
+    /// [`with_file_optional`], [`with_file_format`] and the helpers built on
+    /// them. Substitution works on the raw file text before parsing; it is
+    /// unrelated to the environment source. `$${VAR}` yields a literal `${VAR}`
+    /// and an undefined variable fails loading the file. Use
+    /// [`with_interpolation`](ConfigBuilder::with_interpolation) to read the
+    /// process environment or choose another policy for undefined variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    /// use std::collections::HashMap;
+    ///
+    /// let vars = HashMap::from([("DB_HOST", "db.internal")]);
+    /// // config.yaml: `database_url: postgres://${DB_HOST}/app`
+    /// let builder = ConfigBuilder::new()
+    ///     .with_interpolation_env(vars)
+    ///     .with_file("config.yaml")?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`with_file`]: ConfigBuilder::with_file
+    /// [`with_file_optional`]: ConfigBuilder::with_file_optional
+    /// [`with_file_format`]: ConfigBuilder::with_file_format
+    pub fn with_interpolation_env<I, K, V>(self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.with_interpolation(Interpolation::from_map(vars))
+    }
+
+    /// Substitute placeholders in configuration files added after this call
+    /// using a custom [`Interpolation`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::interpolate::{Interpolation, MissingVar};
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_interpolation(Interpolation::from_process_env().on_missing(MissingVar::Empty));
+    /// ```
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = Some(interpolation);
+        self
+    }
+
     /// Add a required configuration file.
     ///
-    /// The file format is automatically detected from the file extension:
+    /// The file format is automatically detected from the file extension with
+    /// [`ConfigFormat::from_path`]:
     /// - `.json` for JSON files
     /// - `.yaml` or `.yml` for YAML files
     /// - `.toml` for TOML files
+    /// - `.ini` for INI files
     ///
-    /// Returns an error if the file doesn't exist or can't be parsed.
+    /// Returns an error if the extension is not recognized or the file doesn't
+    /// exist or can't be parsed. Use
+    /// [`with_file_format`](ConfigBuilder::with_file_format) for files with
+    /// other extensions. Files named in [`SKIP_FILES_VAR`] are not loaded, so
+    /// they cannot fail here, and are left out when the configuration is built.
     ///
     /// # Examples
     ///
@@ -168,10 +702,11 @@ impl ConfigBuilder {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Io`] if the file cannot be read, or [`Error::Config`]
-    /// if the file cannot be parsed.
+    /// Returns [`Error::UnknownFormat`] for an unrecognized extension,
+    /// [`Error::Io`] carrying the path if the file cannot be read, or
+    /// [`Error::Serialization`] if the file cannot be parsed.
     pub fn with_file(self, path: impl AsRef<Path>) -> Result<Self> {
-        let config = Config::from_file(path)?;
+        let config = Config::open(path.as_ref(), None, true, self.interpolation.clone())?;
         Ok(self.add_source(Box::new(config)))
     }
 
@@ -193,10 +728,40 @@ impl ConfigBuilder {
     ///
     /// [`with_file`]: ConfigBuilder::with_file
     pub fn with_file_optional(self, path: impl AsRef<Path>) -> Result<Self> {
-        let config = Config::from_file_optional(path)?;
+        let config = Config::open(path.as_ref(), None, false, self.interpolation.clone())?;
         Ok(self.add_source(Box::new(config)))
     }
 
+    /// Add an optional configuration file from the user's configuration directory.
+    ///
+    /// Resolves `$XDG_CONFIG_HOME/{app_name}/{filename}` when `XDG_CONFIG_HOME` is
+    /// set to an absolute path. Otherwise the platform's configuration directory
+    /// is used: `~/.config` on Linux, `~/Library/Application Support` on macOS
+    /// and `%APPDATA%` on Windows. The file is loaded like
+    /// [`with_file_optional`](ConfigBuilder::with_file_optional), so a missing
+    /// file (or an undeterminable home directory) is not an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// // Reads ~/.config/mytool/config.toml on Linux, if it exists
+    /// let builder = ConfigBuilder::new()
+    ///     .with_xdg_config("mytool", "config.toml")?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn with_xdg_config(self, app_name: &str, filename: &str) -> Result<Self> {
+        match user_config_dir() {
+            Some(dir) => self.with_file_optional(dir.join(app_name).join(filename)),
+            None => Ok(self),
+        }
+    }
+
     /// Add a configuration file with explicit format.
     ///
     /// Use this method when you need to override the automatic format detection
@@ -213,230 +778,1970 @@ impl ConfigBuilder {
     /// # Ok::<(), gonfig::Error>(())
     /// ```
     pub fn with_file_format(self, path: impl AsRef<Path>, format: ConfigFormat) -> Result<Self> {
-        let config = Config::with_format(path, format)?;
+        let config = Config::open(
+            path.as_ref(),
+            Some(format),
+            true,
+            self.interpolation.clone(),
+        )?;
         Ok(self.add_source(Box::new(config)))
     }
 
-    /// Add CLI arguments from `std::env::args()`.
-    ///
-    /// This creates a basic CLI source that parses arguments in the format:
-    /// - `--key=value` or `--key value`
-    /// - `-k=value` or `-k value` (for single character keys)
+    /// Add a required configuration file, keeping only one of its sections.
     ///
-    /// For more advanced CLI parsing with clap, use [`with_clap`] instead.
+    /// The format is detected from the extension as for
+    /// [`with_file`](ConfigBuilder::with_file), and the object at `section`
+    /// becomes the source's root; see [`Config::with_section`].
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use gonfig::ConfigBuilder;
     ///
-    /// // Parses CLI args like: --database-url=postgres://... --port=8080
+    /// // Only the [billing] table of a shared file
     /// let builder = ConfigBuilder::new()
-    ///     .with_cli();
+    ///     .with_file_section("services.toml", "billing")?;
+    /// # Ok::<(), gonfig::Error>(())
     /// ```
     ///
-    /// [`with_clap`]: ConfigBuilder::with_clap
-    pub fn with_cli(self) -> Self {
-        let cli = Cli::from_args();
-        self.add_source(Box::new(cli))
-    }
-
-    /// Add a custom CLI configuration.
-    pub fn with_cli_custom(self, cli: Cli) -> Self {
-        self.add_source(Box::new(cli))
+    /// # Errors
+    ///
+    /// Returns the errors of [`with_file`](ConfigBuilder::with_file), and
+    /// [`Error::Config`] if the file has no such section.
+    pub fn with_file_section(
+        self,
+        path: impl AsRef<Path>,
+        section: impl Into<String>,
+    ) -> Result<Self> {
+        let config = Config::open(path.as_ref(), None, true, self.interpolation.clone())?
+            .with_section(section)?;
+        Ok(self.add_source(Box::new(config)))
     }
 
-    /// Add CLI arguments using clap parser.
+    /// Add a required INI file, keeping only its `[section]`.
     ///
-    /// This method integrates with clap's derive API for advanced CLI argument parsing.
-    /// Your struct must implement both `clap::Parser` and `serde::Serialize`.
+    /// The file is parsed as [`ConfigFormat::Ini`] whatever its extension, and
+    /// the keys of `section` become the source's top-level keys.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use gonfig::ConfigBuilder;
-    /// use serde::{Deserialize, Serialize};
-    /// use clap::Parser;
-    ///
-    /// #[derive(Parser, Serialize, Deserialize)]
-    /// struct CliArgs {
-    ///     #[arg(long, env = "DATABASE_URL")]
-    ///     database_url: String,
-    ///     
-    ///     #[arg(short, long, default_value = "8080")]
-    ///     port: u16,
-    /// }
     ///
     /// let builder = ConfigBuilder::new()
-    ///     .with_clap::<CliArgs>()?;
+    ///     .with_file_ini_section("/etc/legacy/services.conf", "billing")?;
     /// # Ok::<(), gonfig::Error>(())
     /// ```
-    pub fn with_clap<T: clap::Parser + serde::Serialize>(self) -> Result<Self> {
-        let cli = Cli::with_clap_app::<T>()?;
-        Ok(self.add_source(Box::new(cli)))
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read,
+    /// [`Error::Serialization`] if it cannot be parsed, and [`Error::Config`]
+    /// if it has no such section.
+    pub fn with_file_ini_section(
+        self,
+        path: impl AsRef<Path>,
+        section: impl Into<String>,
+    ) -> Result<Self> {
+        let config = Config::open(
+            path.as_ref(),
+            Some(ConfigFormat::Ini),
+            true,
+            self.interpolation.clone(),
+        )?
+        .with_section(section)?;
+        Ok(self.add_source(Box::new(config)))
     }
 
-    /// Add default values as a fallback configuration source.
+    /// Add several configuration files, layered by explicit priority.
     ///
-    /// Default values are applied with the lowest priority, so they will be overridden
-    /// by any other configuration source (environment variables, config files, CLI args).
+    /// Each file's format is detected from its own extension, so a TOML base
+    /// can be combined with a YAML override. Files are merged from the lowest
+    /// to the highest priority, the highest winning; files with equal priority
+    /// keep their order in `files`. All of them sit at the configuration file
+    /// level, above defaults and below environment variables.
+    ///
+    /// Plain [`with_file`](ConfigBuilder::with_file) calls layer the same way,
+    /// with later files winning; this method makes the precedence explicit.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use gonfig::ConfigBuilder;
-    /// use serde_json::json;
-    ///
-    /// let defaults = json!({
-    ///     "port": 8080,
-    ///     "debug": false,
-    ///     "database": {
-    ///         "pool_size": 10
-    ///     }
-    /// });
     ///
     /// let builder = ConfigBuilder::new()
-    ///     .with_defaults(defaults)?;
+    ///     .with_layered_files(&[("override.yaml", 10), ("base.toml", 0)])?;
     /// # Ok::<(), gonfig::Error>(())
     /// ```
-    pub fn with_defaults(mut self, defaults: Value) -> Result<Self> {
-        // Create a custom source for defaults with lowest priority
-        struct DefaultsSource {
-            value: Value,
-        }
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`with_file`](ConfigBuilder::with_file) for
+    /// the first file that cannot be loaded.
+    pub fn with_layered_files<P: AsRef<Path>>(mut self, files: &[(P, i32)]) -> Result<Self> {
+        let mut ordered: Vec<&(P, i32)> = files.iter().collect();
+        ordered.sort_by_key(|(_, priority)| *priority);
 
-        impl ConfigSource for DefaultsSource {
-            fn collect(&self) -> Result<Value> {
-                Ok(self.value.clone())
-            }
+        for (path, _) in ordered {
+            self = self.with_file(path)?;
+        }
+        Ok(self)
+    }
 
-            fn source_type(&self) -> crate::source::Source {
-                // Use Default source type which has the lowest priority
-                crate::source::Source::Default
-            }
+    /// Add every file of `format` in `dir`, merged in lexical filename order.
+    ///
+    /// Intended for drop-in directories such as `conf.d/`: files whose
+    /// extension belongs to `format` (see [`ConfigFormat::from_path`]) are
+    /// sorted by name and added like
+    /// [`with_file_format`](ConfigBuilder::with_file_format), so
+    /// `20-override.yaml` wins over `10-base.yaml`. Hidden files and
+    /// subdirectories are skipped; see
+    /// [`with_directory_recursive`](ConfigBuilder::with_directory_recursive).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{ConfigBuilder, ConfigFormat};
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_file("config.yaml")?
+    ///     .with_directory("conf.d", ConfigFormat::Yaml)?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the directory cannot be read, and the errors
+    /// of [`with_file_format`](ConfigBuilder::with_file_format) for the first
+    /// file that cannot be loaded.
+    pub fn with_directory(self, dir: impl AsRef<Path>, format: ConfigFormat) -> Result<Self> {
+        self.add_directory(dir.as_ref(), format, false)
+    }
 
-            fn has_value(&self, key: &str) -> bool {
-                self.value.get(key).is_some()
-            }
+    /// Like [`with_directory`](ConfigBuilder::with_directory), also descending
+    /// into subdirectories.
+    ///
+    /// Files are ordered by their path relative to `dir`, so `a/z.yaml` comes
+    /// before `b.yaml`. Hidden files and hidden directories are still skipped.
+    ///
+    /// # Errors
+    ///
+    /// See [`with_directory`](ConfigBuilder::with_directory).
+    pub fn with_directory_recursive(
+        self,
+        dir: impl AsRef<Path>,
+        format: ConfigFormat,
+    ) -> Result<Self> {
+        self.add_directory(dir.as_ref(), format, true)
+    }
 
-            fn get_value(&self, key: &str) -> Option<Value> {
-                self.value.get(key).cloned()
-            }
+    fn add_directory(mut self, dir: &Path, format: ConfigFormat, recursive: bool) -> Result<Self> {
+        let mut files = Vec::new();
+        collect_directory(dir, &format, recursive, &mut files)?;
+        files.sort();
 
-            fn as_any(&self) -> &dyn std::any::Any {
-                self
-            }
+        for file in files {
+            self = self.with_file_format(dir.join(file), format.clone())?;
         }
-
-        // Add defaults as the first source (lowest priority)
-        self.sources
-            .insert(0, Box::new(DefaultsSource { value: defaults }));
         Ok(self)
     }
 
-    /// Add a validation function that will be called on the final merged configuration.
+    /// Load a profile-specific overlay file chosen by `selector`.
+    ///
+    /// `selector` receives the configuration merged from the sources added so
+    /// far (before transformations and validation) and returns the active
+    /// profile, or `None` for no overlay. The profile replaces `{profile}` in
+    /// `path_template` and the resulting file is added like
+    /// [`with_file_optional`](ConfigBuilder::with_file_optional), so a profile
+    /// without an overlay is not an error. Because selection runs when this
+    /// method is called, add the sources the selector depends on first.
     ///
     /// # Examples
     ///
-    /// ```rust
-    /// use gonfig::{ConfigBuilder, Error};
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
     ///
+    /// // APP_ENV=prod loads config.prod.toml on top of config.toml
     /// let builder = ConfigBuilder::new()
-    ///     .validate_with(|value| {
-    ///         if let Some(port) = value.get("port").and_then(|p| p.as_u64()) {
-    ///             if port > 65535 {
-    ///                 return Err(Error::Validation("Invalid port number".into()));
-    ///             }
-    ///         }
-    ///         Ok(())
-    ///     });
+    ///     .with_file("config.toml")?
+    ///     .with_env("APP")
+    ///     .with_profile_selector("config.{profile}.toml", |merged| {
+    ///         merged.get("env").and_then(|env| env.as_str()).map(str::to_string)
+    ///     })?;
+    /// # Ok::<(), gonfig::Error>(())
     /// ```
-    pub fn validate_with<F>(mut self, validator: F) -> Self
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sources added so far cannot be collected, or if
+    /// the overlay exists but cannot be read or parsed.
+    pub fn with_profile_selector<F>(self, path_template: &str, selector: F) -> Result<Self>
     where
-        F: Fn(&Value) -> Result<()> + 'static,
+        F: FnOnce(&Value) -> Option<String>,
     {
-        self.validate = Some(Box::new(validator));
-        self
+        let merged = self.merge_collected(self.collect_values()?)?;
+        match selector(&merged) {
+            Some(profile) => self.with_file_optional(path_template.replace("{profile}", &profile)),
+            None => Ok(self),
+        }
     }
 
-    /// Build the final configuration by merging all sources.
+    /// Add a configuration file stored inside a zip or tar archive.
     ///
-    /// This method processes all registered sources in order, applies the configured
-    /// merge strategy, runs any validation, and deserializes the result into the
-    /// target configuration type.
+    /// Loads `entry_name` from the archive at `archive_path` and adds it with
+    /// configuration file priority. The archive type is detected from its
+    /// extension (`.zip` or `.tar`).
     ///
-    /// # Type Parameters
+    /// Requires the `archive` feature.
     ///
-    /// * `T` - The target configuration type that implements [`serde::de::DeserializeOwned`]
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{ConfigBuilder, ConfigFormat};
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_archive_entry("bundle.zip", "config.yaml", ConfigFormat::Yaml)?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[cfg(feature = "archive")]
+    pub fn with_archive_entry(
+        self,
+        archive_path: impl AsRef<Path>,
+        entry_name: impl Into<String>,
+        format: ConfigFormat,
+    ) -> Result<Self> {
+        let source = crate::archive::ArchiveSource::from_path(archive_path, entry_name, format)?;
+        Ok(self.add_source(Box::new(source)))
+    }
+
+    /// Add CLI arguments from `std::env::args()`.
+    ///
+    /// This creates a basic CLI source that parses arguments in the format:
+    /// - `--key=value` or `--key value`
+    /// - `-k=value` or `-k value` (for single character keys)
+    ///
+    /// For more advanced CLI parsing with clap, use [`with_clap`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// // Parses CLI args like: --database-url=postgres://... --port=8080
+    /// let builder = ConfigBuilder::new()
+    ///     .with_cli();
+    /// ```
+    ///
+    /// [`with_clap`]: ConfigBuilder::with_clap
+    pub fn with_cli(self) -> Self {
+        let cli = Cli::from_args();
+        self.add_source(Box::new(cli))
+    }
+
+    /// Add a custom CLI configuration.
+    ///
+    /// Several CLI sources can be registered, for example one built by hand
+    /// and the one the derive macro adds. They share the CLI priority, so
+    /// they are merged in registration order and the source added last wins
+    /// for a flag both provide. Within one source, a repeated flag takes the
+    /// value of its last occurrence; see [`Cli::from_vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{Cli, ConfigBuilder};
+    ///
+    /// let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+    /// let value = ConfigBuilder::new()
+    ///     .with_cli_custom(Cli::from_vec(args(&["app", "--port", "1", "--host", "a"])))
+    ///     .with_cli_custom(Cli::from_vec(args(&["app", "--port", "2", "--port", "3"])))
+    ///     .build_value()?;
+    /// assert_eq!(value, serde_json::json!({ "port": 3, "host": "a" }));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_cli_custom(self, cli: Cli) -> Self {
+        self.add_source(Box::new(cli))
+    }
+
+    /// Add CLI arguments using clap parser.
+    ///
+    /// This method integrates with clap's derive API for advanced CLI argument parsing.
+    /// Your struct must implement both `clap::Parser` and `serde::Serialize`.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use gonfig::ConfigBuilder;
-    /// use serde::Deserialize;
+    /// use serde::{Deserialize, Serialize};
+    /// use clap::Parser;
     ///
-    /// #[derive(Deserialize)]
-    /// struct AppConfig {
+    /// #[derive(Parser, Serialize, Deserialize)]
+    /// struct CliArgs {
+    ///     #[arg(long, env = "DATABASE_URL")]
     ///     database_url: String,
+    ///     
+    ///     #[arg(short, long, default_value = "8080")]
     ///     port: u16,
-    ///     debug: bool,
     /// }
     ///
-    /// let config: AppConfig = ConfigBuilder::new()
-    ///     .with_env("APP")
-    ///     .with_file_optional("config.json")?
-    ///     .with_cli()
-    ///     .build()?;
+    /// let builder = ConfigBuilder::new()
+    ///     .with_clap::<CliArgs>()?;
     /// # Ok::<(), gonfig::Error>(())
     /// ```
+    pub fn with_clap<T: clap::Parser + serde::Serialize>(self) -> Result<Self> {
+        let cli = Cli::with_clap_app::<T>()?;
+        Ok(self.add_source(Box::new(cli)))
+    }
+
+    /// Add parameters from AWS SSM Parameter Store under a path prefix.
     ///
-    /// # Errors
+    /// Parameters are read with credentials and region from the standard AWS
+    /// chain and nested by their `/`-separated path relative to
+    /// `path_prefix`. The source sits above environment variables and below
+    /// CLI arguments, and honors [`resilient`](ConfigBuilder::resilient).
     ///
-    /// Returns an error if:
-    /// - Any required configuration source fails to load
-    /// - Validation fails
-    /// - The final merged configuration cannot be deserialized into type `T`
-    pub fn build<T: DeserializeOwned>(self) -> Result<T> {
-        let merger = ConfigMerger::new(self.merge_strategy);
-
-        let mut source_values = Vec::new();
-        for source in &self.sources {
-            let value = source.collect()?;
-            let priority = source.source_type().priority();
-            source_values.push((value, priority));
-        }
-
-        let merged = merger.merge_sources(source_values);
-
-        if let Some(validator) = &self.validate {
-            validator(&merged)?;
-        }
-
-        serde_json::from_value(merged)
-            .map_err(|e| Error::Serialization(format!("Failed to deserialize config: {e}")))
+    /// Requires the `aws` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_env("MYAPP")
+    ///     .with_ssm("/myapp/prod")
+    ///     .build_value()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[cfg(feature = "aws")]
+    pub fn with_ssm(self, path_prefix: impl Into<String>) -> Self {
+        let source = crate::ssm::SsmSource::new(path_prefix);
+        self.add_source(Box::new(source))
     }
 
-    pub fn build_value(self) -> Result<Value> {
-        let merger = ConfigMerger::new(self.merge_strategy);
-
-        let mut source_values = Vec::new();
-        for source in &self.sources {
-            let value = source.collect()?;
-            let priority = source.source_type().priority();
-            source_values.push((value, priority));
-        }
-
-        let merged = merger.merge_sources(source_values);
+    /// Like [`with_ssm`](Self::with_ssm), reading parameters through `store`
+    /// instead of the AWS API (see [`ParameterStore`]).
+    ///
+    /// Requires the `aws` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ssm::{Parameter, ParameterStore};
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// struct Fixed;
+    ///
+    /// impl ParameterStore for Fixed {
+    ///     fn parameters_by_path(&self, _path: &str, _decrypt: bool) -> gonfig::Result<Vec<Parameter>> {
+    ///         Ok(vec![Parameter::new("/myapp/prod/port", "8080")])
+    ///     }
+    /// }
+    ///
+    /// let builder = ConfigBuilder::new().with_ssm_store(Fixed, "/myapp/prod");
+    /// ```
+    ///
+    /// [`ParameterStore`]: crate::ssm::ParameterStore
+    #[cfg(feature = "aws")]
+    pub fn with_ssm_store(
+        self,
+        store: impl crate::ssm::ParameterStore + 'static,
+        path_prefix: impl Into<String>,
+    ) -> Self {
+        let source = crate::ssm::SsmSource::with_store(store, path_prefix);
+        self.add_source(Box::new(source))
+    }
 
-        if let Some(validator) = &self.validate {
-            validator(&merged)?;
-        }
+    /// Add a `.env` file as a configuration source.
+    ///
+    /// The file's variables sit above defaults and below config files and real
+    /// environment variables, so a value exported in the shell still wins. Keys are
+    /// lowercased; to strip an application prefix, add a
+    /// [`DotEnv`](crate::dotenv::DotEnv) with
+    /// [`with_prefix`](crate::dotenv::DotEnv::with_prefix) through
+    /// [`add_source`](Self::add_source) instead. Use
+    /// [`DotEnv::auto`](crate::dotenv::DotEnv::auto) for a `.env` that may be
+    /// absent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_dotenv(".env")?
+    ///     .with_env("APP");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read and [`Error::Config`]
+    /// if it is malformed.
+    pub fn with_dotenv(self, path: impl AsRef<Path>) -> Result<Self> {
+        let dotenv = crate::dotenv::DotEnv::from_path(path)?;
+        Ok(self.add_source(Box::new(dotenv)))
+    }
 
-        Ok(merged)
+    /// Add a `KEY=VALUE` file whose variables act as if they were exported.
+    ///
+    /// Unlike [`with_dotenv`](Self::with_dotenv), the file merges at the
+    /// environment's priority, so an ops team can ship overrides that beat
+    /// configuration files. A variable that is really exported still beats the
+    /// file's value for the same name; those names are logged as a warning
+    /// when the configuration is collected and listed by
+    /// [`DotEnv::shadowed_vars`](crate::dotenv::DotEnv::shadowed_vars).
+    /// Variables are matched with `prefix` like [`with_env`](Self::with_env).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// // overrides.env: APP_PORT=9000 beats config.toml, but not an exported APP_PORT
+    /// let builder = ConfigBuilder::new()
+    ///     .with_file("config.toml")?
+    ///     .with_env_file_override("overrides.env", "APP")?
+    ///     .with_env("APP");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read and [`Error::Config`]
+    /// if it is malformed.
+    pub fn with_env_file_override(
+        self,
+        path: impl AsRef<Path>,
+        prefix: impl Into<String>,
+    ) -> Result<Self> {
+        let dotenv = crate::dotenv::DotEnv::from_path(path)?
+            .with_prefix(prefix)
+            .at_env_priority();
+        Ok(self.add_source(Box::new(dotenv)))
     }
 
-    pub fn sources(&self) -> &[Box<dyn ConfigSource>] {
-        &self.sources
+    /// Add a gRPC config service as a remote configuration source.
+    ///
+    /// `endpoint` is the address of a service implementing the
+    /// [`grpc`](crate::grpc) protocol, e.g. `http://config.internal:50051`,
+    /// called with a [`GrpcClient`]; `request` selects the document to fetch.
+    /// The source is merged above environment variables and below CLI
+    /// arguments, and is skipped on failure when the builder is
+    /// [`resilient`](Self::resilient).
+    ///
+    /// Requires the `grpc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::grpc::ConfigRequest;
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_env("APP")
+    ///     .with_grpc("http://config.internal:50051", ConfigRequest::new("billing").profile("prod"))
+    ///     .resilient(true)
+    ///     .build_value()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`GrpcClient`]: crate::grpc::GrpcClient
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc(
+        self,
+        endpoint: impl Into<String>,
+        request: crate::grpc::ConfigRequest,
+    ) -> Self {
+        self.add_source(Box::new(crate::grpc::GrpcSource::connect(
+            endpoint, request,
+        )))
+    }
+
+    /// Add a config service reached through a custom [`ConfigService`]
+    /// client, such as a configured [`GrpcClient`] or a mock.
+    ///
+    /// Behaves like [`with_grpc`](Self::with_grpc). Requires the `grpc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::grpc::{ConfigRequest, ConfigResponse, ConfigService};
+    /// use gonfig::{ConfigBuilder, ConfigFormat};
+    ///
+    /// struct Fixed;
+    ///
+    /// impl ConfigService for Fixed {
+    ///     fn fetch(&self, _request: &ConfigRequest) -> gonfig::Result<ConfigResponse> {
+    ///         Ok(ConfigResponse::new(r#"{"port": 8080}"#, ConfigFormat::Json))
+    ///     }
+    /// }
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_grpc_service(Fixed, ConfigRequest::new("billing").profile("prod"))
+    ///     .build_value()?;
+    /// assert_eq!(value["port"], 8080);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`ConfigService`]: crate::grpc::ConfigService
+    /// [`GrpcClient`]: crate::grpc::GrpcClient
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_service(
+        self,
+        service: impl crate::grpc::ConfigService + 'static,
+        request: crate::grpc::ConfigRequest,
+    ) -> Self {
+        self.add_source(Box::new(crate::grpc::GrpcSource::new(service, request)))
+    }
+
+    /// Add a JSON document fetched over HTTP as a remote configuration source.
+    ///
+    /// `url` may be `http://` or `https://` and is fetched by the built-in
+    /// [`HttpClient`](crate::remote::HttpClient) within its default deadline
+    /// and body size limit. The document is merged above environment
+    /// variables and below CLI arguments, and is skipped on failure when the
+    /// builder is [`resilient`](Self::resilient). Use
+    /// [`with_remote_at`](Self::with_remote_at) for another priority. For a
+    /// bearer token, another deadline or a subpath, configure an
+    /// [`HttpJsonSource`] and pass it to [`add_source`](Self::add_source).
+    ///
+    /// Requires the `remote` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_remote("https://config.internal/v1/config/app")
+    ///     .build_value()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`HttpJsonSource`]: crate::remote::HttpJsonSource
+    #[cfg(feature = "remote")]
+    pub fn with_remote(self, url: impl Into<String>) -> Self {
+        self.add_source(Box::new(crate::remote::HttpJsonSource::new(url)))
+    }
+
+    /// Like [`with_remote`](Self::with_remote), merging the document at the
+    /// priority of `source_type`, e.g. [`Source::Default`] for shared defaults
+    /// every other source may override.
+    ///
+    /// Requires the `remote` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{ConfigBuilder, Source};
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_remote_at("https://config.internal/v1/defaults", Source::Default)
+    ///     .with_env("APP")
+    ///     .build_value()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[cfg(feature = "remote")]
+    pub fn with_remote_at(self, url: impl Into<String>, source_type: Source) -> Self {
+        self.add_source(Box::new(
+            crate::remote::HttpJsonSource::new(url).at_priority(source_type),
+        ))
+    }
+
+    /// Add default values as a fallback configuration source.
+    ///
+    /// Default values are applied with the lowest priority, so they will be overridden
+    /// by any other configuration source (environment variables, config files, CLI args).
+    ///
+    /// Among defaults the order is deterministic: field defaults from
+    /// `#[gonfig(default = "...")]` (see [`with_field_defaults`]) are applied
+    /// first, then every `with_defaults` value is deep-merged on top in call
+    /// order, so a `with_defaults` value wins over an attribute default and a
+    /// later call wins over an earlier one. This holds wherever the calls sit
+    /// among other `with_*` calls. Releases before this kept the first call's
+    /// value instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde_json::json;
+    ///
+    /// let defaults = json!({
+    ///     "port": 8080,
+    ///     "debug": false,
+    ///     "database": {
+    ///         "pool_size": 10
+    ///     }
+    /// });
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_defaults(defaults)?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`with_field_defaults`]: ConfigBuilder::with_field_defaults
+    pub fn with_defaults(mut self, defaults: Value) -> Result<Self> {
+        // Defaults stay ahead of every other source, after earlier defaults
+        let position = self
+            .sources
+            .iter()
+            .take_while(|source| source.source_type() == Source::Default)
+            .count();
+        self.sources.insert(
+            position,
+            Arc::new(DefaultsSource {
+                value: defaults,
+                label: "defaults",
+            }),
+        );
+        self.clear_source_cache();
+        Ok(self)
+    }
+
+    /// Add per-field default values beneath every other default.
+    ///
+    /// This is what `#[derive(Gonfig)]` uses for `#[gonfig(default = "...")]`
+    /// attributes: the values are applied before any
+    /// [`with_defaults`](ConfigBuilder::with_defaults) value, so defaults passed
+    /// to the builder override attribute defaults for the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde_json::json;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(json!({ "port": 9000 }))?
+    ///     .with_field_defaults(json!({ "port": 8080, "host": "localhost" }))
+    ///     .build_value()?;
+    /// assert_eq!(value["port"], 9000);
+    /// assert_eq!(value["host"], "localhost");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_field_defaults(mut self, defaults: Value) -> Self {
+        self.sources.insert(
+            0,
+            Arc::new(DefaultsSource {
+                value: defaults,
+                label: "field defaults",
+            }),
+        );
+        self.clear_source_cache();
+        self
+    }
+
+    /// Layer another builder's configuration beneath this one.
+    ///
+    /// `other` is collected whenever this builder collects its sources (running
+    /// its own merge strategy and validation), and the resulting value is
+    /// merged below every source of this builder, including its defaults. This
+    /// lets a library expose a pre-configured builder that applications extend
+    /// with their own sources. Errors produced while collecting `other` are
+    /// returned by [`build`](ConfigBuilder::build) and the other collecting
+    /// methods.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde_json::json;
+    ///
+    /// let library = ConfigBuilder::new().with_defaults(json!({ "port": 8080, "host": "0.0.0.0" }))?;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(json!({ "port": 9000 }))?
+    ///     .with_fallback_builder(library)
+    ///     .build_value()?;
+    /// assert_eq!(value["port"], 9000);
+    /// assert_eq!(value["host"], "0.0.0.0");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_fallback_builder(mut self, other: ConfigBuilder) -> Self {
+        self.fallbacks.push(other);
+        self.clear_source_cache();
+        self
+    }
+
+    /// Add a validation function that will be called on the final merged configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .validate_with(|value| {
+    ///         if let Some(port) = value.get("port").and_then(|p| p.as_u64()) {
+    ///             if port > 65535 {
+    ///                 return Err(Error::Validation("Invalid port number".into()));
+    ///             }
+    ///         }
+    ///         Ok(())
+    ///     });
+    /// ```
+    pub fn validate_with<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&Value) -> Result<()> + 'static,
+    {
+        self.validate = Some(Box::new(validator));
+        self
+    }
+
+    /// Require dotted `paths` to be present in the merged configuration.
+    ///
+    /// A path counts as missing when it does not exist or holds `null`. The
+    /// check runs after transformations and before any
+    /// [`validate_with`](ConfigBuilder::validate_with) function; repeated calls
+    /// add to the list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// let result = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "database": { "pool": 5 } }))?
+    ///     .validate_required(&["database.url", "api_key"])
+    ///     .build_value();
+    ///
+    /// match result {
+    ///     Err(Error::Validation(msg)) => assert!(msg.contains("database.url, api_key")),
+    ///     _ => panic!("expected a validation error"),
+    /// }
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building returns [`Error::Validation`] listing every missing path.
+    pub fn validate_required(mut self, paths: &[&str]) -> Self {
+        self.required
+            .extend(paths.iter().map(|path| path.to_string()));
+        self
+    }
+
+    /// Require raw environment variables to be set before anything is collected.
+    ///
+    /// The names are checked exactly as given, independently of prefixes and of
+    /// the target struct. A variable set to an empty string counts as set.
+    /// Repeated calls add to the list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// let result = ConfigBuilder::new()
+    ///     .with_required_env(&["SOME_UNSET_DATABASE_URL"])
+    ///     .build_value();
+    /// assert!(matches!(result, Err(Error::Environment(_))));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building returns [`Error::Environment`] listing every unset variable.
+    pub fn with_required_env(mut self, names: &[&str]) -> Self {
+        self.required_env
+            .extend(names.iter().map(|name| name.to_string()));
+        self
+    }
+
+    /// Reject merged keys that do not correspond to a field of the target.
+    ///
+    /// Unlike serde's `#[serde(deny_unknown_fields)]`, the check covers every
+    /// source and does not depend on the struct's attributes, so a typo in a
+    /// config file or a stray prefixed environment variable is caught. The
+    /// known fields come from `#[derive(Gonfig)]` when building through the
+    /// generated methods, or from [`with_known_fields`](ConfigBuilder::with_known_fields).
+    /// The check runs after transformations and before
+    /// [`validate_required`](ConfigBuilder::validate_required). Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// let result = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080, "prot": 9090 }))?
+    ///     .with_known_fields(["port"])
+    ///     .deny_unknown_fields(true)
+    ///     .build_value();
+    ///
+    /// match result {
+    ///     Err(Error::Validation(msg)) => assert!(msg.contains("prot")),
+    ///     _ => panic!("expected a validation error"),
+    /// }
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Building returns [`Error::Validation`] listing every unknown key, or
+    /// [`Error::Config`] when no known fields were declared. A derived struct
+    /// with a `#[serde(flatten)]` catch-all map also returns [`Error::Config`],
+    /// since that map accepts every key.
+    pub fn deny_unknown_fields(mut self, deny: bool) -> Self {
+        self.deny_unknown = deny;
+        self
+    }
+
+    /// Whether [`deny_unknown_fields`](ConfigBuilder::deny_unknown_fields) is
+    /// enabled.
+    pub fn denies_unknown_fields(&self) -> bool {
+        self.deny_unknown
+    }
+
+    /// Declare the dotted paths the target accepts, for
+    /// [`deny_unknown_fields`](ConfigBuilder::deny_unknown_fields).
+    ///
+    /// A path also accepts everything below it, so `database` covers
+    /// `database.url`. Repeated calls add to the list.
+    pub fn with_known_fields<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.known_fields
+            .get_or_insert_with(Vec::new)
+            .extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add a transformation applied to the merged configuration.
+    ///
+    /// Transformations run in registration order after all sources are merged
+    /// and before validation and deserialization. They can reshape values, for
+    /// example to adapt a legacy schema to the current struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "name": "svc" }))?
+    ///     .transform_with(|value| {
+    ///         value["name"] = serde_json::json!("renamed");
+    ///         Ok(())
+    ///     })
+    ///     .build_value()?;
+    /// assert_eq!(value["name"], "renamed");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn transform_with<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut Value) -> Result<()> + 'static,
+    {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Read the field at dotted `path` through `parser`, for human-friendly
+    /// values such as `30s` or `10MB`.
+    ///
+    /// The parser runs while each source is collected, before merging, and
+    /// returns the value the field deserializes from. Environment variables,
+    /// `.env` files and CLI flags hand it their text exactly as given, so
+    /// `007` or `1e3` reach the parser unchanged instead of being coerced into
+    /// numbers first. For other sources a string, number or boolean at `path`
+    /// is passed in its text form; arrays and objects are left alone. A
+    /// failing parser is reported as [`Error::Parse`] naming `path`. Used by
+    /// `#[gonfig(parse_with = "...")]`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment};
+    ///
+    /// fn parse_code(text: &str) -> Result<serde_json::Value, String> {
+    ///     match text.len() {
+    ///         3 => Ok(text.into()),
+    ///         _ => Err(format!("expected three digits, got `{text}`")),
+    ///     }
+    /// }
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .add_source(Box::new(Environment::new().override_with("AGENT", "007")))
+    ///     .with_value_parser("agent", parse_code)
+    ///     .build_value()?;
+    /// assert_eq!(value["agent"], "007");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_value_parser<E, F>(mut self, path: impl Into<String>, parser: F) -> Self
+    where
+        E: std::fmt::Display,
+        F: Fn(&str) -> std::result::Result<Value, E> + Send + Sync + 'static,
+    {
+        let path = path.into();
+        self.value_parsers.retain(|(own, _)| *own != path);
+        self.value_parsers.push((path, ValueParser::new(parser)));
+        self.clear_source_cache();
+        self
+    }
+
+    /// Combine a numeric field and its sibling unit field into a duration.
+    ///
+    /// For schemas that split a duration as `timeout_value: 30` and
+    /// `timeout_unit: "s"`, this replaces both with a `std::time::Duration`
+    /// compatible value at `target_path`. See
+    /// [`transform::combine_duration`](crate::transform::combine_duration) for
+    /// the supported units.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     timeout: Duration,
+    /// }
+    ///
+    /// let config: Config = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "timeout_value": 500, "timeout_unit": "ms" }))?
+    ///     .with_duration_pair("timeout_value", "timeout_unit", "timeout")
+    ///     .build()?;
+    /// assert_eq!(config.timeout, Duration::from_millis(500));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_duration_pair(
+        self,
+        value_path: impl Into<String>,
+        unit_path: impl Into<String>,
+        target_path: impl Into<String>,
+    ) -> Self {
+        let (value_path, unit_path, target_path) =
+            (value_path.into(), unit_path.into(), target_path.into());
+        self.transform_with(move |value| {
+            crate::transform::combine_duration(value, &value_path, &unit_path, &target_path)
+        })
+    }
+
+    /// Accept `true` and `false` in any case for the boolean at `path`.
+    ///
+    /// The check runs on the merged configuration, so it covers values from
+    /// files, such as a YAML `debug: "TRUE"`, as well as environment variables.
+    /// See [`transform::normalize_bool`](crate::transform::normalize_bool).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "debug": "False" }))?
+    ///     .case_insensitive_bool("debug")
+    ///     .build_value()?;
+    /// assert_eq!(value["debug"], false);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn case_insensitive_bool(self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.transform_with(move |value| {
+            crate::transform::normalize_bool(value, &path);
+            Ok(())
+        })
+    }
+
+    /// Accept any casing of `variants` for the unit enum at `path`.
+    ///
+    /// Like [`case_insensitive_bool`](Self::case_insensitive_bool), this runs
+    /// on the merged configuration and applies to every source. See
+    /// [`transform::normalize_variant`](crate::transform::normalize_variant).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// enum Mode {
+    ///     Dev,
+    ///     Prod,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     mode: Mode,
+    /// }
+    ///
+    /// let config: Config = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "mode": "PROD" }))?
+    ///     .case_insensitive_enum("mode", &["Dev", "Prod"])
+    ///     .build()?;
+    /// assert_eq!(config.mode, Mode::Prod);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn case_insensitive_enum(self, path: impl Into<String>, variants: &[&str]) -> Self {
+        let path = path.into();
+        let variants: Vec<String> = variants.iter().map(|v| v.to_string()).collect();
+        self.transform_with(move |value| {
+            let variants: Vec<&str> = variants.iter().map(String::as_str).collect();
+            crate::transform::normalize_variant(value, &path, &variants);
+            Ok(())
+        })
+    }
+
+    /// Register a callback run with the new configuration after each
+    /// successful [`reload`](Self::reload).
+    ///
+    /// The merged configuration is deserialized into `T` and passed along with
+    /// a [`ConfigDiff`] against the configuration before the reload, so
+    /// subsystems can reconfigure themselves when their settings change.
+    /// Callbacks run in registration order. A callback that panics, or whose
+    /// `T` cannot be deserialized from the new configuration, is logged and
+    /// skipped; the remaining callbacks still run and later reloads keep
+    /// working.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     workers: u32,
+    /// }
+    ///
+    /// let mut builder = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "workers": 4 }))?
+    ///     .on_reload(|config: &Config, diff| {
+    ///         if diff.contains("workers") {
+    ///             println!("resizing pool to {}", config.workers);
+    ///         }
+    ///     });
+    /// builder.reload()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn on_reload<T, F>(mut self, callback: F) -> Self
+    where
+        T: DeserializeOwned,
+        F: Fn(&T, &ConfigDiff) + Send + Sync + 'static,
+    {
+        self.reload_callbacks.push(Box::new(move |value, diff| {
+            let config: T = deserialize_merged(value.clone(), PhantomData::<T>)?;
+            callback(&config, diff);
+            Ok(())
+        }));
+        self
+    }
+
+    /// Re-read configuration files and rebuild the merged configuration.
+    ///
+    /// Every file source is loaded from disk again and the other sources are
+    /// collected afresh, discarding any [cached](Self::cache_sources) snapshot.
+    /// On success the [`on_reload`](Self::on_reload) callbacks are run and the
+    /// new merged value is returned. When loading or validation fails, the
+    /// error is returned, the callbacks are not run, and file sources keep
+    /// their previous contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let mut builder = ConfigBuilder::new().with_file("app.yaml")?;
+    /// // ... app.yaml is edited ...
+    /// let value = builder.reload()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn reload(&mut self) -> Result<Value> {
+        let previous = self.collect_value().unwrap_or(Value::Null);
+
+        let mut sources = SourceList::default();
+        for source in self.sources.iter() {
+            match source.as_any().downcast_ref::<Config>() {
+                Some(config) => {
+                    let mut fresh = config.clone();
+                    fresh.reload()?;
+                    sources.push(Arc::new(fresh));
+                }
+                None => sources.push(Arc::clone(source)),
+            }
+        }
+
+        let stale = std::mem::replace(&mut self.sources, sources);
+        self.clear_source_cache();
+        let current = match self.collect_value() {
+            Ok(current) => current,
+            Err(e) => {
+                self.sources = stale;
+                return Err(e);
+            }
+        };
+
+        let diff = ConfigDiff::between(&previous, &current);
+        for callback in &self.reload_callbacks {
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                callback(&current, &diff)
+            }));
+            match outcome {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::error!(
+                    "Configuration reload callback skipped: {}",
+                    e.with_context("during reload")
+                ),
+                Err(_) => tracing::error!("Configuration reload callback panicked"),
+            }
+        }
+
+        Ok(current)
+    }
+
+    /// Build `T` and keep it up to date as configuration files change.
+    ///
+    /// `make` creates the builder on the watch thread, which keeps it for
+    /// every reload. Because the builder never crosses threads, closures
+    /// given to [`validate_with`](Self::validate_with) and
+    /// [`transform_with`](Self::transform_with) need not be `Send`; only
+    /// `make` itself must be. Changes are applied once the files stayed quiet
+    /// for [`DEFAULT_DEBOUNCE`](crate::watch::DEFAULT_DEBOUNCE); see
+    /// [`watch_with_debounce`](Self::watch_with_debounce) to choose the delay
+    /// and [`WatchedConfig`] for how changes are applied. Requires the `watch`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{ConfigBuilder, Error};
+    /// use serde::Deserialize;
+    /// use std::rc::Rc;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     workers: u32,
+    /// }
+    ///
+    /// let watched = ConfigBuilder::watch_with::<Config, _>(|| {
+    ///     // Not `Send`, which is fine: the builder stays on the watch thread
+    ///     let limit = Rc::new(64);
+    ///     Ok(ConfigBuilder::new()
+    ///         .with_file("app.toml")?
+    ///         .validate_with(move |value| match value["workers"].as_u64() {
+    ///             Some(workers) if workers <= *limit => Ok(()),
+    ///             _ => Err(Error::Validation("too many workers".to_string())),
+    ///         }))
+    /// })?;
+    /// println!("running {} workers", watched.current().workers);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `make`, the same errors as [`build`](Self::build)
+    /// for the initial configuration, and [`Error::Io`] when a file's
+    /// directory cannot be watched. Errors of later reloads are reported
+    /// through [`WatchedConfig::subscribe_errors`].
+    #[cfg(feature = "watch")]
+    pub fn watch_with<T, F>(make: F) -> Result<WatchedConfig<T>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce() -> Result<ConfigBuilder> + Send + 'static,
+    {
+        Self::watch_with_debounce(make, crate::watch::DEFAULT_DEBOUNCE)
+    }
+
+    /// Like [`watch_with`](Self::watch_with), waiting `debounce` for the files
+    /// to stop changing before reloading.
+    ///
+    /// # Errors
+    ///
+    /// See [`watch_with`](Self::watch_with).
+    #[cfg(feature = "watch")]
+    pub fn watch_with_debounce<T, F>(make: F, debounce: Duration) -> Result<WatchedConfig<T>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce() -> Result<ConfigBuilder> + Send + 'static,
+    {
+        WatchedConfig::start(make, debounce)
+    }
+
+    /// Also reload a [watched](Self::watch_with) configuration when the process
+    /// receives `SIGHUP`.
+    ///
+    /// This is the classic daemon convention for "re-read your configuration"
+    /// and covers changes file watching cannot see, such as environment
+    /// variables or remote sources. The reload goes through the same pipeline
+    /// as a file change: sources are re-collected, transforms and validation
+    /// run, and only a configuration that deserializes replaces the current
+    /// one, once the debounce delay passed without further events.
+    ///
+    /// The handler is registered through `signal-hook`, alongside any other
+    /// handlers of the process, and removed when the
+    /// [`WatchedConfig`](crate::watch::WatchedConfig) is dropped. While it is
+    /// registered, `SIGHUP` no longer terminates the process. Requires the
+    /// `signal` feature and a Unix target.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     workers: u32,
+    /// }
+    ///
+    /// let watched = ConfigBuilder::watch_with::<Config, _>(|| {
+    ///     Ok(ConfigBuilder::new()
+    ///         .with_file("app.toml")?
+    ///         .with_env("APP")
+    ///         .reload_on_sighup())
+    /// })?;
+    /// // `kill -HUP <pid>` now reloads app.toml and the APP_* variables
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[cfg(all(feature = "signal", unix))]
+    pub fn reload_on_sighup(mut self) -> Self {
+        self.reload_on_sighup = true;
+        self
+    }
+
+    /// Whether [`reload_on_sighup`](Self::reload_on_sighup) was requested.
+    #[cfg(feature = "watch")]
+    pub(crate) fn reloads_on_sighup(&self) -> bool {
+        #[cfg(all(feature = "signal", unix))]
+        return self.reload_on_sighup;
+        #[cfg(not(all(feature = "signal", unix)))]
+        return false;
+    }
+
+    /// Paths of the configuration files registered on the builder.
+    #[cfg(feature = "watch")]
+    pub(crate) fn file_paths(&self) -> Vec<PathBuf> {
+        self.sources
+            .iter()
+            .filter_map(|source| source.as_any().downcast_ref::<Config>())
+            .filter_map(|config| config.file_path().map(Path::to_path_buf))
+            .collect()
+    }
+
+    /// Build the final configuration by merging all sources.
+    ///
+    /// This method processes all registered sources in order, applies the configured
+    /// merge strategy, runs any validation, and deserializes the result into the
+    /// target configuration type.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The target configuration type that implements [`serde::de::DeserializeOwned`]
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct AppConfig {
+    ///     database_url: String,
+    ///     port: u16,
+    ///     debug: bool,
+    /// }
+    ///
+    /// let config: AppConfig = ConfigBuilder::new()
+    ///     .with_env_flat("APP")
+    ///     .with_file_optional("config.json")?
+    ///     .with_cli()
+    ///     .build()?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Any required configuration source fails to load
+    /// - Validation fails
+    /// - The final merged configuration cannot be deserialized into type `T`;
+    ///   the [`Error::Deserialize`] names the path of the offending field
+    ///
+    /// Error messages are prefixed with the phase that failed, e.g.
+    /// `during validation` or `during collection from <source>`; see
+    /// [`Error::with_context`].
+    pub fn build<T: DeserializeOwned>(self) -> Result<T> {
+        self.build_with_seed(PhantomData::<T>)
+    }
+
+    /// Build the final configuration using a custom [`DeserializeSeed`].
+    ///
+    /// This is the stateful counterpart of [`build`]: the merged configuration is
+    /// handed to `seed`, letting callers control deserialization (for example to
+    /// inject context or intern strings). A failure is an [`Error::Deserialize`]
+    /// with the JSON pointer of the failing field within the merged
+    /// configuration, e.g. `/database/pool/size`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::de::{DeserializeSeed, Deserializer};
+    /// use serde::Deserialize;
+    ///
+    /// struct Scaled(u64);
+    ///
+    /// impl<'de> DeserializeSeed<'de> for Scaled {
+    ///     type Value = u64;
+    ///
+    ///     fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<u64, D::Error> {
+    ///         #[derive(Deserialize)]
+    ///         struct Raw { timeout: u64 }
+    ///         Ok(Raw::deserialize(d)?.timeout * self.0)
+    ///     }
+    /// }
+    ///
+    /// let millis = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "timeout": 3 }))?
+    ///     .build_with_seed(Scaled(1000))?;
+    /// assert_eq!(millis, 3000);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// [`build`]: ConfigBuilder::build
+    pub fn build_with_seed<'de, S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value> {
+        let merged = self.build_value()?;
+        deserialize_merged(merged, seed)
+    }
+
+    /// Deserialize the merged configuration into `T` without consuming the builder.
+    ///
+    /// The sources are collected on the first call and reused by later calls,
+    /// so several typed views of the same configuration are built from one
+    /// consistent snapshot. Adding a source discards the snapshot, as does
+    /// [`clear_source_cache`](ConfigBuilder::clear_source_cache).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Server { port: u16 }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database { database_url: String }
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080, "database_url": "postgres://db" }))?;
+    /// let server: Server = builder.build_as()?;
+    /// let database: Database = builder.build_as()?;
+    /// assert_eq!(server.port, 8080);
+    /// assert_eq!(database.database_url, "postgres://db");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build`](ConfigBuilder::build).
+    pub fn build_as<T: DeserializeOwned>(&self) -> Result<T> {
+        // Snapshot the sources even when `cache_sources` is off
+        let mut collected = {
+            let mut snapshot = self.snapshot.lock().unwrap_or_else(|e| e.into_inner());
+            match snapshot.as_ref() {
+                Some(collected) => collected.clone(),
+                None => snapshot.insert(self.collect_sources()?).clone(),
+            }
+        };
+        collected.sort_by_key(|(_, source_type)| self.priority_of(*source_type));
+
+        let merged = self.merge_values(collected)?;
+        deserialize_merged(merged, PhantomData::<T>)
+    }
+
+    /// Collect and merge once, then deserialize into every type of a tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Server { port: u16 }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Database { database_url: String }
+    ///
+    /// let (server, database): (Server, Database) = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080, "database_url": "postgres://db" }))?
+    ///     .build_all()?;
+    /// assert_eq!(server.port, 8080);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build`](ConfigBuilder::build), for the first
+    /// type that fails to deserialize.
+    pub fn build_all<T: BuildAll>(self) -> Result<T> {
+        let merged = self.build_value()?;
+        T::from_merged(&merged)
+    }
+
+    /// Build the merged configuration as a JSON value.
+    ///
+    /// Sources are merged in priority order with the builder's
+    /// [merge strategy](ConfigBuilder::merge_strategy), so with
+    /// [`MergeStrategy::Shallow`] a section provided by a higher-priority
+    /// source replaces the whole section below it, while
+    /// [`MergeStrategy::Deep`] keeps the keys it does not mention.
+    /// Transformations and validation run as for [`build`](ConfigBuilder::build).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, MergeStrategy};
+    /// use serde_json::json;
+    ///
+    /// let builder = |strategy| {
+    ///     ConfigBuilder::new()
+    ///         .with_defaults(json!({ "database": { "host": "localhost", "port": 5432 } }))
+    ///         .map(|builder| {
+    ///             builder
+    ///                 .with_env_custom(
+    ///                     Environment::new()
+    ///                         .nested(true)
+    ///                         .override_with("DATABASE_HOST", "db.internal"),
+    ///                 )
+    ///                 .with_merge_strategy(strategy)
+    ///         })
+    /// };
+    ///
+    /// let deep = builder(MergeStrategy::Deep)?.build_value()?;
+    /// assert_eq!(deep["database"], json!({ "host": "db.internal", "port": 5432 }));
+    /// let shallow = builder(MergeStrategy::Shallow)?.build_value()?;
+    /// assert_eq!(shallow["database"], json!({ "host": "db.internal" }));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build`](ConfigBuilder::build), except for
+    /// deserialization.
+    pub fn build_value(self) -> Result<Value> {
+        self.collect_value()
+    }
+
+    /// Build the merged configuration and read the single value at a dotted `path`.
+    ///
+    /// Useful for quick lookups and tooling that need one setting without a
+    /// struct for the whole configuration. Transformations and validation run
+    /// as for [`build_value`](ConfigBuilder::build_value).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = || {
+    ///     ConfigBuilder::new().with_defaults(serde_json::json!({ "http": { "port": 8080 } }))
+    /// };
+    /// assert_eq!(builder()?.get::<u16>("http.port")?, Some(8080));
+    /// assert_eq!(builder()?.get::<u16>("http.timeout")?, None);
+    /// assert!(builder()?.get::<bool>("http.port").is_err());
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`build_value`](ConfigBuilder::build_value), and
+    /// [`Error::Deserialize`] pointing at `path` if the value cannot be
+    /// converted to `T`. A missing path is `Ok(None)`.
+    pub fn get<T: DeserializeOwned>(self, path: &str) -> Result<Option<T>> {
+        let merged = self.build_value()?;
+        let Some(value) = path
+            .split('.')
+            .try_fold(&merged, |current, part| current.get(part))
+        else {
+            return Ok(None);
+        };
+        deserialize_at(value.clone(), PhantomData, path).map(Some)
+    }
+
+    /// Merge all sources without consuming the builder.
+    ///
+    /// Returns the same value as [`build_value`](ConfigBuilder::build_value),
+    /// including validation, and is useful for tooling that inspects the merged
+    /// configuration before building. Combine with
+    /// [`cache_sources`](ConfigBuilder::cache_sources) to avoid collecting the
+    /// sources twice.
+    pub fn collect_value(&self) -> Result<Value> {
+        let collected = self.collect_values()?;
+        self.merge_values(collected)
+    }
+
+    /// Dotted paths of the leaf values that a source other than defaults
+    /// provides, such as `database.host`.
+    ///
+    /// No transformation or validation runs. Sources are collected through
+    /// the [`cache_sources`](ConfigBuilder::cache_sources) cache, so a builder
+    /// with caching enabled can ask this and still build from one collection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment};
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080 }))?
+    ///     .with_env_custom(
+    ///         Environment::new()
+    ///             .with_prefix("PROVIDED")
+    ///             .override_with("PROVIDED_HOST", "db"),
+    ///     )
+    ///     .cache_sources(true);
+    /// assert_eq!(builder.provided_paths()?, vec!["host".to_string()]);
+    /// assert_eq!(builder.build_value()?["port"], 8080);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns collection errors.
+    pub fn provided_paths(&self) -> Result<Vec<String>> {
+        let mut paths = Vec::new();
+        for (value, source) in self.collect_values()? {
+            if source != Source::Default {
+                leaf_paths(&value, "", &mut paths);
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    /// Build the merged configuration as a dynamic [`Config`] that remembers
+    /// which source supplied each value.
+    ///
+    /// Values are read with [`ConfigSource::get_value`] and their origin with
+    /// [`Config::source_of`], e.g. to warn when a secret was passed on the
+    /// command line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, ConfigSource, Source};
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080 }))?
+    ///     .build_config()?;
+    ///
+    /// assert_eq!(config.get_value("port"), Some(serde_json::json!(8080)));
+    /// assert_eq!(config.source_of("port"), Some(Source::Default));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build_value`](ConfigBuilder::build_value).
+    pub fn build_config(self) -> Result<Config> {
+        let (merged, provenance) = self.merge_with_provenance()?;
+        Ok(Config::from_merged(merged, provenance))
+    }
+
+    /// Build the merged configuration together with the source of each value.
+    ///
+    /// The map is keyed by the dotted path of every leaf value, such as
+    /// `database.pool.maxsize`, and names the [`Source`] that last wrote it
+    /// during the merge. This is a dry run for diagnosing precedence problems, e.g. an
+    /// environment variable that unexpectedly overrides a file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, Source};
+    ///
+    /// let (value, provenance) = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "database": { "host": "localhost", "port": 5432 } }))?
+    ///     .with_env_custom(
+    ///         Environment::new()
+    ///             .nested(true)
+    ///             .override_with("DATABASE_PORT", "6543"),
+    ///     )
+    ///     .build_with_provenance()?;
+    ///
+    /// assert_eq!(value["database"]["port"], 6543);
+    /// assert_eq!(provenance["database.port"], Source::Environment);
+    /// assert_eq!(provenance["database.host"], Source::Default);
+    /// assert!(!provenance.contains_key("database"));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`build_value`](ConfigBuilder::build_value).
+    pub fn build_with_provenance(self) -> Result<(Value, HashMap<String, Source>)> {
+        let (merged, mut provenance) = self.merge_with_provenance()?;
+
+        let mut leaves = Vec::new();
+        leaf_paths(&merged, "", &mut leaves);
+        provenance.retain(|path, _| leaves.contains(path));
+        Ok((merged, provenance))
+    }
+
+    /// Run the pipeline and return every intermediate result.
+    ///
+    /// Intended for white-box tests and debugging: the returned [`BuildParts`]
+    /// hold each source's collected value in merge order, the merged value
+    /// after transformations, and the outcome of validation. Unlike
+    /// [`build_value`](Self::build_value), a validation failure does not fail
+    /// the call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, Source};
+    ///
+    /// let parts = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080, "debug": false }))?
+    ///     .with_env_custom(Environment::new().override_with("PORT", "9000"))
+    ///     .validate_required(&["name"])
+    ///     .into_parts()?;
+    ///
+    /// assert_eq!(parts.sources[0].source, Source::Default);
+    /// assert_eq!(parts.sources[1].value["port"], 9000);
+    /// assert_eq!(parts.merged["port"], 9000);
+    /// assert!(parts.validation.is_err());
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns collection and transformation errors.
+    pub fn into_parts(self) -> Result<BuildParts> {
+        let mut sources = self.collect_described()?;
+        sources.sort_by_key(|part| self.priority_of(part.source));
+
+        let collected = sources
+            .iter()
+            .map(|part| (part.value.clone(), part.source))
+            .collect();
+        let mut merged = self.merge_collected(collected)?;
+        for transform in &self.transforms {
+            transform(&mut merged).map_err(|e| e.with_context("during transformation"))?;
+        }
+        let validation = self.validate_merged(&merged);
+
+        Ok(BuildParts {
+            sources,
+            merged,
+            validation,
+        })
+    }
+
+    /// Merge all sources and attribute each path to the source it came from.
+    fn merge_with_provenance(&self) -> Result<(Value, HashMap<String, Source>)> {
+        let collected = self.collect_values()?;
+        let mut provenance = HashMap::new();
+        let merged = self.merge_values_traced(collected, Some(&mut provenance))?;
+        Ok((merged, provenance))
+    }
+
+    /// Collect every source, fallbacks included, ordered by merge priority.
+    fn collect_values(&self) -> Result<Vec<(Value, Source)>> {
+        let mut collected = if self.cache_sources {
+            let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+            match cached.as_ref() {
+                Some(collected) => collected.clone(),
+                None => cached.insert(self.collect_sources()?).clone(),
+            }
+        } else {
+            self.collect_sources()?
+        };
+
+        collected.sort_by_key(|(_, source_type)| self.priority_of(*source_type));
+        Ok(collected)
+    }
+
+    /// Collect fallbacks and sources in registration order.
+    fn collect_sources(&self) -> Result<Vec<(Value, Source)>> {
+        Ok(self
+            .collect_described()?
+            .into_iter()
+            .map(|part| (part.value, part.source))
+            .collect())
+    }
+
+    /// Collect fallbacks and sources in registration order, keeping each
+    /// source's description.
+    fn collect_described(&self) -> Result<Vec<SourcePart>> {
+        let missing: Vec<&str> = self
+            .required_env
+            .iter()
+            .filter(|name| std::env::var_os(name).is_none())
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::Environment(format!(
+                "Missing required environment variables: {}",
+                missing.join(", ")
+            )));
+        }
+
+        // Fallback builders sit beneath every source, defaults included
+        let mut collected = Vec::new();
+        for fallback in &self.fallbacks {
+            let value = fallback
+                .collect_value()
+                .map_err(|e| e.with_context("during collection from fallback builder"))?;
+            collected.push(SourcePart {
+                source: Source::Default,
+                description: "fallback builder".to_string(),
+                value,
+            });
+        }
+
+        for source in self.sources.iter() {
+            let file = source
+                .as_any()
+                .downcast_ref::<Config>()
+                .and_then(Config::file_path);
+            if let Some(path) = file.filter(|path| is_skipped_file(path)) {
+                tracing::info!("Skipping {} listed in {}", path.display(), SKIP_FILES_VAR);
+                continue;
+            }
+
+            let value = match self.collect_source(source) {
+                Ok(value) => value,
+                Err(e) if self.resilient && source.source_type() == Source::Remote => {
+                    tracing::warn!("Skipping remote configuration source: {}", e);
+                    continue;
+                }
+                // A timeout already names the source it waited for
+                Err(e @ Error::Timeout { .. }) => return Err(e),
+                Err(e) => {
+                    return Err(
+                        e.with_context(format!("during collection from {}", source.describe()))
+                    )
+                }
+            };
+            collected.push(SourcePart {
+                source: source.source_type(),
+                description: source.describe(),
+                value,
+            });
+        }
+
+        Ok(collected)
+    }
+
+    /// Merge collected values with the configured strategy, then apply
+    /// transformations and run validation.
+    ///
+    /// Merging itself cannot fail; errors from the later phases are annotated
+    /// with the phase they occurred in.
+    fn merge_values(&self, collected: Vec<(Value, Source)>) -> Result<Value> {
+        self.merge_values_traced(collected, None)
+    }
+
+    /// Like [`merge_values`](Self::merge_values), recording in `provenance`
+    /// the source that last wrote each path of the result.
+    fn merge_values_traced(
+        &self,
+        collected: Vec<(Value, Source)>,
+        mut provenance: Option<&mut HashMap<String, Source>>,
+    ) -> Result<Value> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_collected(&collected);
+
+        let mut merged = self.merge_collected_traced(collected, provenance.as_deref_mut())?;
+
+        for transform in &self.transforms {
+            transform(&mut merged).map_err(|e| e.with_context("during transformation"))?;
+        }
+
+        if let Err(e) = self.validate_merged(&merged) {
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_validation_failure();
+            return Err(e.with_context("during validation"));
+        }
+
+        // Transformations may have removed paths
+        if let Some(provenance) = provenance {
+            provenance.retain(|path, _| lookup_path(&merged, path).is_some());
+        }
+        Ok(merged)
+    }
+
+    /// Check required paths, then run the validation function.
+    fn validate_merged(&self, merged: &Value) -> Result<()> {
+        if self.deny_unknown {
+            self.check_unknown_fields(merged)?;
+        }
+
+        let missing: Vec<&str> = self
+            .required
+            .iter()
+            .filter(|path| {
+                path.split('.')
+                    .try_fold(merged, |current, part| current.get(part))
+                    .filter(|value| !value.is_null())
+                    .is_none()
+            })
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::Validation(format!(
+                "Missing required configuration keys: {}",
+                missing.join(", ")
+            )));
+        }
+
+        match &self.validate {
+            Some(validator) => validator(merged),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail with every merged leaf path not covered by a known field.
+    fn check_unknown_fields(&self, merged: &Value) -> Result<()> {
+        let known = self.known_fields.as_deref().ok_or_else(|| {
+            Error::Config(
+                "deny_unknown_fields needs the target's fields; build through \
+                 #[derive(Gonfig)] or call with_known_fields"
+                    .to_string(),
+            )
+        })?;
+
+        let mut paths = Vec::new();
+        leaf_paths(merged, "", &mut paths);
+        let unknown: Vec<String> = paths
+            .into_iter()
+            .filter(|path| {
+                !known.iter().any(|field| {
+                    path == field
+                        || path.starts_with(&format!("{field}."))
+                        || field.starts_with(&format!("{path}."))
+                })
+            })
+            .collect();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        Err(Error::Validation(format!(
+            "Unknown configuration keys: {}",
+            unknown.join(", ")
+        )))
+    }
+
+    /// Merge collected values with the configured strategy, without validation.
+    fn merge_collected(&self, collected: Vec<(Value, Source)>) -> Result<Value> {
+        self.merge_collected_traced(collected, None)
+    }
+
+    /// Like [`merge_collected`](Self::merge_collected), recording in
+    /// `provenance` the source that last wrote each path of the result.
+    fn merge_collected_traced(
+        &self,
+        collected: Vec<(Value, Source)>,
+        mut provenance: Option<&mut HashMap<String, Source>>,
+    ) -> Result<Value> {
+        let mut collected = collected;
+        collected.sort_by_key(|(_, source_type)| self.priority_of(*source_type));
+
+        let mut trace = provenance.as_deref_mut().map(|provenance| ProvenanceTrace {
+            sources: collected
+                .iter()
+                .map(|(_, source_type)| *source_type)
+                .collect(),
+            provenance,
+        });
+        let layers = collected
+            .into_iter()
+            .map(|(value, source_type)| (value, self.strategy_for(source_type)));
+        let merged = crate::merge::merge_layers(
+            layers,
+            &self.path_strategies,
+            true,
+            trace.as_mut().map(|trace| trace as &mut dyn MergeTrace),
+        )?;
+        // Drop paths written by a layer and removed by a later one
+        if let Some(provenance) = provenance {
+            provenance.retain(|path, _| lookup_path(&merged, path).is_some());
+        }
+        Ok(merged)
+    }
+
+    /// Strategy used to merge a source of `source_type` into the sources below it.
+    fn strategy_for(&self, source_type: Source) -> MergeStrategy {
+        self.source_strategies
+            .iter()
+            .rev()
+            .find(|(source, _)| *source == source_type)
+            .map_or(self.merge_strategy, |(_, strategy)| *strategy)
+    }
+
+    /// Human-readable, multi-line summary of the registered sources.
+    ///
+    /// Each line lists a source in registration order with its type, merge
+    /// priority and a short description (file path and whether it exists,
+    /// environment prefix, ...). Intended for logging at startup.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    ///
+    /// let builder = ConfigBuilder::new().with_env("APP");
+    /// let summary = builder.sources_summary();
+    /// assert!(summary.contains("Environment"));
+    /// assert!(summary.contains("APP_"));
+    /// ```
+    pub fn sources_summary(&self) -> String {
+        let mut summary = format!(
+            "Configuration sources ({}, merge strategy {:?}):",
+            self.sources.len(),
+            self.merge_strategy
+        );
+        for (index, source) in self.sources.iter().enumerate() {
+            let source_type = source.source_type();
+            summary.push_str(&format!(
+                "\n  {}. {:<12} priority={}  {}",
+                index + 1,
+                format!("{source_type:?}"),
+                self.priority_of(source_type),
+                source.describe()
+            ));
+        }
+        summary
+    }
+
+    /// Add a source whose whole value is placed under `key`.
+    ///
+    /// Lets independent parts of an application, such as plugins, each
+    /// contribute a sub-tree without their keys colliding. `key` may be a
+    /// dotted path. The source keeps its own type, so it merges with the
+    /// priority of that type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{dotenv::DotEnv, ConfigBuilder};
+    ///
+    /// let cache = DotEnv::parse("ENABLED=true\n")?;
+    /// let value = ConfigBuilder::new()
+    ///     .with_fragment("plugins.cache", Box::new(cache))
+    ///     .build_value()?;
+    /// assert_eq!(value["plugins"]["cache"]["enabled"], true);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_fragment(self, key: impl Into<String>, source: Box<dyn ConfigSource>) -> Self {
+        self.add_source(Box::new(FragmentSource {
+            key: key.into(),
+            source,
+        }))
+    }
+
+    pub fn sources(&self) -> &[Box<dyn ConfigSource>] {
+        &self.sources.boxed
     }
 
     pub fn get_source<T: ConfigSource + 'static>(&self) -> Option<&T> {
@@ -445,3 +2750,438 @@ impl ConfigBuilder {
             .find_map(|source| source.as_any().downcast_ref::<T>())
     }
 }
+
+/// The builder's sources in registration order.
+///
+/// Sources are shared so that a timed-out collection can keep running on its
+/// own thread, and each also has a boxed handle for [`ConfigBuilder::sources`].
+#[derive(Default)]
+struct SourceList {
+    shared: Vec<Arc<dyn ConfigSource>>,
+    boxed: Vec<Box<dyn ConfigSource>>,
+}
+
+impl SourceList {
+    fn insert(&mut self, index: usize, source: Arc<dyn ConfigSource>) {
+        self.boxed
+            .insert(index, Box::new(SharedSource(Arc::clone(&source))));
+        self.shared.insert(index, source);
+    }
+
+    fn push(&mut self, source: Arc<dyn ConfigSource>) {
+        self.insert(self.shared.len(), source);
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, Arc<dyn ConfigSource>> {
+        self.shared.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.shared.len()
+    }
+}
+
+/// Boxed handle to a shared source, forwarding every call to it.
+struct SharedSource(Arc<dyn ConfigSource>);
+
+impl ConfigSource for SharedSource {
+    fn source_type(&self) -> Source {
+        self.0.source_type()
+    }
+
+    fn collect(&self) -> Result<Value> {
+        self.0.collect()
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.0.has_value(key)
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        self.0.get_value(key)
+    }
+
+    fn describe(&self) -> String {
+        self.0.describe()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.0.as_any()
+    }
+}
+
+/// Source holding default values, merged with the lowest priority.
+struct DefaultsSource {
+    value: Value,
+    label: &'static str,
+}
+
+impl ConfigSource for DefaultsSource {
+    fn collect(&self) -> Result<Value> {
+        Ok(self.value.clone())
+    }
+
+    fn source_type(&self) -> Source {
+        Source::Default
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.value.get(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        self.value.get(key).cloned()
+    }
+
+    fn describe(&self) -> String {
+        self.label.to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Source placing another source's value under a dotted key.
+struct FragmentSource {
+    key: String,
+    source: Box<dyn ConfigSource>,
+}
+
+impl ConfigSource for FragmentSource {
+    fn collect(&self) -> Result<Value> {
+        let mut value = Value::Object(serde_json::Map::new());
+        crate::transform::insert_path(&mut value, &self.key, self.source.collect()?);
+        Ok(value)
+    }
+
+    fn source_type(&self) -> Source {
+        self.source.source_type()
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        self.collect().ok()?.get(key).cloned()
+    }
+
+    fn describe(&self) -> String {
+        format!("{} under `{}`", self.source.describe(), self.key)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Dotted paths of every non-object value below `node`.
+pub(crate) fn leaf_paths(node: &Value, path: &str, paths: &mut Vec<String>) {
+    match node {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                leaf_paths(child, &child_path, paths);
+            }
+        }
+        _ if !path.is_empty() => paths.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Collect `source` on a separate thread, giving up after `timeout`.
+///
+/// The thread is detached rather than joined when the timeout fires: it holds
+/// its own reference to `source`, runs until `collect` returns and then drops
+/// the result, since the receiving end is gone by then.
+fn collect_with_timeout(source: Arc<dyn ConfigSource>, timeout: Duration) -> Result<Value> {
+    let (sender, receiver) = mpsc::channel();
+    let worker = Arc::clone(&source);
+    std::thread::spawn(move || {
+        let _ = sender.send(worker.collect());
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Error::Timeout {
+            source_name: source.describe(),
+            timeout,
+        }),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(Error::Config(format!(
+            "{} stopped without returning a value",
+            source.describe()
+        ))),
+    }
+}
+
+/// Intermediate results of the build pipeline, returned by
+/// [`ConfigBuilder::into_parts`].
+#[derive(Debug)]
+pub struct BuildParts {
+    /// Value collected from each source, in merge order (lowest priority first).
+    pub sources: Vec<SourcePart>,
+    /// Merged value after transformations, before validation.
+    pub merged: Value,
+    /// Outcome of required-key checks and the validation function.
+    pub validation: Result<()>,
+}
+
+/// One source's contribution to a build.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcePart {
+    /// Kind of source.
+    pub source: Source,
+    /// The source's [`describe`](ConfigSource::describe) text.
+    pub description: String,
+    /// Value collected from the source.
+    pub value: Value,
+}
+
+/// Deserialize a merged configuration, reporting the path of a failing field.
+pub(crate) fn deserialize_merged<'de, S: DeserializeSeed<'de>>(
+    merged: Value,
+    seed: S,
+) -> Result<S::Value> {
+    deserialize_at(merged, seed, "")
+}
+
+/// Deserialize `value`, found at the dotted path `base` of the configuration.
+///
+/// Failures become [`Error::Deserialize`] with the JSON pointer of the
+/// offending value, counted from the configuration's root.
+pub(crate) fn deserialize_at<'de, S: DeserializeSeed<'de>>(
+    value: Value,
+    seed: S,
+    base: &str,
+) -> Result<S::Value> {
+    let mut track = serde_path_to_error::Track::new();
+    let deserializer = serde_path_to_error::Deserializer::new(value, &mut track);
+    seed.deserialize(deserializer).map_err(|source| {
+        let base = base.split('.').filter(|part| !part.is_empty());
+        let tracked = track.path();
+        let tracked = tracked.iter().map(|segment| match segment {
+            serde_path_to_error::Segment::Seq { index } => index.to_string(),
+            serde_path_to_error::Segment::Map { key } => key.clone(),
+            serde_path_to_error::Segment::Enum { variant } => variant.clone(),
+            serde_path_to_error::Segment::Unknown => "?".to_string(),
+        });
+        let path = base
+            .map(str::to_string)
+            .chain(tracked)
+            .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+            .collect();
+        Error::Deserialize {
+            path,
+            source,
+            context: String::new(),
+        }
+        .with_context("during deserialization")
+    })
+}
+
+/// Deserialize a merged configuration value into `T`.
+///
+/// This is the conversion [`ConfigBuilder::build`] ends with, for values
+/// merged or edited by hand. Unlike `serde_json::from_value`, a failure is
+/// reported as [`Error::Deserialize`] with the JSON pointer of the offending
+/// field.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::Error;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Server { ports: Vec<u16> }
+///
+/// let err = gonfig::from_value::<Server>(serde_json::json!({ "ports": [80, "x"] })).unwrap_err();
+/// assert!(matches!(err, Error::Deserialize { ref path, .. } if path == "/ports/1"));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Deserialize`] if `value` does not match `T`.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T> {
+    deserialize_merged(value, PhantomData)
+}
+
+/// Deserialize `value`, found at the dotted `path` of a configuration.
+///
+/// Like [`from_value`], with the JSON pointer of a failure counted from the
+/// configuration's root, e.g. `/server/port` for a bad `port` of the value at
+/// `server`.
+///
+/// # Errors
+///
+/// Returns [`Error::Deserialize`] if `value` does not match `T`.
+pub fn from_value_at<T: DeserializeOwned>(value: Value, path: &str) -> Result<T> {
+    deserialize_at(value, PhantomData, path)
+}
+
+/// Whether `path` ends with one of the entries of [`SKIP_FILES_VAR`].
+pub(crate) fn is_skipped_file(path: &Path) -> bool {
+    std::env::var(SKIP_FILES_VAR)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| path.ends_with(entry))
+}
+
+/// Types built together from one merged configuration by
+/// [`ConfigBuilder::build_all`].
+///
+/// Implemented for tuples of two to four [`DeserializeOwned`] types.
+pub trait BuildAll: Sized {
+    /// Deserialize every element from `merged`.
+    fn from_merged(merged: &Value) -> Result<Self>;
+}
+
+macro_rules! impl_build_all {
+    ($($ty:ident),+) => {
+        impl<$($ty: DeserializeOwned),+> BuildAll for ($($ty,)+) {
+            fn from_merged(merged: &Value) -> Result<Self> {
+                Ok(($(deserialize_merged(merged.clone(), PhantomData::<$ty>)?,)+))
+            }
+        }
+    };
+}
+
+impl_build_all!(A, B);
+impl_build_all!(A, B, C);
+impl_build_all!(A, B, C, D);
+
+/// The user's configuration directory, honoring `XDG_CONFIG_HOME` on every platform.
+fn user_config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.config_dir().to_path_buf()))
+}
+
+/// Paths, relative to `dir`, of the visible files of `format` below `dir`.
+///
+/// Hidden entries (names starting with `.`) are skipped, and subdirectories
+/// are only entered when `recursive` is set.
+fn collect_directory(
+    dir: &Path,
+    format: &ConfigFormat,
+    recursive: bool,
+    files: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let walk = |dir: &Path| -> Result<Vec<(PathBuf, bool)>> {
+        std::fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| {
+                        let entry = entry?;
+                        Ok((entry.path(), entry.file_type()?.is_dir()))
+                    })
+                    .collect()
+            })
+            .map_err(|e| Error::io_at(dir, e))
+    };
+
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for (path, is_dir) in walk(&current)? {
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if is_dir {
+                if recursive {
+                    pending.push(path);
+                }
+            } else if ConfigFormat::from_path(&path).as_ref() == Some(format) {
+                if let Ok(relative) = path.strip_prefix(dir) {
+                    files.push(relative.to_path_buf());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Value at the dotted `path` of `value`.
+fn lookup_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, part| current.get(part))
+}
+
+/// Records the source that last wrote each path while merging the layers of
+/// collected sources, in `sources` order.
+struct ProvenanceTrace<'a> {
+    sources: Vec<Source>,
+    provenance: &'a mut HashMap<String, Source>,
+}
+
+impl MergeTrace for ProvenanceTrace<'_> {
+    fn write(
+        &mut self,
+        layer: usize,
+        strategy: MergeStrategy,
+        base: Option<&Value>,
+        incoming: &Value,
+        path: &str,
+    ) {
+        record_writes(
+            strategy,
+            base,
+            incoming,
+            path,
+            self.sources[layer],
+            self.provenance,
+        );
+    }
+
+    fn reset(&mut self, path: &str) {
+        let prefix = format!("{path}.");
+        self.provenance
+            .retain(|traced, _| traced != path && !traced.starts_with(&prefix));
+    }
+}
+
+/// Attribute to `source` every path that merging `incoming`, found at `path`,
+/// into `base`, the value already at `path`, with `strategy` writes.
+///
+/// Every strategy lets the incoming value win, except
+/// [`MergeStrategy::DeepPreferExisting`], which keeps values `base` already
+/// has. Paths the merge removes are pruned by the caller.
+fn record_writes(
+    strategy: MergeStrategy,
+    base: Option<&Value>,
+    incoming: &Value,
+    path: &str,
+    source: Source,
+    provenance: &mut HashMap<String, Source>,
+) {
+    if !path.is_empty() {
+        let kept = strategy == MergeStrategy::DeepPreferExisting
+            && base.is_some_and(|base| !(base.is_object() && incoming.is_object()));
+        if kept {
+            return;
+        }
+        provenance.insert(path.to_string(), source);
+    }
+
+    if let Value::Object(map) = incoming {
+        for (key, child) in map {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            let base = base.and_then(|base| base.get(key));
+            record_writes(strategy, base, child, &child_path, source, provenance);
+        }
+    }
+}
@@ -1,16 +1,24 @@
 use crate::{
-    error::Result,
+    error::{Error, Result},
     source::{ConfigSource, Source},
+    transform::ValueParser,
 };
 use clap::Parser;
 use serde_json::Value;
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
 pub struct Cli {
     parsed_values: HashMap<String, Value>,
+    /// Text of each flag that was given a single value, before coercion.
+    raw_values: HashMap<String, String>,
     field_mappings: HashMap<String, String>,
+    excluded: HashSet<String>,
+    conflicts: Vec<(String, String)>,
+    short_flags: HashMap<char, String>,
+    /// Index in `args` of each flag's last occurrence, for `from_vec` sources.
+    positions: HashMap<String, usize>,
 }
 
 impl Cli {
@@ -18,33 +26,77 @@ impl Cli {
         Self::from_vec(std::env::args().collect())
     }
 
+    /// Parse `args`, skipping the program name in `args[0]`.
+    ///
+    /// Flags are written `--key value`, `--key=value`, `-k value` or
+    /// `-k=value`; a flag not followed by a value is `true`. Values are
+    /// coerced to booleans, numbers and JSON arrays where they parse as such.
+    ///
+    /// A flag passed more than once takes the value of its last occurrence,
+    /// so `--port 1 --port 2` sets `port` to `2`. This also holds across a
+    /// long flag and its [short alias](Cli::with_short_flag).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{Cli, ConfigSource};
+    ///
+    /// let cli = Cli::from_vec(
+    ///     ["app", "--port", "8080", "--debug", "--port=9090"]
+    ///         .map(String::from)
+    ///         .to_vec(),
+    /// );
+    /// assert_eq!(cli.get_value("port"), Some(serde_json::json!(9090)));
+    /// assert_eq!(cli.get_value("debug"), Some(serde_json::json!(true)));
+    /// ```
     pub fn from_vec(args: Vec<String>) -> Self {
         let mut parsed_values = HashMap::new();
+        let mut raw_values = HashMap::new();
+        let mut positions = HashMap::new();
+        let mut record = |key: &str, raw: Option<&str>, position: usize| {
+            let value = raw.map_or(Value::Bool(true), Self::parse_value);
+            match raw {
+                Some(raw) => raw_values.insert(key.to_string(), raw.to_string()),
+                None => raw_values.remove(key),
+            };
+            parsed_values.insert(key.to_string(), value);
+            positions.insert(key.to_string(), position);
+        };
 
         let mut i = 1;
         while i < args.len() {
             let arg = &args[i];
+            let short_with_value = arg
+                .strip_prefix('-')
+                .and_then(|rest| rest.split_once('='))
+                .filter(|(key, _)| key.chars().count() == 1);
 
-            if arg.starts_with("--") {
+            if let Some((key, value)) = arg.strip_prefix("--").and_then(|key| key.split_once('=')) {
+                record(key, Some(value), i);
+                i += 1;
+            } else if arg.starts_with("--") {
                 let key = arg.trim_start_matches("--");
 
                 if i + 1 < args.len() && !args[i + 1].starts_with("--") {
                     let value = &args[i + 1];
-                    parsed_values.insert(key.to_string(), Self::parse_value(value));
+                    record(key, Some(value), i);
                     i += 2;
                 } else {
-                    parsed_values.insert(key.to_string(), Value::Bool(true));
+                    record(key, None, i);
                     i += 1;
                 }
+            } else if let Some((key, value)) = short_with_value {
+                record(key, Some(value), i);
+                i += 1;
             } else if arg.starts_with("-") && arg.len() == 2 {
                 let key = arg.trim_start_matches("-");
 
                 if i + 1 < args.len() && !args[i + 1].starts_with("-") {
                     let value = &args[i + 1];
-                    parsed_values.insert(key.to_string(), Self::parse_value(value));
+                    record(key, Some(value), i);
                     i += 2;
                 } else {
-                    parsed_values.insert(key.to_string(), Value::Bool(true));
+                    record(key, None, i);
                     i += 1;
                 }
             } else {
@@ -54,7 +106,12 @@ impl Cli {
 
         Self {
             parsed_values,
+            raw_values,
             field_mappings: HashMap::new(),
+            excluded: HashSet::new(),
+            conflicts: Vec::new(),
+            short_flags: HashMap::new(),
+            positions,
         }
     }
 
@@ -74,10 +131,97 @@ impl Cli {
 
         Ok(Self {
             parsed_values,
+            raw_values: HashMap::new(),
             field_mappings: HashMap::new(),
+            excluded: HashSet::new(),
+            conflicts: Vec::new(),
+            short_flags: HashMap::new(),
+            positions: HashMap::new(),
         })
     }
 
+    /// Read the flags clap matched for a `command`.
+    ///
+    /// Values are keyed by argument id, which for a command generated by the
+    /// derive macro's `gonfig_clap_command()` is the field path; a
+    /// [`ConfigBuilder`](crate::ConfigBuilder) places dotted ids into nested
+    /// sections. Arguments that only hold their clap
+    /// default are left out, so defaults shown in `--help` never override
+    /// configuration files or the environment. `SetTrue` flags become `true`,
+    /// `Append` arguments become arrays, and other values are coerced like
+    /// [`from_vec`](Cli::from_vec) values. Requires the `clap` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use clap::{Arg, ArgAction, Command};
+    /// use gonfig::{Cli, ConfigBuilder};
+    ///
+    /// let command = Command::new("app")
+    ///     .arg(Arg::new("port").long("port").default_value("8080"))
+    ///     .arg(Arg::new("log.level").long("log-level"))
+    ///     .arg(Arg::new("debug").long("debug").action(ArgAction::SetTrue));
+    /// let matches = command
+    ///     .clone()
+    ///     .try_get_matches_from(["app", "--log-level", "debug", "--debug"])
+    ///     .unwrap();
+    ///
+    /// let value = ConfigBuilder::new()
+    ///     .with_cli_custom(Cli::from_clap(&command, &matches))
+    ///     .build_value()?;
+    /// assert_eq!(value["log"]["level"], "debug");
+    /// assert_eq!(value["debug"], true);
+    /// assert!(value.get("port").is_none());
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[cfg(feature = "clap")]
+    pub fn from_clap(command: &clap::Command, matches: &clap::ArgMatches) -> Self {
+        use clap::parser::ValueSource;
+        use clap::ArgAction;
+
+        let mut parsed_values = HashMap::new();
+        let mut raw_values = HashMap::new();
+        let mut field_mappings = HashMap::new();
+        for arg in command.get_arguments() {
+            let id = arg.get_id().as_str();
+            if matches.value_source(id) != Some(ValueSource::CommandLine) {
+                continue;
+            }
+            let Some(raw) = matches.get_raw(id) else {
+                continue;
+            };
+            let mut values = raw.map(|value| value.to_string_lossy().into_owned());
+            let value = match arg.get_action() {
+                ArgAction::SetTrue => Value::Bool(true),
+                ArgAction::SetFalse => Value::Bool(false),
+                ArgAction::Append => {
+                    Value::Array(values.map(|value| Self::parse_value(&value)).collect())
+                }
+                _ => match values.next_back() {
+                    Some(value) => {
+                        raw_values.insert(id.to_string(), value.clone());
+                        Self::parse_value(&value)
+                    }
+                    None => continue,
+                },
+            };
+            if id.contains('.') {
+                field_mappings.insert(id.to_string(), id.to_string());
+            }
+            parsed_values.insert(id.to_string(), value);
+        }
+
+        Self {
+            parsed_values,
+            raw_values,
+            field_mappings,
+            excluded: HashSet::new(),
+            conflicts: Vec::new(),
+            short_flags: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
     pub fn with_field_mapping(
         mut self,
         field_name: impl Into<String>,
@@ -88,6 +232,149 @@ impl Cli {
         self
     }
 
+    /// Accept `-{short}` as another spelling of `--{cli_key}`.
+    ///
+    /// A value passed with the short flag is reported under the long name, so
+    /// field mappings, [`exclude`](Cli::exclude) and
+    /// [`with_conflict`](Cli::with_conflict) see it like the long form. When
+    /// both spellings are passed, the one given last wins.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{Cli, ConfigSource};
+    ///
+    /// let cli = Cli::from_vec(vec!["app".into(), "-p".into(), "8080".into()])
+    ///     .with_field_mapping("port", "port")
+    ///     .with_short_flag('p', "port");
+    /// assert_eq!(cli.get_value("port"), Some(serde_json::json!(8080)));
+    /// assert!(!cli.has_value("p"));
+    /// ```
+    pub fn with_short_flag(mut self, short: char, cli_key: impl Into<String>) -> Self {
+        self.short_flags.insert(short, cli_key.into());
+        self
+    }
+
+    /// Ignore a flag entirely, as if it had not been passed.
+    ///
+    /// Used for sensitive fields that must not be supplied on the command line,
+    /// where they would leak into process listings and shell history.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{Cli, ConfigSource};
+    ///
+    /// let cli = Cli::from_vec(vec!["app".into(), "--password".into(), "hunter2".into()])
+    ///     .exclude("password");
+    /// assert!(!cli.has_value("password"));
+    /// ```
+    pub fn exclude(mut self, cli_key: impl Into<String>) -> Self {
+        self.excluded.insert(cli_key.into());
+        self
+    }
+
+    /// Declare two flags as mutually exclusive.
+    ///
+    /// Collecting the source fails with [`Error::Cli`] when both flags are
+    /// passed, mirroring clap's `conflicts_with`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{Cli, ConfigSource};
+    ///
+    /// let cli = Cli::from_vec(vec!["app".into(), "--quiet".into(), "--verbose".into()])
+    ///     .with_conflict("quiet", "verbose");
+    /// assert!(cli.collect().is_err());
+    /// ```
+    ///
+    /// [`Error::Cli`]: crate::Error::Cli
+    pub fn with_conflict(mut self, flag: impl Into<String>, other: impl Into<String>) -> Self {
+        self.conflicts.push((flag.into(), other.into()));
+        self
+    }
+
+    fn check_conflicts(&self) -> Result<()> {
+        let passed = |flag: &str| {
+            let short = self.short_flags.iter().any(|(short, long)| {
+                long == flag && self.parsed_values.contains_key(&short.to_string())
+            });
+            (self.parsed_values.contains_key(flag) || short) && !self.excluded.contains(flag)
+        };
+        for (flag, other) in &self.conflicts {
+            if passed(flag) && passed(other) {
+                return Err(Error::Cli(format!(
+                    "--{flag} cannot be used together with --{other}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Flags that are not excluded, keyed by flag name.
+    fn visible_values(&self) -> HashMap<String, Value> {
+        self.visible(&self.parsed_values)
+    }
+
+    /// Entries of `flags` that are not excluded, with short flags reported
+    /// under their long name.
+    fn visible<V: Clone>(&self, flags: &HashMap<String, V>) -> HashMap<String, V> {
+        let mut values: HashMap<String, V> = flags
+            .iter()
+            .filter(|(key, _)| !self.excluded.contains(key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        // Short flags are reported under their long name, the spelling passed
+        // last winning; without positions the long flag wins
+        for (short, long) in &self.short_flags {
+            let short = short.to_string();
+            if let Some(value) = values.remove(&short) {
+                if self.excluded.contains(long) {
+                    continue;
+                }
+                let position = |key: &str| self.positions.get(key).copied().unwrap_or(0);
+                if !values.contains_key(long) || position(&short) > position(long) {
+                    values.insert(long.clone(), value);
+                }
+            }
+        }
+
+        values
+    }
+
+    /// Collect the flags with every flag registered through
+    /// [`with_field_mapping`](Cli::with_field_mapping) reported under its
+    /// field name instead of its flag name, so each value appears once.
+    ///
+    /// This is what [`ConfigBuilder`](crate::ConfigBuilder) merges. Mapped
+    /// field names may be dotted paths into nested sections. A flag whose
+    /// field has one of `parsers` is read by it from the flag's text.
+    pub(crate) fn collect_fields(&self, parsers: &[(String, ValueParser)]) -> Result<Value> {
+        self.check_conflicts()?;
+        let mut values = self.visible_values();
+        if !parsers.is_empty() {
+            let raw = self.visible(&self.raw_values);
+            for (path, parser) in parsers {
+                let flag = self.field_mappings.get(path).unwrap_or(path);
+                if let Some(text) = raw.get(flag) {
+                    values.insert(flag.clone(), parser.parse(path, text)?);
+                }
+            }
+        }
+        let mut collected = Value::Object(Default::default());
+        for (field_name, cli_key) in &self.field_mappings {
+            if let Some(value) = values.remove(cli_key) {
+                crate::transform::insert_path(&mut collected, field_name, value);
+            }
+        }
+        if let Value::Object(map) = &mut collected {
+            map.extend(values);
+        }
+        Ok(collected)
+    }
+
     fn parse_value(value: &str) -> Value {
         if let Ok(b) = value.parse::<bool>() {
             return Value::Bool(b);
@@ -124,20 +411,20 @@ impl ConfigSource for Cli {
     }
 
     fn collect(&self) -> Result<Value> {
-        Ok(Value::Object(
-            self.parsed_values
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect(),
-        ))
+        self.check_conflicts()?;
+        Ok(Value::Object(self.visible_values().into_iter().collect()))
     }
 
     fn has_value(&self, key: &str) -> bool {
-        self.parsed_values.contains_key(key)
+        self.visible_values().contains_key(key)
     }
 
     fn get_value(&self, key: &str) -> Option<Value> {
-        self.parsed_values.get(key).cloned()
+        self.visible_values().remove(key)
+    }
+
+    fn describe(&self) -> String {
+        format!("command line ({} args)", self.parsed_values.len())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -1,10 +1,18 @@
 use crate::{
+    builder::{deserialize_at, deserialize_merged, is_skipped_file, SKIP_FILES_VAR},
+    environment::Environment,
     error::{Error, Result},
+    interpolate::Interpolation,
+    merge::MergeStrategy,
     source::{ConfigSource, Source},
 };
-use serde_json::Value;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use std::any::Any;
+use std::collections::HashMap;
 use std::fs;
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 
 /// Supported configuration file formats.
@@ -33,6 +41,15 @@ pub enum ConfigFormat {
     Yaml,
     /// TOML format (.toml files)
     Toml,
+    /// INI format (.ini files)
+    ///
+    /// `[section]` headers become nested objects (`[database.replica]` nests
+    /// further) and keys before the first header stay at the top level.
+    /// Unquoted values are type-coerced like environment variables, so
+    /// `port = 5432` is a number; quoted values are always strings. A key
+    /// repeated within a section keeps its last value and repeated sections
+    /// are merged. Lines starting with `;` or `#` are comments.
+    Ini,
 }
 
 impl ConfigFormat {
@@ -42,6 +59,7 @@ impl ConfigFormat {
     /// - `json` → [`ConfigFormat::Json`]
     /// - `yaml`, `yml` → [`ConfigFormat::Yaml`]
     /// - `toml` → [`ConfigFormat::Toml`]
+    /// - `ini` → [`ConfigFormat::Ini`]
     ///
     /// # Examples
     ///
@@ -58,10 +76,218 @@ impl ConfigFormat {
             "json" => Some(ConfigFormat::Json),
             "yaml" | "yml" => Some(ConfigFormat::Yaml),
             "toml" => Some(ConfigFormat::Toml),
+            "ini" => Some(ConfigFormat::Ini),
             _ => None,
         }
     }
 
+    /// Detect configuration format from a file path's extension.
+    ///
+    /// Uses the same mapping as [`from_extension`](ConfigFormat::from_extension),
+    /// ignoring case, and returns `None` for paths without a recognized
+    /// extension. Files added with
+    /// [`ConfigBuilder::with_file`](crate::ConfigBuilder::with_file) are
+    /// detected this way and fail with [`Error::UnknownFormat`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(ConfigFormat::from_path(Path::new("app.YML")), Some(ConfigFormat::Yaml));
+    /// assert_eq!(ConfigFormat::from_path(Path::new("conf/app.ini")), Some(ConfigFormat::Ini));
+    /// assert_eq!(ConfigFormat::from_path(Path::new("Makefile")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Render a configuration value in this format.
+    ///
+    /// JSON output is pretty-printed. TOML requires the value to be an object
+    /// and cannot represent `null`, so null entries make TOML rendering fail.
+    /// INI has the same restrictions; nested objects become dotted section
+    /// headers and arrays are written as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Serialization`] if the value cannot be represented in
+    /// the format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigFormat;
+    ///
+    /// let yaml = ConfigFormat::Yaml.serialize(&serde_json::json!({ "port": 8080 }))?;
+    /// assert_eq!(yaml.trim(), "port: 8080");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn serialize(&self, value: &Value) -> Result<String> {
+        match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value)
+                .map_err(|e| Error::Serialization(format!("JSON serialize error: {e}"))),
+            ConfigFormat::Yaml => serde_yaml::to_string(value)
+                .map_err(|e| Error::Serialization(format!("YAML serialize error: {e}"))),
+            ConfigFormat::Toml => toml::to_string_pretty(value)
+                .map_err(|e| Error::Serialization(format!("TOML serialize error: {e}"))),
+            ConfigFormat::Ini => Self::serialize_ini(value),
+        }
+    }
+
+    fn serialize_ini(value: &Value) -> Result<String> {
+        let root = value.as_object().ok_or_else(|| {
+            Error::Serialization("INI serialize error: value must be an object".to_string())
+        })?;
+
+        let mut output = String::new();
+        let mut sections = vec![(String::new(), root)];
+        while let Some((name, section)) = sections.pop() {
+            let entries: Vec<_> = section.iter().filter(|(_, v)| !v.is_object()).collect();
+            if !name.is_empty() && !entries.is_empty() {
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("[{name}]\n"));
+            }
+            for (key, entry) in entries {
+                let rendered = match entry {
+                    Value::Null => {
+                        return Err(Error::Serialization(format!(
+                            "INI serialize error: cannot represent null at `{key}`"
+                        )))
+                    }
+                    Value::String(text) if Environment::parse_env_value(text) == *entry => {
+                        if text.trim() != text || text.contains(['#', ';', '"', '\'']) {
+                            entry.to_string()
+                        } else {
+                            text.clone()
+                        }
+                    }
+                    other => other.to_string(),
+                };
+                output.push_str(&format!("{key} = {rendered}\n"));
+            }
+
+            // Reversed so sections come out in document order
+            for (key, child) in section.iter().rev() {
+                if let Value::Object(child) = child {
+                    let child_name = if name.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{name}.{key}")
+                    };
+                    sections.push((child_name, child));
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    fn parse_ini(content: &str) -> Result<Value> {
+        let mut root = Map::new();
+        let mut section: Vec<String> = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: &str| {
+                Error::Serialization(format!("INI parse error: {message} on line {}", index + 1))
+            };
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| error("malformed section header"))?;
+                section = name
+                    .split('.')
+                    .map(|part| part.trim().to_string())
+                    .collect();
+                continue;
+            }
+
+            let (key, raw) = line
+                .split_once('=')
+                .filter(|(key, _)| !key.trim().is_empty())
+                .ok_or_else(|| error("expected `key = value`"))?;
+            let value = Self::parse_ini_value(raw.trim()).map_err(|msg| error(&msg))?;
+
+            let mut table = &mut root;
+            for part in &section {
+                let entry = table
+                    .entry(part.clone())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                table = entry.as_object_mut().ok_or_else(|| {
+                    error(&format!(
+                        "section `{part}` clashes with a key of the same name"
+                    ))
+                })?;
+            }
+            table.insert(key.trim().to_string(), value);
+        }
+
+        Ok(Value::Object(root))
+    }
+
+    fn parse_ini_value(raw: &str) -> std::result::Result<Value, String> {
+        if raw.starts_with('"') {
+            let mut escaped = false;
+            let end = raw
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| {
+                    let closes = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closes
+                })
+                .map(|(i, _)| i + 1)
+                .ok_or_else(|| "unterminated double-quoted value".to_string())?;
+            return serde_json::from_str::<String>(&raw[..end])
+                .map(Value::String)
+                .map_err(|e| format!("invalid quoted value ({e})"));
+        }
+
+        if let Some(rest) = raw.strip_prefix('\'') {
+            let end = rest
+                .find('\'')
+                .ok_or_else(|| "unterminated single-quoted value".to_string())?;
+            return Ok(Value::String(rest[..end].to_string()));
+        }
+
+        // An unquoted value ends at a comment introduced by whitespace
+        let end = [" ;", "\t;", " #", "\t#"]
+            .iter()
+            .filter_map(|marker| raw.find(marker))
+            .min()
+            .unwrap_or(raw.len());
+        Ok(Environment::parse_env_value(raw[..end].trim()))
+    }
+
+    /// Parse every `---`-separated YAML document and deep-merge them in order,
+    /// so later documents override earlier ones.
+    fn parse_yaml_documents(content: &str) -> Result<Value> {
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let value = Value::deserialize(document)
+                .map_err(|e| Error::Serialization(format!("YAML parse error: {e}")))?;
+            documents.push(value);
+        }
+
+        // Empty documents (e.g. a trailing `---`) contribute nothing
+        let mut documents = documents.into_iter().filter(|document| !document.is_null());
+        let first = documents.next().unwrap_or(Value::Null);
+        Ok(documents.fold(first, |merged, document| {
+            MergeStrategy::Deep.merge(merged, document)
+        }))
+    }
+
     /// Parse configuration content according to the format.
     ///
     /// Converts the string content into a [`serde_json::Value`] that can be
@@ -81,6 +307,9 @@ impl ConfigFormat {
     /// Returns [`Error::Serialization`] if the content cannot be parsed
     /// according to the format's syntax rules.
     ///
+    /// YAML content may hold several `---`-separated documents; they are
+    /// deep-merged in order into a single value.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -94,8 +323,7 @@ impl ConfigFormat {
         match self {
             ConfigFormat::Json => serde_json::from_str(content)
                 .map_err(|e| Error::Serialization(format!("JSON parse error: {e}"))),
-            ConfigFormat::Yaml => serde_yaml::from_str(content)
-                .map_err(|e| Error::Serialization(format!("YAML parse error: {e}"))),
+            ConfigFormat::Yaml => Self::parse_yaml_documents(content),
             ConfigFormat::Toml => {
                 let toml_value: toml::Value = toml::from_str(content)
                     .map_err(|e| Error::Serialization(format!("TOML parse error: {e}")))?;
@@ -103,6 +331,7 @@ impl ConfigFormat {
                     Error::Serialization(format!("TOML to JSON conversion error: {e}"))
                 })
             }
+            ConfigFormat::Ini => Self::parse_ini(content),
         }
     }
 }
@@ -125,12 +354,22 @@ impl ConfigFormat {
 /// let config = Config::from_file_optional("optional.yaml")?;
 /// # Ok::<(), gonfig::Error>(())
 /// ```
+///
+/// A `Config` can also hold the merged result of a [`ConfigBuilder`], produced
+/// by [`ConfigBuilder::build_config`]. Such a config remembers which source
+/// supplied each value, see [`Config::source_of`].
+///
+/// [`ConfigBuilder`]: crate::ConfigBuilder
+/// [`ConfigBuilder::build_config`]: crate::ConfigBuilder::build_config
 #[derive(Debug, Clone)]
 pub struct Config {
     path: PathBuf,
     format: ConfigFormat,
     required: bool,
     data: Option<Value>,
+    provenance: Option<HashMap<String, Source>>,
+    interpolation: Option<Interpolation>,
+    section: Option<String>,
 }
 
 impl Config {
@@ -156,32 +395,18 @@ impl Config {
     ///
     /// # Errors
     ///
-    /// - [`Error::Config`] if the file extension is not recognized
+    /// - [`Error::UnknownFormat`] if the file extension is not recognized
     /// - [`Error::Io`] if the file cannot be read
     /// - [`Error::Serialization`] if the file cannot be parsed
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref().to_path_buf();
-        let format = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .and_then(ConfigFormat::from_extension)
-            .ok_or_else(|| Error::Config(format!("Unknown config format for file: {path:?}")))?;
-
-        let mut config = Self {
-            path,
-            format,
-            required: true,
-            data: None,
-        };
-
-        config.load()?;
-        Ok(config)
+        Self::open(path.as_ref(), None, true, None)
     }
 
     /// Load an optional configuration file with automatic format detection.
     ///
     /// Similar to [`from_file`], but won't return an error if the file doesn't exist.
-    /// Parse errors will still cause the method to fail.
+    /// A file that exists but cannot be read or parsed is logged and treated
+    /// as empty.
     ///
     /// # Arguments
     ///
@@ -197,27 +422,86 @@ impl Config {
     /// # Ok::<(), gonfig::Error>(())
     /// ```
     ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownFormat`] if the file extension is not recognized.
+    ///
     /// [`from_file`]: Config::from_file
     pub fn from_file_optional(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref().to_path_buf();
-        let format = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .and_then(ConfigFormat::from_extension)
-            .ok_or_else(|| Error::Config(format!("Unknown config format for file: {path:?}")))?;
+        Self::open(path.as_ref(), None, false, None)
+    }
+
+    /// Load a configuration file with explicit format specification.
+    ///
+    /// Use this method when you need to override automatic format detection
+    /// or when working with files that don't have standard extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the configuration file
+    /// * `format` - The format to use for parsing
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{Config, ConfigFormat};
+    ///
+    /// // Force JSON parsing for a file without extension
+    /// let config = Config::with_format("config", ConfigFormat::Json)?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Io`] if the file cannot be read
+    /// - [`Error::Serialization`] if the file cannot be parsed as `format`
+    pub fn with_format(path: impl AsRef<Path>, format: ConfigFormat) -> Result<Self> {
+        Self::open(path.as_ref(), Some(format), true, None)
+    }
+
+    /// Load a file, detecting its format from the extension unless `format` is
+    /// given, and substituting placeholders with `interpolation` before parsing.
+    ///
+    /// A missing optional file yields an empty configuration, and an optional
+    /// file that fails to parse is logged and ignored.
+    pub(crate) fn open(
+        path: &Path,
+        format: Option<ConfigFormat>,
+        required: bool,
+        interpolation: Option<Interpolation>,
+    ) -> Result<Self> {
+        let path = path.to_path_buf();
+        let format = match format {
+            Some(format) => format,
+            None => ConfigFormat::from_path(&path).ok_or_else(|| Error::UnknownFormat {
+                extension: path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                context: String::new(),
+            })?,
+        };
 
         let path_display = path.display().to_string();
         let mut config = Self {
             path,
             format,
-            required: false,
+            required,
             data: None,
+            provenance: None,
+            interpolation,
+            section: None,
         };
 
+        if required {
+            config.load()?;
+            return Ok(config);
+        }
+
         // For optional configs, only ignore file-not-found errors
         match config.load() {
             Ok(()) => {}
-            Err(Error::Io(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(Error::Io { ref source, .. }) if source.kind() == std::io::ErrorKind::NotFound => {
                 // File not found is OK for optional configs
             }
             Err(e) => {
@@ -232,46 +516,129 @@ impl Config {
         Ok(config)
     }
 
-    /// Load a configuration file with explicit format specification.
+    /// Wrap an already merged configuration together with its provenance.
+    pub(crate) fn from_merged(data: Value, provenance: HashMap<String, Source>) -> Self {
+        Self {
+            path: PathBuf::new(),
+            format: ConfigFormat::Json,
+            required: false,
+            data: Some(data),
+            provenance: Some(provenance),
+            interpolation: None,
+            section: None,
+        }
+    }
+
+    /// Report which kind of source supplied the value at a dotted `path`.
     ///
-    /// Use this method when you need to override automatic format detection
-    /// or when working with files that don't have standard extensions.
+    /// For a config produced by [`ConfigBuilder::build_config`] this is the
+    /// source that last wrote the path while merging, so a value two sources
+    /// agree on is attributed to the one that won. An object path names the
+    /// last source that wrote into it, and a path only a transformation added
+    /// has no source. For a config loaded from a file, every existing path
+    /// reports [`Source::ConfigFile`]. Returns `None` if the path does not
+    /// exist.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `path` - Path to the configuration file
-    /// * `format` - The format to use for parsing
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Environment, Source};
     ///
-    /// # Examples
+    /// let config = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080, "host": "localhost" }))?
+    ///     .with_env_custom(Environment::new().override_with("PORT", "9000"))
+    ///     .build_config()?;
     ///
-    /// ```rust,no_run
-    /// use gonfig::{Config, ConfigFormat};
+    /// assert_eq!(config.source_of("port"), Some(Source::Environment));
+    /// assert_eq!(config.source_of("host"), Some(Source::Default));
+    /// assert_eq!(config.source_of("missing"), None);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
     ///
-    /// // Force JSON parsing for a file without extension
-    /// let config = Config::with_format("config", ConfigFormat::Json)?;
+    /// [`ConfigBuilder::build_config`]: crate::ConfigBuilder::build_config
+    pub fn source_of(&self, path: &str) -> Option<Source> {
+        match &self.provenance {
+            Some(provenance) => provenance.get(path).copied(),
+            None => self.has_value(path).then_some(Source::ConfigFile),
+        }
+    }
+
+    /// Deserialize the whole configuration into a typed struct.
+    ///
+    /// Bridges dynamic and typed usage: inspect a [`Config`] from
+    /// [`ConfigBuilder::build_config`](crate::ConfigBuilder::build_config), then
+    /// turn it into `T` without building again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::ConfigBuilder;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Server {
+    ///     port: u16,
+    /// }
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "port": 8080 }))?
+    ///     .build_config()?;
+    /// let server: Server = config.try_deserialize()?;
+    /// assert_eq!(server.port, 8080);
     /// # Ok::<(), gonfig::Error>(())
     /// ```
-    pub fn with_format(path: impl AsRef<Path>, format: ConfigFormat) -> Result<Self> {
-        let mut config = Self {
-            path: path.as_ref().to_path_buf(),
-            format,
-            required: true,
-            data: None,
-        };
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Deserialize`] naming the failing path if the
+    /// configuration does not match `T`.
+    pub fn try_deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        deserialize_merged(self.collect()?, PhantomData)
+    }
 
-        config.load()?;
-        Ok(config)
+    /// Deserialize the value at a dotted `path` into a typed struct.
+    ///
+    /// Like [`try_deserialize`](Config::try_deserialize) for one part of the
+    /// configuration, e.g. a `database` section owned by a single component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if nothing exists at `path`, and
+    /// [`Error::Deserialize`] if the value there does not match `T`.
+    pub fn try_deserialize_path<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let value = self
+            .get_value(path)
+            .ok_or_else(|| Error::Config(format!("No configuration value at `{path}`")))?;
+        deserialize_at(value, PhantomData, path)
     }
 
     fn load(&mut self) -> Result<()> {
+        // A skipped file is neither read nor parsed, so it can be broken
+        if is_skipped_file(&self.path) {
+            tracing::info!(
+                "Skipping {} listed in {}",
+                self.path.display(),
+                SKIP_FILES_VAR
+            );
+            self.data = None;
+            return Ok(());
+        }
+
         match fs::read_to_string(&self.path) {
             Ok(content) => {
-                self.data = Some(self.format.parse(&content)?);
+                let content = match &self.interpolation {
+                    Some(interpolation) => interpolation
+                        .apply(&content)
+                        .map_err(|e| e.with_context(self.path.display()))?,
+                    None => content,
+                };
+                let data = self.format.parse(&content)?;
+                self.data = Some(self.extract_section(data)?);
                 Ok(())
             }
             Err(e) => {
                 if self.required {
-                    Err(Error::Io(e))
+                    Err(Error::io_at(&self.path, e))
                 } else {
                     self.data = Some(Value::Object(serde_json::Map::new()));
                     Ok(())
@@ -280,6 +647,64 @@ impl Config {
         }
     }
 
+    /// Use only one section of the file as the configuration root.
+    ///
+    /// `section` names an INI `[section]` or TOML table, or any object in other
+    /// formats, and may be a dotted path such as `services.billing`. Other
+    /// sections are ignored, which keeps shared files from leaking unrelated
+    /// settings into the configuration. The section is extracted again on
+    /// every [`reload`](Config::reload).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use gonfig::{Config, ConfigFormat};
+    ///
+    /// let billing = Config::with_format("services.ini", ConfigFormat::Ini)?
+    ///     .with_section("billing")?;
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if a required file has no such section or the
+    /// path names a plain value. A missing optional file stays empty.
+    pub fn with_section(mut self, section: impl Into<String>) -> Result<Self> {
+        self.section = Some(section.into());
+        if let Some(data) = self.data.take() {
+            self.data = Some(self.extract_section(data)?);
+        }
+        Ok(self)
+    }
+
+    /// Narrow freshly parsed file content to the configured section.
+    fn extract_section(&self, data: Value) -> Result<Value> {
+        let Some(section) = &self.section else {
+            return Ok(data);
+        };
+
+        let found = section
+            .split('.')
+            .try_fold(&data, |current, part| current.get(part));
+        match found {
+            Some(value @ Value::Object(_)) => Ok(value.clone()),
+            Some(_) => Err(Error::Config(format!(
+                "`{section}` in {} is a value, not a section",
+                self.path.display()
+            ))),
+            None if self.required => Err(Error::Config(format!(
+                "Section `{section}` not found in {}",
+                self.path.display()
+            ))),
+            None => Ok(Value::Object(serde_json::Map::new())),
+        }
+    }
+
+    /// Path of the backing file, `None` for merged configurations.
+    pub(crate) fn file_path(&self) -> Option<&Path> {
+        self.provenance.is_none().then_some(self.path.as_path())
+    }
+
     /// Reload the configuration from disk.
     ///
     /// This method re-reads the configuration file and parses it again.
@@ -302,6 +727,10 @@ impl Config {
     /// Returns the same errors as the original loading method if the file
     /// cannot be read or parsed.
     pub fn reload(&mut self) -> Result<()> {
+        // Merged configs have no backing file to re-read
+        if self.provenance.is_some() {
+            return Ok(());
+        }
         self.load()
     }
 }
@@ -352,6 +781,20 @@ impl ConfigSource for Config {
         }
     }
 
+    fn describe(&self) -> String {
+        if self.provenance.is_some() {
+            return "merged configuration".to_string();
+        }
+        let status = if self.path.exists() {
+            "exists"
+        } else if self.required {
+            "missing"
+        } else {
+            "missing, optional"
+        };
+        format!("file {} ({status})", self.path.display())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -0,0 +1,268 @@
+//! `.env` file configuration source.
+
+use crate::{
+    environment::Environment,
+    error::{Error, Result},
+    source::{ConfigSource, Source},
+    transform::ValueParser,
+};
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Configuration source reading `KEY=VALUE` pairs from a `.env` file.
+///
+/// Lines may start with `export `, values may be single- or double-quoted, and
+/// `#` starts a comment on its own line or after an unquoted value. Double
+/// quoted values understand `\n`, `\t`, `\"` and `\\` escapes; single quoted
+/// values are taken literally.
+///
+/// Keys are lowercased like flat [`Environment`] keys, after stripping the
+/// prefix set with [`with_prefix`](DotEnv::with_prefix), and values are
+/// type-coerced the same way as environment variables. The source reports
+/// [`Source::DotEnv`], so it sits above defaults and below config files and
+/// the real environment, unless [`at_env_priority`](DotEnv::at_env_priority) moves it
+/// up to the environment's level.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::dotenv::DotEnv;
+/// use gonfig::ConfigSource;
+///
+/// let source = DotEnv::parse("export APP_PORT=8080\nAPP_NAME=\"my app\" # quoted\n")?
+///     .with_prefix("APP");
+/// let value = source.collect()?;
+/// assert_eq!(value["port"], 8080);
+/// assert_eq!(value["name"], "my app");
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DotEnv {
+    path: Option<PathBuf>,
+    vars: Vec<(String, String)>,
+    prefix: Option<String>,
+    env_priority: bool,
+}
+
+impl DotEnv {
+    /// Load a `.env` file, failing if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read and [`Error::Config`]
+    /// for a line that is not a `KEY=VALUE` pair.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| Error::io_at(path, e))?;
+        let mut dotenv = Self::parse(&content).map_err(|e| e.with_context(path.display()))?;
+        dotenv.path = Some(path.to_path_buf());
+        Ok(dotenv)
+    }
+
+    /// Load `.env` from the current directory, or nothing if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn auto() -> Result<Self> {
+        let path = Path::new(".env");
+        if !path.exists() {
+            return Ok(Self {
+                path: Some(path.to_path_buf()),
+                vars: Vec::new(),
+                prefix: None,
+                env_priority: false,
+            });
+        }
+        Self::from_path(path)
+    }
+
+    /// Parse `.env` file content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for a line that is not a `KEY=VALUE` pair or
+    /// has an unterminated quoted value.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut vars = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line
+                .split_once('=')
+                .filter(|(key, _)| !key.trim().is_empty())
+                .ok_or_else(|| {
+                    Error::Config(format!("Expected KEY=VALUE on line {}", index + 1))
+                })?;
+            let value = Self::parse_value(value.trim())
+                .map_err(|msg| Error::Config(format!("{msg} on line {}", index + 1)))?;
+            vars.push((key.trim().to_string(), value));
+        }
+
+        Ok(Self {
+            path: None,
+            vars,
+            prefix: None,
+            env_priority: false,
+        })
+    }
+
+    /// Only read keys starting with `prefix` (and `_`), with the prefix removed.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Merge the file at the environment's priority instead of the config
+    /// file's, as if its variables were exported.
+    ///
+    /// The file then beats configuration files, while a variable that is
+    /// really exported still wins over the file's value for the same name:
+    /// such variables are skipped, and a warning naming them is logged. See
+    /// [`ConfigBuilder::with_env_file_override`](crate::ConfigBuilder::with_env_file_override).
+    pub fn at_env_priority(mut self) -> Self {
+        self.env_priority = true;
+        self
+    }
+
+    /// Names in the file that are also exported in the process environment.
+    ///
+    /// With [`at_env_priority`](DotEnv::at_env_priority) the exported values
+    /// win for these names; otherwise the environment source overrides them
+    /// anyway.
+    pub fn shadowed_vars(&self) -> Vec<&str> {
+        self.vars
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| std::env::var_os(name).is_some())
+            .collect()
+    }
+
+    /// Variables in file order, as written.
+    pub fn vars(&self) -> &[(String, String)] {
+        &self.vars
+    }
+
+    fn parse_value(raw: &str) -> std::result::Result<String, String> {
+        if let Some(rest) = raw.strip_prefix('\'') {
+            let end = rest
+                .find('\'')
+                .ok_or_else(|| "Unterminated single-quoted value".to_string())?;
+            return Ok(rest[..end].to_string());
+        }
+
+        if let Some(rest) = raw.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => return Ok(value),
+                    '\\' => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(other) => value.push(other),
+                        None => break,
+                    },
+                    other => value.push(other),
+                }
+            }
+            return Err("Unterminated double-quoted value".to_string());
+        }
+
+        // An unquoted value ends at a comment introduced by whitespace
+        let value = match raw.find(" #").or_else(|| raw.find("\t#")) {
+            Some(end) => &raw[..end],
+            None => raw,
+        };
+        Ok(value.trim().to_string())
+    }
+
+    /// Config key for a variable, or `None` when it lacks the prefix.
+    fn key_for(&self, name: &str) -> Option<String> {
+        let key = match &self.prefix {
+            Some(prefix) => {
+                let upper = name.to_uppercase();
+                let rest = upper.strip_prefix(&prefix.to_uppercase())?;
+                rest.strip_prefix('_')?.to_string()
+            }
+            None => name.to_string(),
+        };
+        Some(key.to_lowercase())
+    }
+
+    /// Collect the variables, reading a key that has one of `parsers` from the
+    /// variable's text instead of coercing it.
+    pub(crate) fn collect_parsed(&self, parsers: &[(String, ValueParser)]) -> Result<Value> {
+        let shadowed = if self.env_priority {
+            self.shadowed_vars()
+        } else {
+            Vec::new()
+        };
+        if !shadowed.is_empty() {
+            tracing::warn!(
+                "{} also exported in the environment; the exported values win over {}",
+                shadowed.join(", "),
+                self.describe()
+            );
+        }
+
+        let mut result = Map::new();
+        for (name, value) in &self.vars {
+            if shadowed.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(key) = self.key_for(name) {
+                let value = match parsers.iter().find(|(path, _)| *path == key) {
+                    Some((path, parser)) => parser.parse(path, value)?,
+                    None => Environment::parse_env_value(value),
+                };
+                result.insert(key, value);
+            }
+        }
+        Ok(Value::Object(result))
+    }
+}
+
+impl ConfigSource for DotEnv {
+    fn source_type(&self) -> Source {
+        if self.env_priority {
+            Source::Environment
+        } else {
+            Source::DotEnv
+        }
+    }
+
+    fn collect(&self) -> Result<Value> {
+        self.collect_parsed(&[])
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        self.collect().ok()?.get(key).cloned()
+    }
+
+    fn describe(&self) -> String {
+        let kind = if self.env_priority {
+            "env override file"
+        } else {
+            "dotenv"
+        };
+        match &self.path {
+            Some(path) => format!("{kind} {}", path.display()),
+            None => kind.to_string(),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
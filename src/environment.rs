@@ -1,12 +1,14 @@
 use crate::{
-    error::Result,
+    error::{Error, Result},
     source::{ConfigSource, Source},
+    transform::ValueParser,
     Prefix,
 };
 use serde_json::{json, Map, Value};
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::env;
+use std::sync::{Arc, Mutex};
 
 /// Environment variable configuration source.
 ///
@@ -46,28 +48,146 @@ use std::env;
 ///     .with_prefix("MYAPP")
 ///     .separator("__")  // Use double underscore
 ///     .case_sensitive(true)
-///     .override_with("database_url", "postgres://override/db")
+///     .override_with("MYAPP__DATABASE_URL", "postgres://override/db")
 ///     .with_field_mapping("db_url", "CUSTOM_DB_CONNECTION");
 /// ```
 #[derive(Debug, Clone)]
 pub struct Environment {
     prefix: Option<Prefix>,
+    fallback_prefixes: Vec<Prefix>,
+    struct_infix: Option<String>,
     separator: String,
+    prefix_separator: Option<String>,
     case_sensitive: bool,
+    prefix_case: Option<Case>,
     overrides: HashMap<String, String>,
+    typed_overrides: HashMap<String, Value>,
     field_mappings: HashMap<String, String>,
     nested: bool,
+    trim_values: bool,
+    numeric_formats: bool,
+    known_paths: Option<Vec<String>>,
+    strict_types: bool,
+    list_syntax: Option<ListSyntax>,
+    value_parsers: Vec<(String, ValueParser)>,
+    presence_flags: Vec<String>,
+    nesting_chars: Vec<char>,
+    field_aliases: Vec<(String, String)>,
+    strict_aliases: bool,
+    collected_keys: Arc<Mutex<BTreeSet<String>>>,
+}
+
+/// A field whose variable and aliases are set to different values.
+///
+/// Returned by [`Environment::alias_conflicts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasConflict {
+    /// Field path the variables map to.
+    pub field: String,
+    /// Every candidate variable that is set, in precedence order.
+    pub names: Vec<String>,
+}
+
+/// How the prefix of an [`Environment`] is cased before matching variables.
+///
+/// Set with [`Environment::prefix_case`] or
+/// [`ConfigBuilder::with_env_prefix_case`](crate::ConfigBuilder::with_env_prefix_case).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// Uppercase the prefix, e.g. `app` matches `APP_...` variables.
+    Upper,
+    /// Lowercase the prefix.
+    Lower,
+    /// Use the prefix exactly as configured.
+    Preserve,
+}
+
+/// How an [`Environment`] recognizes list values.
+///
+/// Set with [`Environment::with_list_syntax`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListSyntax {
+    separator: char,
+    brackets: Option<(char, char)>,
+}
+
+impl ListSyntax {
+    /// Split every value containing `separator`, e.g. `a,b,c`.
+    ///
+    /// The source does not know the type of the field a variable ends up in,
+    /// so a value meant for a string field is split too, and an empty value
+    /// becomes an empty list. A single element without a separator stays a
+    /// plain value; pair this with the derive's `wrap_scalars` option so it
+    /// still fills a `Vec` field. Values that look like JSON arrays or objects
+    /// are left to the usual parsing.
+    pub fn separated(separator: char) -> Self {
+        Self {
+            separator,
+            brackets: None,
+        }
+    }
+
+    /// Split values wrapped in `open` and `close` on `separator`, e.g.
+    /// `(a|b|c)`, for platforms where brackets cannot be used in environment
+    /// values. Other values, including JSON arrays, are parsed as usual.
+    pub fn bracketed(open: char, close: char, separator: char) -> Self {
+        Self {
+            separator,
+            brackets: Some((open, close)),
+        }
+    }
+
+    /// Trimmed items of `value`, or `None` when it is not a list.
+    fn split(self, value: &str) -> Option<Vec<&str>> {
+        let value = value.trim();
+        let items = match self.brackets {
+            Some((open, close)) => value.strip_prefix(open)?.strip_suffix(close)?.trim(),
+            None if value.is_empty() => value,
+            None if value.contains(self.separator) && !value.starts_with(['[', '{']) => value,
+            None => return None,
+        };
+        if items.is_empty() {
+            return Some(Vec::new());
+        }
+        Some(items.split(self.separator).map(str::trim).collect())
+    }
+}
+
+impl Case {
+    fn apply(self, text: &str) -> String {
+        match self {
+            Case::Upper => text.to_uppercase(),
+            Case::Lower => text.to_lowercase(),
+            Case::Preserve => text.to_string(),
+        }
+    }
 }
 
 impl Default for Environment {
     fn default() -> Self {
         Self {
             prefix: None,
+            fallback_prefixes: Vec::new(),
+            struct_infix: None,
             separator: "_".to_string(),
+            prefix_separator: None,
             case_sensitive: false,
+            prefix_case: None,
             overrides: HashMap::new(),
+            typed_overrides: HashMap::new(),
             field_mappings: HashMap::new(),
             nested: false,
+            trim_values: false,
+            numeric_formats: false,
+            known_paths: None,
+            strict_types: false,
+            list_syntax: None,
+            value_parsers: Vec::new(),
+            presence_flags: Vec::new(),
+            nesting_chars: Vec::new(),
+            field_aliases: Vec::new(),
+            strict_aliases: false,
+            collected_keys: Arc::new(Mutex::new(BTreeSet::new())),
         }
     }
 }
@@ -108,9 +228,111 @@ impl Environment {
     /// ```
     pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
         self.prefix = Some(Prefix::new(prefix));
+        self.fallback_prefixes.clear();
         self
     }
 
+    /// Accept variables under any of several prefixes, highest priority first.
+    ///
+    /// Useful while renaming a prefix: with `vec!["APP", "OLDAPP"]` both
+    /// `APP_PORT` and `OLDAPP_PORT` configure `port`. When the same key is set
+    /// under two prefixes, the value under the earlier prefix wins regardless of
+    /// the order variables are read in. The first prefix is the one used to
+    /// build variable names, e.g. for [`has_value`](ConfigSource::has_value)
+    /// and [`describe`](ConfigSource::describe); the others are only consulted
+    /// when collecting. An empty list clears the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefixes(vec!["APP", "OLDAPP"])
+    ///     .override_with("OLDAPP_PORT", "8080")
+    ///     .override_with("OLDAPP_HOST", "old.example.com")
+    ///     .override_with("APP_HOST", "new.example.com");
+    /// let value = env.collect()?;
+    /// assert_eq!(value["port"], 8080);
+    /// assert_eq!(value["host"], "new.example.com");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut prefixes = prefixes.into_iter().map(Prefix::new);
+        self.prefix = prefixes.next();
+        self.fallback_prefixes = prefixes.collect();
+        self
+    }
+
+    /// Expect a struct name between the prefix and the field names.
+    ///
+    /// For conventions where variables include the struct they configure,
+    /// e.g. `APP_SERVERCONFIG_PORT` for the `port` field of `ServerConfig`.
+    /// The infix is joined to the prefix with the
+    /// [prefix separator](Environment::prefix_separator) and behaves as part
+    /// of the prefix from then on: it is uppercased unless the source is
+    /// [case sensitive](Environment::case_sensitive), and fields follow it
+    /// with the same separator. Without a prefix the infix is the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .with_struct_infix("ServerConfig")
+    ///     .override_with("APP_SERVERCONFIG_MAX_POOL_SIZE", "16");
+    /// assert_eq!(env.collect()?["max_pool_size"], 16);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_struct_infix(mut self, name: impl Into<String>) -> Self {
+        self.struct_infix = Some(name.into());
+        self
+    }
+
+    /// Prefix followed by the struct infix, when either is set.
+    fn full_prefix(&self) -> Option<String> {
+        let prefix = match (&self.prefix, &self.struct_infix) {
+            (Some(prefix), Some(infix)) => {
+                Some(format!("{}{}{}", prefix.as_str(), self.prefix_sep(), infix))
+            }
+            (Some(prefix), None) => Some(prefix.as_str().to_string()),
+            (None, infix) => infix.clone(),
+        };
+        prefix.map(|prefix| self.cased_prefix(prefix))
+    }
+
+    /// `prefix` with the configured [`Case`] applied.
+    fn cased_prefix(&self, prefix: String) -> String {
+        match self.prefix_case {
+            Some(case) => case.apply(&prefix),
+            None => prefix,
+        }
+    }
+
+    /// Full prefixes to match while collecting, highest priority first.
+    fn full_prefixes(&self) -> Vec<String> {
+        let Some(primary) = self.full_prefix() else {
+            return Vec::new();
+        };
+        let mut prefixes = vec![primary];
+        prefixes.extend(
+            self.fallback_prefixes
+                .iter()
+                .map(|prefix| match &self.struct_infix {
+                    Some(infix) => format!("{}{}{}", prefix.as_str(), self.prefix_sep(), infix),
+                    None => prefix.as_str().to_string(),
+                })
+                .map(|prefix| self.cased_prefix(prefix)),
+        );
+        prefixes
+    }
+
     /// Set the separator used between prefix and field names.
     ///
     /// The default separator is `"_"`. This affects how environment variable
@@ -130,6 +352,34 @@ impl Environment {
         self
     }
 
+    /// Set the separator used only between the prefix and the field path.
+    ///
+    /// Defaults to [`separator`](Environment::separator). Use this for conventions
+    /// such as `APP__DB_HOST`, where a double underscore follows the prefix and a
+    /// single underscore separates nested segments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .prefix_separator("__")
+    ///     .nested(true)
+    ///     .override_with("APP__DB_HOST", "localhost");
+    /// assert_eq!(env.collect().unwrap()["db"]["host"], "localhost");
+    /// ```
+    pub fn prefix_separator(mut self, sep: impl Into<String>) -> Self {
+        self.prefix_separator = Some(sep.into());
+        self
+    }
+
+    /// Separator placed between the prefix and the first field segment.
+    fn prefix_sep(&self) -> &str {
+        self.prefix_separator.as_deref().unwrap_or(&self.separator)
+    }
+
     /// Control case sensitivity for environment variable names.
     ///
     /// When `false` (default), all environment variable names are converted
@@ -150,23 +400,94 @@ impl Environment {
         self
     }
 
-    /// Override a specific field with a hardcoded value.
+    /// Case the prefix independently of the rest of the variable name.
+    ///
+    /// Only matters for a [case sensitive](Environment::case_sensitive) source,
+    /// which otherwise uses the prefix exactly as configured: with
+    /// [`Case::Upper`], a prefix configured as `app` matches `APP_logLevel`
+    /// while the `logLevel` part is still matched case-sensitively. The struct
+    /// [infix](Environment::with_struct_infix) counts as part of the prefix.
+    /// Case-insensitive sources uppercase the whole name anyway.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::environment::Case;
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("app")
+    ///     .case_sensitive(true)
+    ///     .prefix_case(Case::Upper)
+    ///     .override_with("APP_port", "8080");
+    /// assert_eq!(env.collect()?["port"], 8080);
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn prefix_case(mut self, case: Case) -> Self {
+        self.prefix_case = Some(case);
+        self
+    }
+
+    /// Override a specific environment variable with a hardcoded value.
     ///
     /// This is useful for providing default values or overriding environment
     /// variables programmatically. Overrides take precedence over actual
     /// environment variables.
     ///
+    /// The key is the full variable name, including the prefix and separator
+    /// (e.g. `APP_DEBUG`), exactly as it would be exported in the shell. An
+    /// override behaves identically to setting that variable, whether the source
+    /// collects through field mappings or prefix matching. Unless
+    /// [`case_sensitive`](Environment::case_sensitive) is enabled, the key is
+    /// matched case-insensitively.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use gonfig::Environment;
     ///
     /// let env = Environment::new()
-    ///     .override_with("debug", "true")
-    ///     .override_with("timeout", "30");
+    ///     .with_prefix("APP")
+    ///     .override_with("APP_DEBUG", "true")
+    ///     .override_with("APP_TIMEOUT", "30");
     /// ```
     pub fn override_with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.overrides.insert(key.into(), value.into());
+        let key = key.into();
+        self.typed_overrides.remove(&key);
+        self.overrides.insert(key, value.into());
+        self
+    }
+
+    /// Override a variable with an already typed value.
+    ///
+    /// Like [`override_with`](Environment::override_with), but the value is
+    /// used as given instead of being parsed from a string, so objects, arrays
+    /// and strings that look like numbers arrive unchanged, even with
+    /// [`strict_types`](Environment::strict_types) enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    /// use serde_json::json;
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .override_with_value("APP_DATABASE", json!({ "host": "db", "port": 5432 }))
+    ///     .override_with_value("APP_VERSION", json!("1.10"));
+    /// let value = env.collect()?;
+    /// assert_eq!(value["database"]["port"], 5432);
+    /// assert_eq!(value["version"], "1.10");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn override_with_value(mut self, key: impl Into<String>, value: Value) -> Self {
+        let key = key.into();
+        let text = match &value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        self.overrides.insert(key.clone(), text);
+        self.typed_overrides.insert(key, value);
         self
     }
 
@@ -176,6 +497,9 @@ impl Environment {
     /// for specific fields. The mapping takes precedence over the standard
     /// prefix and separator rules.
     ///
+    /// The field name may be a dotted path such as `database.url`, in which case
+    /// the value is inserted into the corresponding nested object.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -185,6 +509,9 @@ impl Environment {
     ///     .with_prefix("APP")
     ///     .with_field_mapping("database_url", "DATABASE_CONNECTION_STRING");
     /// // database_url will read from DATABASE_CONNECTION_STRING instead of APP_DATABASE_URL
+    ///
+    /// let env = Environment::new().with_field_mapping("database.url", "DATABASE_URL");
+    /// // DATABASE_URL populates {"database": {"url": ...}}
     /// ```
     pub fn with_field_mapping(
         mut self,
@@ -196,6 +523,259 @@ impl Environment {
         self
     }
 
+    /// Also read a mapped field or [known path](Environment::with_known_paths)
+    /// from `alias`, e.g. a deprecated variable name.
+    ///
+    /// The variable from [`with_field_mapping`](Environment::with_field_mapping),
+    /// or the one derived from the known path, wins, then aliases in the order
+    /// they were added. When more than one of
+    /// them is set with different values a warning is logged, or collection
+    /// fails under [`strict_aliases`](Environment::strict_aliases); see
+    /// [`alias_conflicts`](Environment::alias_conflicts).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_field_mapping("database_url", "APP_DATABASE_URL")
+    ///     .with_field_alias("database_url", "DB_URL")
+    ///     .override_with("DB_URL", "postgres://legacy");
+    /// assert_eq!(env.collect()?["database_url"], "postgres://legacy");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_field_alias(
+        mut self,
+        field_name: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> Self {
+        self.field_aliases.push((field_name.into(), alias.into()));
+        self
+    }
+
+    /// Fail collection when a field's variable and an alias disagree.
+    ///
+    /// Off by default, in which case the conflict is only logged as a warning.
+    pub fn strict_aliases(mut self, strict: bool) -> Self {
+        self.strict_aliases = strict;
+        self
+    }
+
+    /// Fields whose variable and aliases are set to different values.
+    ///
+    /// Each conflict lists the field and every candidate name that is set, in
+    /// precedence order, so migrations can be checked before they bite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::Environment;
+    ///
+    /// let env = Environment::new()
+    ///     .with_field_mapping("port", "APP_PORT")
+    ///     .with_field_alias("port", "PORT")
+    ///     .override_with("APP_PORT", "8080")
+    ///     .override_with("PORT", "9090");
+    /// let conflicts = env.alias_conflicts();
+    /// assert_eq!(conflicts[0].field, "port");
+    /// assert_eq!(conflicts[0].names, vec!["APP_PORT", "PORT"]);
+    /// ```
+    pub fn alias_conflicts(&self) -> Vec<AliasConflict> {
+        let mut fields: Vec<&String> = self
+            .field_mappings
+            .keys()
+            .chain(self.known_paths.iter().flatten())
+            .collect();
+        fields.sort();
+        fields.dedup();
+        fields
+            .into_iter()
+            .filter_map(|field| {
+                let set = self.set_candidates(field);
+                let conflicting = set.iter().any(|(_, value)| value != &set[0].1);
+                conflicting.then(|| AliasConflict {
+                    field: field.clone(),
+                    names: set.into_iter().map(|(name, _)| name).collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Candidate variables for a field that are set, in precedence order.
+    ///
+    /// The field's own variable is its mapping or, for a
+    /// [known path](Environment::with_known_paths), the name derived from the
+    /// path. It is read under the first [prefix](Environment::with_prefixes)
+    /// it is set with; only that one is a candidate, so a fallback prefix
+    /// never conflicts with the primary one.
+    fn set_candidates(&self, field: &str) -> Vec<(String, String)> {
+        let env_key = match self.field_mappings.get(field) {
+            Some(env_key) => Some(env_key.clone()),
+            None => self
+                .known_paths
+                .iter()
+                .flatten()
+                .any(|path| path == field)
+                .then(|| self.build_env_key(&field.split('.').collect::<Vec<_>>())),
+        };
+        let own = env_key.and_then(|env_key| {
+            self.prefixed_keys(&env_key)
+                .into_iter()
+                .find_map(|name| self.var(&name).map(|value| (name, value)))
+        });
+        own.into_iter()
+            .chain(
+                self.field_aliases
+                    .iter()
+                    .filter(|(aliased, _)| aliased == field)
+                    .filter_map(|(_, alias)| self.var(alias).map(|value| (alias.clone(), value))),
+            )
+            .collect()
+    }
+
+    /// The variable `field` is read from among its [candidates](Self::set_candidates),
+    /// with its value.
+    ///
+    /// When candidates disagree a warning is logged, or an error returned under
+    /// [`strict_aliases`](Environment::strict_aliases).
+    fn resolve_field(&self, field: &str) -> Result<Option<(String, String)>> {
+        let mut set = self.set_candidates(field);
+        if set.is_empty() {
+            return Ok(None);
+        }
+        let (env_key, value) = set.remove(0);
+        if set.iter().any(|(_, other)| *other != value) {
+            let names: Vec<&str> = std::iter::once(env_key.as_str())
+                .chain(set.iter().map(|(name, _)| name.as_str()))
+                .collect();
+            let message = format!(
+                "Conflicting values for `{field}`: {} are all set; using {env_key}",
+                names.join(", ")
+            );
+            if self.strict_aliases {
+                return Err(Error::Environment(message));
+            }
+            tracing::warn!("{}", message);
+        }
+        Ok(Some((env_key, value)))
+    }
+
+    /// `env_key` followed by the same variable under each fallback prefix, when
+    /// it starts with the primary prefix.
+    ///
+    /// A mapping that names its variable verbatim, e.g. `DATABASE_URL`, has no
+    /// fallbacks.
+    fn prefixed_keys(&self, env_key: &str) -> Vec<String> {
+        let mut keys = vec![env_key.to_string()];
+        let prefixes = self.full_prefixes();
+        let Some((primary, fallbacks)) = prefixes.split_first() else {
+            return keys;
+        };
+        let head = format!("{primary}{}", self.prefix_sep());
+        let matches = match env_key.get(..head.len()) {
+            Some(start) if self.case_sensitive => start == head,
+            Some(start) => start.eq_ignore_ascii_case(&head),
+            None => false,
+        };
+        if matches {
+            let rest = &env_key[head.len()..];
+            keys.extend(fallbacks.iter().map(|fallback| {
+                let key = format!("{fallback}{}{rest}", self.prefix_sep());
+                if self.case_sensitive {
+                    key
+                } else {
+                    key.to_uppercase()
+                }
+            }));
+        }
+        keys
+    }
+
+    /// Combine another source's field mappings, overrides and flags into this one.
+    ///
+    /// Lets a library ship a base environment source that applications extend.
+    /// When both sources map the same field or override the same variable,
+    /// `other` wins if `prefer_other` is `true` and `self` wins otherwise.
+    /// Presence flags and known paths are combined. Settings such as the
+    /// prefix, separator and parsing options are taken from `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let base = Environment::new()
+    ///     .with_field_mapping("database_url", "DATABASE_URL")
+    ///     .override_with("DATABASE_URL", "postgres://base");
+    /// let app = Environment::new()
+    ///     .with_field_mapping("log_level", "RUST_LOG")
+    ///     .override_with("RUST_LOG", "debug");
+    ///
+    /// let value = base.merge(app, true).collect()?;
+    /// assert_eq!(value["database_url"], "postgres://base");
+    /// assert_eq!(value["log_level"], "debug");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn merge(mut self, other: Environment, prefer_other: bool) -> Self {
+        fn combine(
+            target: &mut HashMap<String, String>,
+            source: HashMap<String, String>,
+            replace: bool,
+        ) {
+            for (key, value) in source {
+                if replace {
+                    target.insert(key, value);
+                } else {
+                    target.entry(key).or_insert(value);
+                }
+            }
+        }
+
+        // Typed overrides follow whichever override wins for their variable
+        for key in other.overrides.keys() {
+            if prefer_other {
+                self.typed_overrides.remove(key);
+            }
+        }
+        for (key, value) in other.typed_overrides {
+            if prefer_other || !self.overrides.contains_key(&key) {
+                self.typed_overrides.insert(key, value);
+            }
+        }
+
+        combine(&mut self.field_mappings, other.field_mappings, prefer_other);
+        combine(&mut self.overrides, other.overrides, prefer_other);
+
+        for alias in other.field_aliases {
+            if !self.field_aliases.contains(&alias) {
+                self.field_aliases.push(alias);
+            }
+        }
+
+        for path in other.presence_flags {
+            if !self.presence_flags.contains(&path) {
+                self.presence_flags.push(path);
+            }
+        }
+        for (path, parser) in other.value_parsers {
+            match self.value_parsers.iter_mut().find(|(own, _)| *own == path) {
+                Some(own) if prefer_other => own.1 = parser,
+                Some(_) => {}
+                None => self.value_parsers.push((path, parser)),
+            }
+        }
+        if let Some(other_paths) = other.known_paths {
+            let paths = self.known_paths.get_or_insert_with(Vec::new);
+            for path in other_paths {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        self
+    }
+
     /// Enable nested mode to convert flat environment variable keys into nested structures.
     ///
     /// When enabled, environment variables with the configured separator (default: `_`) will be split
@@ -204,6 +784,12 @@ impl Environment {
     /// This is essential for properly overriding nested configuration file values with
     /// environment variables when using the Deep merge strategy.
     ///
+    /// Internally tagged enums (`#[serde(tag = "type")]`) load the same way: the
+    /// discriminator is just another key, so `APP_STORAGE_TYPE=s3` next to
+    /// `APP_STORAGE_BUCKET=assets` yields `{"storage": {"type": "s3", "bucket": "assets"}}`.
+    /// When variant fields contain the separator, use a different separator such as
+    /// `__` or [`with_known_paths`](Environment::with_known_paths).
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -219,18 +805,316 @@ impl Environment {
         self
     }
 
-    fn build_env_key(&self, path: &[&str]) -> String {
-        let mut parts = Vec::new();
+    /// Also split variable names on `delimiter` when nesting.
+    ///
+    /// Some platforms pass literal names such as `app.http.port` or
+    /// `app-http-port`. With `nest_on('.')` such a name nests exactly like
+    /// `APP_HTTP_PORT`. Can be called repeatedly to add several delimiters, and
+    /// enables [`nested`](Environment::nested) mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .override_with("app.http.port", "9000")
+    ///     .nest_on('.');
+    /// assert_eq!(env.collect().unwrap()["http"]["port"], 9000);
+    /// ```
+    pub fn nest_on(mut self, delimiter: char) -> Self {
+        if !self.nesting_chars.contains(&delimiter) {
+            self.nesting_chars.push(delimiter);
+        }
+        self.nested = true;
+        self
+    }
+
+    /// Ignore surrounding whitespace when coercing values to typed JSON.
+    ///
+    /// Shells and `.env` files often leave stray whitespace around values, so
+    /// `PORT=" 8080 "` would otherwise stay a string and fail to deserialize into
+    /// a number. When enabled, values are trimmed before type coercion. Values
+    /// that remain plain strings after trimming keep their original whitespace,
+    /// since it may be meaningful. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("PORT", " 8080 ")
+    ///     .trim_values(true);
+    /// assert_eq!(env.collect().unwrap()["port"], 8080);
+    /// ```
+    pub fn trim_values(mut self, trim: bool) -> Self {
+        self.trim_values = trim;
+        self
+    }
+
+    /// Recognize extended integer literals when coercing values.
+    ///
+    /// When enabled, underscore digit separators (`1_000_000`) and the `0x`, `0o`
+    /// and `0b` radix prefixes (`0xFF`, `0o755`, `0b1010`) are parsed as integers,
+    /// optionally preceded by `-`. An `_` must sit between two digits, so
+    /// `1__000`, `_1` and `+1_000` stay strings. This is opt-in so that ordinary strings such
+    /// as version tags or hashes are not reinterpreted as numbers. Disabled by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("MAX", "1_000_000")
+    ///     .override_with("MASK", "0xFF")
+    ///     .numeric_formats(true);
+    /// let value = env.collect().unwrap();
+    /// assert_eq!(value["max"], 1_000_000);
+    /// assert_eq!(value["mask"], 255);
+    /// ```
+    pub fn numeric_formats(mut self, enabled: bool) -> Self {
+        self.numeric_formats = enabled;
+        self
+    }
+
+    /// Names of the variables found and used by the last collection.
+    ///
+    /// Useful for auditing which variables are set in an environment or for
+    /// generating an allowlist / `.env.example`. The set is replaced on every
+    /// [`collect`](ConfigSource::collect) and is shared between clones of this
+    /// source. Overrides registered with
+    /// [`override_with`](Environment::override_with) are included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("LISTED")
+    ///     .override_with("LISTED_PORT", "8080");
+    /// env.collect().unwrap();
+    /// assert!(env.list_collected_keys().contains("LISTED_PORT"));
+    /// ```
+    pub fn list_collected_keys(&self) -> BTreeSet<String> {
+        self.collected_keys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn record_key(&self, key: &str) {
+        self.collected_keys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string());
+    }
+
+    fn clear_collected_keys(&self) {
+        self.collected_keys
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Disable type coercion and keep every value as a string.
+    ///
+    /// By default values like `8080` or `true` are coerced to numbers and
+    /// booleans. In strict mode they stay strings, so a field must accept a
+    /// string for the variable to deserialize and mismatches are reported by
+    /// serde with the offending field path. Overrides
+    /// [`trim_values`](Environment::trim_values) and
+    /// [`numeric_formats`](Environment::numeric_formats). Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("PORT", "8080")
+    ///     .strict_types(true);
+    /// assert_eq!(env.collect().unwrap()["port"], "8080");
+    /// ```
+    pub fn strict_types(mut self, strict: bool) -> Self {
+        self.strict_types = strict;
+        self
+    }
 
-        if let Some(prefix) = &self.prefix {
-            parts.push(prefix.as_str().to_string());
+    /// Read values written in `syntax` as lists, such as `APP_HOSTS=a,b,c`
+    /// with [`ListSyntax::separated`] or `APP_HOSTS=(a|b|c)` with
+    /// [`ListSyntax::bracketed`].
+    ///
+    /// Items are trimmed and then parsed with this source's settings, like
+    /// any other value: they are coerced to numbers and booleans, honor
+    /// [`numeric_formats`](Environment::numeric_formats), and stay strings
+    /// under [`strict_types`](Environment::strict_types). JSON arrays such as
+    /// `["a","b"]` are still recognized. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::environment::ListSyntax;
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("HOSTS", "a.example, b.example")
+    ///     .override_with("PORTS", "80,443")
+    ///     .override_with("TAGS", "")
+    ///     .with_list_syntax(ListSyntax::separated(','));
+    /// let value = env.collect().unwrap();
+    /// assert_eq!(value["hosts"], serde_json::json!(["a.example", "b.example"]));
+    /// assert_eq!(value["ports"], serde_json::json!([80, 443]));
+    /// assert_eq!(value["tags"], serde_json::json!([]));
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("HOSTS", "(a.example|b.example)")
+    ///     .with_list_syntax(ListSyntax::bracketed('(', ')', '|'));
+    /// assert_eq!(env.collect().unwrap()["hosts"], serde_json::json!(["a.example", "b.example"]));
+    /// ```
+    pub fn with_list_syntax(mut self, syntax: ListSyntax) -> Self {
+        self.list_syntax = Some(syntax);
+        self
+    }
+
+    /// Read the variable of the field at dotted `path` with `parser`.
+    ///
+    /// The parser receives the variable's text exactly as set, before any
+    /// coercion, so `007` stays `007` and `1e3` is not turned into `1000.0`,
+    /// and returns the value the field deserializes from. A failing parser
+    /// is reported as [`Error::Parse`] naming `path`. Typed overrides are
+    /// taken as they are. A parser registered again for the same path
+    /// replaces the earlier one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .override_with("CODE", "007")
+    ///     .with_value_parser("code", |text| Ok::<_, String>(text.into()));
+    /// assert_eq!(env.collect()?["code"], "007");
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    pub fn with_value_parser<E, F>(mut self, path: impl Into<String>, parser: F) -> Self
+    where
+        E: std::fmt::Display,
+        F: Fn(&str) -> std::result::Result<Value, E> + Send + Sync + 'static,
+    {
+        self.set_value_parser(path.into(), ValueParser::new(parser));
+        self
+    }
+
+    pub(crate) fn set_value_parser(&mut self, path: String, parser: ValueParser) {
+        self.value_parsers.retain(|(own, _)| *own != path);
+        self.value_parsers.push((path, parser));
+    }
+
+    /// Read the field at dotted `path` as `true` whenever its variable is set.
+    ///
+    /// The variable's value is ignored, so `APP_FEATURE=` (set but empty) turns
+    /// the flag on just like `APP_FEATURE=1`. When the variable is absent the
+    /// field is left to other sources. The variable name follows the usual
+    /// rules, including [`with_field_mapping`](Environment::with_field_mapping).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .override_with("APP_FEATURE", "")
+    ///     .presence_flag("feature");
+    /// assert_eq!(env.collect().unwrap()["feature"], true);
+    /// ```
+    pub fn presence_flag(mut self, path: impl Into<String>) -> Self {
+        self.presence_flags.push(path.into());
+        self
+    }
+
+    /// Set every presence flag whose variable exists to `true`.
+    fn apply_presence_flags(&self, value: &mut Value) {
+        let Value::Object(map) = value else {
+            return;
+        };
+
+        for path in &self.presence_flags {
+            let env_key = match self.field_mappings.get(path) {
+                Some(env_key) => env_key.clone(),
+                None => self.build_env_key(&path.split('.').collect::<Vec<_>>()),
+            };
+            if self.var(&env_key).is_some() {
+                self.record_key(&env_key);
+                let parts: Vec<String> = path.split('.').map(str::to_string).collect();
+                Self::insert_nested(map, &parts, Value::Bool(true));
+            }
         }
+    }
+
+    /// Only read variables for the given dotted field paths.
+    ///
+    /// Each path such as `database.max_size` is looked up as a single variable
+    /// (`APP_DATABASE_MAX_SIZE` with prefix `APP`) and inserted at exactly that
+    /// path, so field names containing the separator are nested correctly and
+    /// unrelated variables sharing the prefix are ignored. Field mappings still
+    /// apply on top.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigSource, Environment};
+    ///
+    /// let env = Environment::new()
+    ///     .with_prefix("APP")
+    ///     .with_known_paths(["database.max_size"])
+    ///     .override_with("APP_DATABASE_MAX_SIZE", "20")
+    ///     .override_with("APP_UNRELATED", "x");
+    /// let value = env.collect().unwrap();
+    /// assert_eq!(value["database"]["max_size"], 20);
+    /// assert!(value.get("unrelated").is_none());
+    /// ```
+    pub fn with_known_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.known_paths = Some(paths.into_iter().map(Into::into).collect());
+        self
+    }
 
-        for part in path {
-            parts.push(part.to_string());
+    /// Collect the variables of every known path and mapped field, each from
+    /// its mapping, the name derived from its path, or an alias.
+    fn collect_known_paths(&self, paths: &[String]) -> Result<Value> {
+        let mut result = Map::new();
+
+        let mapped = self
+            .field_mappings
+            .keys()
+            .filter(|field| !paths.contains(field));
+        for field in paths.iter().chain(mapped) {
+            if let Some((env_key, value)) = self.resolve_field(field)? {
+                self.record_key(&env_key);
+                let path: Vec<String> = field.split('.').map(str::to_string).collect();
+                Self::insert_nested(&mut result, &path, self.value_at(field, &env_key, &value)?);
+            }
         }
 
-        let key = parts.join(&self.separator);
+        Ok(Value::Object(result))
+    }
+
+    fn build_env_key(&self, path: &[&str]) -> String {
+        let field_path = path.join(&self.separator);
+
+        let key = match self.full_prefix() {
+            Some(prefix) => format!("{}{}{}", prefix, self.prefix_sep(), field_path),
+            None => field_path,
+        };
 
         if self.case_sensitive {
             key
@@ -251,7 +1135,164 @@ impl Environment {
         }
     }
 
-    fn parse_env_value(value: &str) -> Value {
+    /// Find the override registered for a variable name.
+    ///
+    /// Matching is exact when case sensitive, ASCII case-insensitive otherwise.
+    fn find_override(&self, key: &str) -> Option<&String> {
+        self.overrides.get(key).or_else(|| {
+            if self.case_sensitive {
+                None
+            } else {
+                self.overrides
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                    .map(|(_, value)| value)
+            }
+        })
+    }
+
+    /// Look up a single variable, preferring overrides over the process environment.
+    fn var(&self, key: &str) -> Option<String> {
+        self.find_override(key)
+            .cloned()
+            .or_else(|| env::var(key).ok())
+    }
+
+    /// All variables visible to this source: the process environment with
+    /// overrides layered on top, replacing any real variable of the same name.
+    fn vars(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = env::vars()
+            .filter(|(key, _)| self.find_override(key).is_none())
+            .collect();
+        vars.extend(
+            self.overrides
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        vars
+    }
+
+    /// Value of variable `key` read as `raw`: its typed override if it has one,
+    /// otherwise `raw` coerced with this source's settings.
+    fn value_of(&self, key: &str, raw: &str) -> Value {
+        match self.typed_override(key) {
+            Some(value) => value.clone(),
+            None => self.parse_value(raw),
+        }
+    }
+
+    /// Typed override registered for variable `key`.
+    fn typed_override(&self, key: &str) -> Option<&Value> {
+        self.typed_overrides.get(key).or_else(|| {
+            if self.case_sensitive {
+                None
+            } else {
+                self.typed_overrides
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                    .map(|(_, value)| value)
+            }
+        })
+    }
+
+    /// Value of variable `key` read as `raw` for the field at dotted `path`:
+    /// the field's [value parser](Self::with_value_parser) applied to the raw
+    /// text if it has one, otherwise [`value_of`](Self::value_of).
+    fn value_at(&self, path: &str, key: &str, raw: &str) -> Result<Value> {
+        let parser = self
+            .value_parsers
+            .iter()
+            .find(|(parsed, _)| parsed == path)
+            .filter(|_| self.typed_override(key).is_none());
+        match parser {
+            Some((_, parser)) => parser.parse(path, raw),
+            None => Ok(self.value_of(key, raw)),
+        }
+    }
+
+    /// Coerce a raw value using this source's settings.
+    fn parse_value(&self, value: &str) -> Value {
+        match self.list_syntax.and_then(|syntax| syntax.split(value)) {
+            Some(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.parse_item(item))
+                    .collect(),
+            ),
+            None => self.parse_item(value),
+        }
+    }
+
+    /// Coerce a raw value or list item using this source's settings.
+    fn parse_item(&self, value: &str) -> Value {
+        if self.strict_types {
+            return json!(value);
+        }
+
+        if self.numeric_formats {
+            let candidate = if self.trim_values {
+                value.trim()
+            } else {
+                value
+            };
+            if let Some(number) = Self::parse_numeric_literal(candidate) {
+                return number;
+            }
+        }
+
+        if self.trim_values {
+            let parsed = Self::parse_env_value(value.trim());
+            if !parsed.is_string() {
+                return parsed;
+            }
+        }
+        Self::parse_env_value(value)
+    }
+
+    /// Parse an integer written with underscore separators or a radix prefix.
+    ///
+    /// Returns `None` for anything else, including plain decimal integers, which
+    /// are already handled by [`parse_env_value`](Self::parse_env_value).
+    fn parse_numeric_literal(value: &str) -> Option<Value> {
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+
+        let (radix, digits) = match unsigned.get(..2) {
+            Some("0x" | "0X") => (16, &unsigned[2..]),
+            Some("0o" | "0O") => (8, &unsigned[2..]),
+            Some("0b" | "0B") => (2, &unsigned[2..]),
+            _ if unsigned.contains('_') => (10, unsigned),
+            _ => return None,
+        };
+
+        // Only digits of the radix, with each `_` between two of them
+        let mut after_digit = false;
+        for c in digits.chars() {
+            match c {
+                '_' if after_digit => after_digit = false,
+                c if c.is_digit(radix) => after_digit = true,
+                _ => return None,
+            }
+        }
+        if !after_digit {
+            return None;
+        }
+
+        let digits = digits.replace('_', "");
+        let magnitude = u64::from_str_radix(&digits, radix).ok()?;
+
+        if negative {
+            i64::try_from(-i128::from(magnitude)).ok().map(|n| json!(n))
+        } else if let Ok(n) = i64::try_from(magnitude) {
+            Some(json!(n))
+        } else {
+            Some(json!(magnitude))
+        }
+    }
+
+    pub(crate) fn parse_env_value(value: &str) -> Value {
         if let Ok(b) = value.parse::<bool>() {
             return json!(b);
         }
@@ -284,7 +1325,7 @@ impl Environment {
     /// This helper function takes a flat key path (e.g., ["http", "server", "port"])
     /// and creates the necessary nested structure in the map, inserting the value
     /// at the deepest level.
-    fn insert_nested(map: &mut Map<String, Value>, parts: &[String], value: Value) {
+    pub(crate) fn insert_nested(map: &mut Map<String, Value>, parts: &[String], value: Value) {
         if parts.is_empty() {
             return;
         }
@@ -316,6 +1357,14 @@ impl Environment {
         }
     }
 
+    /// Look up `fields` of `struct_name` as `PREFIX_STRUCT_FIELD` variables.
+    ///
+    /// The prefix, struct name and field name are uppercased and the non-empty
+    /// ones joined with the [prefix separator](Environment::prefix_separator),
+    /// so `ServerConfig`'s `max_pool_size` under `APP` is read from
+    /// `APP_SERVERCONFIG_MAX_POOL_SIZE`. A field's override names its variable
+    /// verbatim. Values are keyed by field name.
+    #[deprecated(note = "use `Environment::with_struct_infix` instead")]
     pub fn collect_for_struct(
         &self,
         struct_name: &str,
@@ -324,114 +1373,177 @@ impl Environment {
         let mut result = HashMap::new();
 
         for (field_name, field_override) in fields {
-            let env_key = if let Some(override_name) = field_override {
-                override_name.to_string()
-            } else if let Some(prefix) = &self.prefix {
-                format!(
-                    "{}_{}_{}_{}",
-                    prefix.as_str().to_uppercase(),
-                    struct_name.to_uppercase(),
-                    field_name.to_uppercase(),
-                    ""
-                )
-                .trim_end_matches('_')
-                .to_string()
-            } else {
-                format!(
-                    "{}_{}",
-                    struct_name.to_uppercase(),
-                    field_name.to_uppercase()
-                )
+            let env_key = match field_override {
+                Some(override_name) => override_name.to_string(),
+                None => [
+                    self.prefix.as_ref().map_or("", |prefix| prefix.as_str()),
+                    struct_name,
+                    field_name,
+                ]
+                .iter()
+                .filter(|part| !part.is_empty())
+                .map(|part| part.to_uppercase())
+                .collect::<Vec<_>>()
+                .join(self.prefix_sep()),
             };
 
-            if let Some(override_value) = self.overrides.get(&env_key) {
-                result.insert(
-                    field_name.to_string(),
-                    Self::parse_env_value(override_value),
-                );
-            } else if let Ok(value) = env::var(&env_key) {
-                result.insert(field_name.to_string(), Self::parse_env_value(&value));
+            if let Some(value) = self.var(&env_key) {
+                self.record_key(&env_key);
+                result.insert(field_name.to_string(), self.value_of(&env_key, &value));
             }
         }
 
         result
     }
 
-    pub fn collect_with_flat_keys(&self) -> Result<Value> {
-        let mut flat_map = HashMap::new();
+    /// Collect variables according to the configured lookup mode.
+    fn collect_variables(&self) -> Result<Value> {
+        if let Some(paths) = &self.known_paths {
+            return self.collect_known_paths(paths);
+        }
 
-        // First collect from environment variables
-        for (key, value) in env::vars() {
-            if let Some(prefix) = &self.prefix {
-                let prefix_str = if self.case_sensitive {
-                    prefix.as_str().to_string()
-                } else {
-                    prefix.as_str().to_uppercase()
-                };
+        if !self.field_mappings.is_empty() {
+            // Use field mappings when available
+            let mut result = Map::new();
 
-                let key_check = if self.case_sensitive {
-                    key.clone()
-                } else {
-                    key.to_uppercase()
+            // First collect using field mappings, falling back to their aliases
+            for field_name in self.field_mappings.keys() {
+                let Some((env_key, value)) = self.resolve_field(field_name)? else {
+                    continue;
                 };
+                self.record_key(&env_key);
+                let path: Vec<String> = field_name.split('.').map(str::to_string).collect();
+                Self::insert_nested(
+                    &mut result,
+                    &path,
+                    self.value_at(field_name, &env_key, &value)?,
+                );
+            }
+
+            // Then collect any prefixed variables not in mappings, prefix by
+            // prefix so a higher-priority prefix claims a field first
+            let mapped: Vec<String> = self
+                .field_mappings
+                .values()
+                .flat_map(|env_key| self.prefixed_keys(env_key))
+                .collect();
+            let vars = self.vars();
+            for prefix in self.full_prefixes() {
+                for (key, value) in &vars {
+                    let prefix_str = if self.case_sensitive {
+                        prefix.clone()
+                    } else {
+                        prefix.to_uppercase()
+                    };
 
-                if key_check.starts_with(&prefix_str) {
-                    let trimmed = key_check[prefix_str.len()..].trim_start_matches(&self.separator);
-                    let key_for_map = self.normalize_key(trimmed);
-                    flat_map.insert(key_for_map, Self::parse_env_value(&value));
+                    let key_check = if self.case_sensitive {
+                        key.clone()
+                    } else {
+                        key.to_uppercase()
+                    };
+
+                    if key_check.starts_with(&prefix_str)
+                        && !mapped.contains(key)
+                        && !self.field_aliases.iter().any(|(_, alias)| alias == key)
+                    {
+                        let trimmed =
+                            key_check[prefix_str.len()..].trim_start_matches(self.prefix_sep());
+                        let field_name = trimmed.to_lowercase();
+                        if !result.contains_key(&field_name) {
+                            let value = self.value_at(&field_name, key, value)?;
+                            result.insert(field_name, value);
+                            self.record_key(key);
+                        }
+                    }
                 }
-            } else {
-                flat_map.insert(key.to_lowercase(), Self::parse_env_value(&value));
             }
+
+            Ok(Value::Object(result))
+        } else {
+            self.collect_with_flat_keys()
         }
+    }
 
-        // Then apply overrides (overrides take precedence)
-        for (override_key, override_value) in &self.overrides {
-            if let Some(prefix) = &self.prefix {
-                let prefix_str = if self.case_sensitive {
-                    prefix.as_str().to_string()
-                } else {
-                    prefix.as_str().to_uppercase()
-                };
+    pub fn collect_with_flat_keys(&self) -> Result<Value> {
+        self.clear_collected_keys();
+        let mut flat_map = HashMap::new();
 
-                let key_check = if self.case_sensitive {
-                    override_key.clone()
+        // Overrides are part of `vars()`, so they are matched exactly like real variables
+        let prefixes: Vec<String> = self
+            .full_prefixes()
+            .into_iter()
+            .map(|prefix| {
+                if self.case_sensitive {
+                    prefix
                 } else {
-                    override_key.to_uppercase()
-                };
-
-                if key_check.starts_with(&prefix_str) {
-                    let trimmed = key_check[prefix_str.len()..].trim_start_matches(&self.separator);
-                    let key_for_map = self.normalize_key(trimmed);
-                    flat_map.insert(key_for_map, Self::parse_env_value(override_value));
+                    prefix.to_uppercase()
                 }
+            })
+            .collect();
+        // Index of the prefix each key was taken from, so a higher-priority
+        // prefix wins whatever order the variables come in
+        let mut taken_from: HashMap<String, usize> = HashMap::new();
+        for (key, value) in self.vars() {
+            if prefixes.is_empty() {
+                self.record_key(&key);
+                flat_map.insert(key.to_lowercase(), (key, value));
+                continue;
+            }
+
+            let key_check = if self.case_sensitive {
+                key.clone()
             } else {
-                flat_map.insert(
-                    override_key.to_lowercase(),
-                    Self::parse_env_value(override_value),
-                );
+                key.to_uppercase()
+            };
+
+            let Some((rank, prefix_str)) = prefixes
+                .iter()
+                .enumerate()
+                .find(|(_, prefix)| key_check.starts_with(prefix.as_str()))
+            else {
+                continue;
+            };
+            let trimmed = key_check[prefix_str.len()..].trim_start_matches(self.prefix_sep());
+            let trimmed = trimmed.trim_start_matches(self.nesting_chars.as_slice());
+            let key_for_map = self.normalize_key(trimmed);
+            if taken_from
+                .get(&key_for_map)
+                .is_some_and(|&existing| existing < rank)
+            {
+                continue;
             }
+            taken_from.insert(key_for_map.clone(), rank);
+            self.record_key(&key);
+            flat_map.insert(key_for_map, (key, value));
         }
 
         // Convert flat keys into nested structures if enabled
         let mut result = Map::new();
-        for (key, value) in flat_map {
+        for (key, (env_key, raw)) in flat_map {
             if self.nested {
                 // Split on separator to create nested structure
+                let key = self.nesting_chars.iter().fold(key, |key, delimiter| {
+                    key.replace(*delimiter, &self.separator)
+                });
                 let parts: Vec<&str> = key.split(&self.separator).collect();
                 if parts.len() == 1 {
                     // Single part, insert directly (lowercase it)
-                    result.insert(key.to_lowercase(), value);
+                    let key = key.to_lowercase();
+                    let value = self.value_at(&key, &env_key, &raw)?;
+                    result.insert(key, value);
                 } else {
                     // Multiple parts, create nested structure
                     // Lowercase each part individually
                     let lowercase_parts: Vec<String> =
                         parts.iter().map(|p| p.to_lowercase()).collect();
+                    let value = self.value_at(&lowercase_parts.join("."), &env_key, &raw)?;
                     Self::insert_nested(&mut result, &lowercase_parts, value);
                 }
             } else {
                 // Keep keys flat (backward compatible behavior)
-                result.insert(key.to_lowercase(), value);
+                let key = key.to_lowercase();
+                let value = self.value_at(&key, &env_key, &raw)?;
+                result.insert(key, value);
             }
         }
 
@@ -439,72 +1551,121 @@ impl Environment {
     }
 }
 
-impl ConfigSource for Environment {
-    fn source_type(&self) -> Source {
-        Source::Environment
+/// Resolve `{placeholder}` segments in an environment variable name.
+///
+/// Each `{name}` is replaced by the uppercased value of the variable `NAME`, so
+/// `{stage}_DB_URL` becomes `PROD_DB_URL` when `STAGE=prod`. Characters that
+/// cannot appear in a variable name, such as `-` and `.`, become `_`, so
+/// `eu-west-1` is substituted as `EU_WEST_1`. Names without placeholders are
+/// returned unchanged. Used for `#[gonfig(env_name)]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::environment::resolve_env_name;
+///
+/// std::env::set_var("DEPLOY_REGION", "eu-west-1");
+/// assert_eq!(resolve_env_name("{deploy_region}_BUCKET")?, "EU_WEST_1_BUCKET");
+/// assert_eq!(resolve_env_name("PLAIN_NAME")?, "PLAIN_NAME");
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Environment`] if a placeholder's variable is not set or a
+/// `{` is not closed.
+pub fn resolve_env_name(name: &str) -> Result<String> {
+    let mut resolved = String::with_capacity(name.len());
+    let mut rest = name;
+
+    while let Some((before, var, after)) = next_placeholder(name, rest)? {
+        resolved.push_str(before);
+        let value = env::var(&var).map_err(|_| {
+            Error::Environment(format!(
+                "Env name `{name}` needs the variable {var} to be set"
+            ))
+        })?;
+        resolved.extend(value.chars().map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        }));
+        rest = after;
     }
 
-    fn collect(&self) -> Result<Value> {
-        if !self.field_mappings.is_empty() {
-            // Use field mappings when available
-            let mut result = Map::new();
+    resolved.push_str(rest);
+    Ok(resolved)
+}
 
-            // First collect using field mappings
-            for (field_name, env_key) in &self.field_mappings {
-                // Check overrides first, then environment
-                if let Some(override_value) = self.overrides.get(env_key) {
-                    result.insert(field_name.clone(), Self::parse_env_value(override_value));
-                } else if let Ok(value) = env::var(env_key) {
-                    result.insert(field_name.clone(), Self::parse_env_value(&value));
-                }
-            }
+/// Variables named by the `{placeholder}` segments of an environment variable
+/// name, uppercased as [`resolve_env_name`] reads them.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::environment::env_name_placeholders;
+///
+/// assert_eq!(env_name_placeholders("{stage}_{region}_URL")?, vec!["STAGE", "REGION"]);
+/// assert!(env_name_placeholders("PLAIN_NAME")?.is_empty());
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Environment`] if a `{` is not closed.
+pub fn env_name_placeholders(name: &str) -> Result<Vec<String>> {
+    let mut vars = Vec::new();
+    let mut rest = name;
+    while let Some((_, var, after)) = next_placeholder(name, rest)? {
+        vars.push(var);
+        rest = after;
+    }
+    Ok(vars)
+}
 
-            // Then collect any prefixed variables not in mappings
-            if let Some(prefix) = &self.prefix {
-                for (key, value) in env::vars() {
-                    let prefix_str = if self.case_sensitive {
-                        prefix.as_str().to_string()
-                    } else {
-                        prefix.as_str().to_uppercase()
-                    };
+/// Split `rest` around its first placeholder: the text before it, the
+/// uppercased variable name and the text after it.
+fn next_placeholder<'a>(name: &str, rest: &'a str) -> Result<Option<(&'a str, String, &'a str)>> {
+    let Some(start) = rest.find('{') else {
+        return Ok(None);
+    };
+    let end = rest[start..].find('}').ok_or_else(|| {
+        Error::Environment(format!("Unterminated placeholder in env name `{name}`"))
+    })? + start;
+    let var = rest[start + 1..end].trim().to_uppercase();
+    Ok(Some((&rest[..start], var, &rest[end + 1..])))
+}
 
-                    let key_check = if self.case_sensitive {
-                        key.clone()
-                    } else {
-                        key.to_uppercase()
-                    };
+impl ConfigSource for Environment {
+    fn source_type(&self) -> Source {
+        Source::Environment
+    }
 
-                    if key_check.starts_with(&prefix_str)
-                        && !self.field_mappings.values().any(|v| v == &key)
-                    {
-                        let trimmed =
-                            key_check[prefix_str.len()..].trim_start_matches(&self.separator);
-                        let field_name = trimmed.to_lowercase();
-                        if !result.contains_key(&field_name) {
-                            result.insert(field_name, Self::parse_env_value(&value));
-                        }
-                    }
-                }
-            }
+    fn collect(&self) -> Result<Value> {
+        self.clear_collected_keys();
 
-            Ok(Value::Object(result))
-        } else {
-            self.collect_with_flat_keys()
-        }
+        let mut value = self.collect_variables()?;
+        self.apply_presence_flags(&mut value);
+        Ok(value)
     }
 
     fn has_value(&self, key: &str) -> bool {
         let env_key = self.build_env_key(&[key]);
-        self.overrides.contains_key(&env_key) || env::var(&env_key).is_ok()
+        self.var(&env_key).is_some()
     }
 
     fn get_value(&self, key: &str) -> Option<Value> {
         let env_key = self.build_env_key(&[key]);
+        let raw = self.var(&env_key)?;
+        self.value_at(key, &env_key, &raw).ok()
+    }
 
-        if let Some(override_value) = self.overrides.get(&env_key) {
-            Some(Self::parse_env_value(override_value))
-        } else {
-            env::var(&env_key).ok().map(|v| Self::parse_env_value(&v))
+    fn describe(&self) -> String {
+        match self.full_prefix() {
+            Some(prefix) => format!("env prefix {}{}", prefix, self.prefix_sep()),
+            None => "env (no prefix)".to_string(),
         }
     }
 
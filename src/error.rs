@@ -1,5 +1,6 @@
 //! Error types for configuration management.
 
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Comprehensive error type for configuration management operations.
@@ -15,7 +16,7 @@ use thiserror::Error;
 ///
 /// match ConfigBuilder::new().with_file("nonexistent.json") {
 ///     Ok(_) => println!("Config loaded successfully"),
-///     Err(Error::Io(_)) => println!("File not found or permission denied"),
+///     Err(Error::Io { .. }) => println!("File not found or permission denied"),
 ///     Err(Error::Config(msg)) => println!("Config parsing failed: {}", msg),
 ///     Err(e) => println!("Other error: {}", e),
 /// }
@@ -79,9 +80,32 @@ pub enum Error {
 
     /// File I/O errors.
     ///
-    /// Automatically converted from `std::io::Error` for file operations.
-    #[error("File I/O error: {0}")]
-    Io(#[from] std::io::Error),
+    /// `path` names the file that could not be read when it is known. Errors
+    /// converted from `std::io::Error` (for example through `?`) carry no path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// match ConfigBuilder::new().with_file("missing.json") {
+    ///     Err(Error::Io { path, source }) => {
+    ///         assert_eq!(path.as_deref(), Some(std::path::Path::new("missing.json")));
+    ///         assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+    ///     }
+    ///     _ => panic!("expected an I/O error"),
+    /// }
+    /// ```
+    #[error(
+        "File I/O error{}: {source}",
+        .path.as_ref().map(|path| format!(" for {}", path.display())).unwrap_or_default()
+    )]
+    Io {
+        /// File being accessed, if known.
+        path: Option<PathBuf>,
+        /// Underlying I/O error.
+        source: std::io::Error,
+    },
 
     /// Serialization/deserialization errors.
     ///
@@ -133,6 +157,283 @@ pub enum Error {
     /// ```
     #[error("Validation error: {0}")]
     Validation(String),
+
+    /// A remote source did not answer within the builder's timeout.
+    ///
+    /// Returned when [`ConfigBuilder::with_timeout`](crate::ConfigBuilder::with_timeout)
+    /// is set and a remote source takes longer than allowed to collect.
+    #[error("Timed out after {timeout:?} collecting {source_name}")]
+    Timeout {
+        /// Description of the source that timed out.
+        source_name: String,
+        /// The configured timeout.
+        timeout: std::time::Duration,
+    },
+
+    /// A configuration source failed to provide its values.
+    ///
+    /// Returned by remote sources such as
+    /// [`HttpJsonSource`](crate::remote::HttpJsonSource) when a fetch fails,
+    /// the endpoint answers with an error status, or the response cannot be
+    /// used.
+    #[error("Source error in {source_name}: {message}")]
+    Source {
+        /// Description of the failing source.
+        source_name: String,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// A configuration file's format could not be detected from its extension.
+    ///
+    /// Holds the offending extension, empty when the file has none. Returned
+    /// by [`ConfigBuilder::with_file`](crate::ConfigBuilder::with_file) and
+    /// friends instead of guessing a format; use
+    /// [`with_file_format`](crate::ConfigBuilder::with_file_format) to name
+    /// the format explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    ///
+    /// let err = ConfigBuilder::new().with_file("settings.conf").err().unwrap();
+    /// assert!(matches!(err, Error::UnknownFormat { ref extension, .. } if extension == "conf"));
+    /// ```
+    #[error(
+        "{}Unknown config file format for extension {extension:?}",
+        prefix(context)
+    )]
+    UnknownFormat {
+        /// The file's extension, empty when it has none.
+        extension: String,
+        /// Where the error occurred, empty if not known; see
+        /// [`Error::with_context`].
+        context: String,
+    },
+
+    /// A `#[gonfig(required)]` field was not provided by any source.
+    ///
+    /// Checked by the derive macro before deserializing, so the error names
+    /// the environment variable to set instead of surfacing as serde's
+    /// "missing field" message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[derive(Gonfig, Deserialize)]
+    /// #[Gonfig(env_prefix = "APP")]
+    /// struct Config {
+    ///     #[gonfig(required)]
+    ///     database_url: String,
+    /// }
+    ///
+    /// // Without APP_DATABASE_URL:
+    /// // "during transformation: Missing required field `database_url`; set APP_DATABASE_URL"
+    /// let err = Config::from_gonfig().unwrap_err();
+    /// ```
+    #[error("{}Missing required field `{field}`; set {env_key}", prefix(context))]
+    MissingRequired {
+        /// Field name, or its dotted path within a flattened struct.
+        field: String,
+        /// Environment variable that would provide the field.
+        env_key: String,
+        /// Where the error occurred, empty if not known; see
+        /// [`Error::with_context`].
+        context: String,
+    },
+
+    /// A custom value parser rejected a field's value.
+    ///
+    /// Returned for fields annotated with `#[gonfig(parse_with = "...")]` and
+    /// by [`transform::parse_with`](crate::transform::parse_with), carrying the
+    /// dotted path of the field and the parser's error message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{transform, Error};
+    /// use serde_json::{json, Value};
+    ///
+    /// let mut value = json!({ "timeout": "soon" });
+    /// let err = transform::parse_with(&mut value, "timeout", |text| {
+    ///     text.parse::<u64>().map(Value::from)
+    /// })
+    /// .unwrap_err();
+    /// assert!(matches!(err, Error::Parse { ref path, .. } if path == "timeout"));
+    /// ```
+    #[error("Failed to parse `{path}`: {}{message}", prefix(context))]
+    Parse {
+        /// Dotted path of the field in the merged configuration.
+        path: String,
+        /// Error reported by the parser.
+        message: String,
+        /// Where the error occurred, empty if not known; see
+        /// [`Error::with_context`].
+        context: String,
+    },
+
+    /// The merged configuration does not match the type being built.
+    ///
+    /// Returned by [`ConfigBuilder::build`](crate::ConfigBuilder::build) and
+    /// the other typed builds. `path` is the JSON pointer of the offending
+    /// value, such as `/database/pool/maxsize`, and is empty when the whole
+    /// configuration has the wrong shape. serde's error is kept as the
+    /// [`source`](std::error::Error::source).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::{ConfigBuilder, Error};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Pool { maxsize: u32 }
+    /// #[derive(Debug, Deserialize)]
+    /// struct Database { pool: Pool }
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config { database: Database }
+    ///
+    /// let err = ConfigBuilder::new()
+    ///     .with_defaults(serde_json::json!({ "database": { "pool": { "maxsize": "many" } } }))?
+    ///     .build::<Config>()
+    ///     .unwrap_err();
+    /// assert!(matches!(err, Error::Deserialize { ref path, .. } if path == "/database/pool/maxsize"));
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    #[error(
+        "Deserialization error{}: {}{source}",
+        if path.is_empty() { String::new() } else { format!(" at `{path}`") },
+        prefix(context)
+    )]
+    Deserialize {
+        /// JSON pointer of the value that failed to deserialize.
+        path: String,
+        /// Error reported by serde.
+        source: serde_json::Error,
+        /// Where the error occurred, such as `during deserialization`; see
+        /// [`Error::with_context`].
+        context: String,
+    },
+}
+
+/// `outer` prepended to the context `inner`.
+fn nest(outer: &impl std::fmt::Display, inner: String) -> String {
+    if inner.is_empty() {
+        outer.to_string()
+    } else {
+        format!("{outer}: {inner}")
+    }
+}
+
+/// `context` followed by `": "`, or nothing when `context` is empty.
+fn prefix(context: &str) -> String {
+    if context.is_empty() {
+        String::new()
+    } else {
+        format!("{context}: ")
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(source: std::io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl Error {
+    /// An I/O error that occurred while accessing `path`.
+    pub(crate) fn io_at(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+
+    /// Prefix the error message with `context`, keeping the variant.
+    ///
+    /// [`ConfigBuilder`](crate::ConfigBuilder) uses this to record the phase an
+    /// error occurred in (collection, transformation, validation or
+    /// deserialization) and, while collecting, which source failed. Variants
+    /// without a free-form message, such as [`Error::Deserialize`], keep
+    /// `context` in their `context` field and show it in their message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::Error;
+    ///
+    /// let err = Error::Validation("port must be non-zero".to_string())
+    ///     .with_context("during validation");
+    ///
+    /// assert!(matches!(err, Error::Validation(_)));
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "Validation error: during validation: port must be non-zero"
+    /// );
+    /// ```
+    pub fn with_context(self, context: impl std::fmt::Display) -> Self {
+        match self {
+            Error::Config(msg) => Error::Config(format!("{context}: {msg}")),
+            Error::Environment(msg) => Error::Environment(format!("{context}: {msg}")),
+            Error::Cli(msg) => Error::Cli(format!("{context}: {msg}")),
+            Error::Io { path, source } => Error::Io {
+                path,
+                source: std::io::Error::new(source.kind(), format!("{context}: {source}")),
+            },
+            Error::Serialization(msg) => Error::Serialization(format!("{context}: {msg}")),
+            Error::MergeConflict(msg) => Error::MergeConflict(format!("{context}: {msg}")),
+            Error::Validation(msg) => Error::Validation(format!("{context}: {msg}")),
+            Error::Timeout {
+                source_name,
+                timeout,
+            } => Error::Timeout {
+                source_name: format!("{context}: {source_name}"),
+                timeout,
+            },
+            Error::Source {
+                source_name,
+                message,
+            } => Error::Source {
+                source_name,
+                message: format!("{context}: {message}"),
+            },
+            Error::UnknownFormat {
+                extension,
+                context: inner,
+            } => Error::UnknownFormat {
+                extension,
+                context: nest(&context, inner),
+            },
+            Error::MissingRequired {
+                field,
+                env_key,
+                context: inner,
+            } => Error::MissingRequired {
+                field,
+                env_key,
+                context: nest(&context, inner),
+            },
+            Error::Parse {
+                path,
+                message,
+                context: inner,
+            } => Error::Parse {
+                path,
+                message,
+                context: nest(&context, inner),
+            },
+            Error::Deserialize {
+                path,
+                source,
+                context: inner,
+            } => Error::Deserialize {
+                path,
+                source,
+                context: nest(&context, inner),
+            },
+        }
+    }
 }
 
 /// Type alias for `Result<T, gonfig::Error>`.
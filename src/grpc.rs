@@ -0,0 +1,346 @@
+//! Configuration source backed by a gRPC config service.
+//!
+//! [`GrpcClient`] talks to a service implementing this protocol with `tonic`:
+//!
+//! ```proto
+//! syntax = "proto3";
+//! package gonfig.config.v1;
+//!
+//! service ConfigService {
+//!   rpc GetConfig(GetConfigRequest) returns (GetConfigResponse);
+//! }
+//!
+//! message GetConfigRequest {
+//!   string name = 1;
+//!   string profile = 2;  // empty when not set
+//! }
+//!
+//! message GetConfigResponse {
+//!   bytes content = 1;
+//!   string format = 2;  // `json`, `yaml`, `toml` or `ini`
+//! }
+//! ```
+//!
+//! The messages are available as [`proto::GetConfigRequest`] and
+//! [`proto::GetConfigResponse`] for servers written with `tonic`.
+
+use crate::{
+    config::ConfigFormat,
+    error::{Error, Result},
+    source::{ConfigSource, Source},
+};
+use serde_json::Value;
+use std::any::Any;
+use std::sync::Mutex;
+use std::time::Duration;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::Endpoint;
+
+/// Wire messages of the `gonfig.config.v1.ConfigService` protocol.
+pub mod proto {
+    /// Request of the `GetConfig` call.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetConfigRequest {
+        /// Name of the application or document to fetch.
+        #[prost(string, tag = "1")]
+        pub name: String,
+        /// Profile or environment, empty when not set.
+        #[prost(string, tag = "2")]
+        pub profile: String,
+    }
+
+    /// Response of the `GetConfig` call.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetConfigResponse {
+        /// Encoded configuration document.
+        #[prost(bytes = "vec", tag = "1")]
+        pub content: Vec<u8>,
+        /// Format of `content`: `json`, `yaml`, `toml` or `ini`.
+        #[prost(string, tag = "2")]
+        pub format: String,
+    }
+}
+
+/// Fully qualified name of the config service.
+pub const SERVICE_NAME: &str = "gonfig.config.v1.ConfigService";
+
+/// Path of the `GetConfig` method.
+pub const GET_CONFIG_PATH: &str = "/gonfig.config.v1.ConfigService/GetConfig";
+
+/// Request sent to a config service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigRequest {
+    /// Name of the application or document to fetch.
+    pub name: String,
+    /// Optional profile or environment, e.g. `prod`.
+    pub profile: Option<String>,
+}
+
+impl ConfigRequest {
+    /// Create a request for `name` without a profile.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            profile: None,
+        }
+    }
+
+    /// Set the profile.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+}
+
+/// Document returned by a config service: raw bytes plus their format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigResponse {
+    /// Encoded configuration document.
+    pub content: Vec<u8>,
+    /// Format of `content`.
+    pub format: ConfigFormat,
+}
+
+impl ConfigResponse {
+    /// Create a response holding `content` in `format`.
+    pub fn new(content: impl Into<Vec<u8>>, format: ConfigFormat) -> Self {
+        Self {
+            content: content.into(),
+            format,
+        }
+    }
+}
+
+/// Client abstraction over a gRPC config service.
+///
+/// [`GrpcClient`] implements it with `tonic`. Implement it for a client of a
+/// service speaking another protocol, or for a mock in tests. The unary call
+/// takes a [`ConfigRequest`] and returns the encoded document as a
+/// [`ConfigResponse`].
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::grpc::{ConfigRequest, ConfigResponse, ConfigService};
+/// use gonfig::ConfigFormat;
+///
+/// struct Fixed;
+///
+/// impl ConfigService for Fixed {
+///     fn fetch(&self, _request: &ConfigRequest) -> gonfig::Result<ConfigResponse> {
+///         Ok(ConfigResponse::new(r#"{"port": 8080}"#, ConfigFormat::Json))
+///     }
+/// }
+/// ```
+pub trait ConfigService: Send + Sync {
+    /// Fetch the configuration document for `request`.
+    fn fetch(&self, request: &ConfigRequest) -> Result<ConfigResponse>;
+
+    /// Address of the service, used in source descriptions.
+    fn endpoint(&self) -> String {
+        String::new()
+    }
+}
+
+/// [`ConfigService`] calling `GetConfig` on a `tonic` channel.
+///
+/// Each fetch connects to the endpoint, sends one request and closes the
+/// connection. The call runs on a runtime of its own thread, so it also works
+/// when the configuration is built from within an async runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::grpc::GrpcClient;
+/// use std::time::Duration;
+///
+/// let client = GrpcClient::new("http://config.internal:50051").with_timeout(Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcClient {
+    endpoint: String,
+    timeout: Duration,
+}
+
+impl GrpcClient {
+    /// Create a client for `endpoint`, e.g. `http://config.internal:50051`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Give up on connecting and on each call after `timeout` (30 seconds by
+    /// default).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn get_config(
+        endpoint: Endpoint,
+        request: proto::GetConfigRequest,
+    ) -> Result<proto::GetConfigResponse> {
+        let channel = endpoint.connect().await.map_err(|e| fail(e.to_string()))?;
+        let mut client = tonic::client::Grpc::new(channel);
+        client.ready().await.map_err(|e| fail(e.to_string()))?;
+        let codec =
+            tonic::codec::ProstCodec::<proto::GetConfigRequest, proto::GetConfigResponse>::default(
+            );
+        client
+            .unary(
+                tonic::Request::new(request),
+                PathAndQuery::from_static(GET_CONFIG_PATH),
+                codec,
+            )
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(|status| fail(format!("{:?}: {}", status.code(), status.message())))
+    }
+}
+
+impl ConfigService for GrpcClient {
+    fn fetch(&self, request: &ConfigRequest) -> Result<ConfigResponse> {
+        let endpoint = Endpoint::from_shared(self.endpoint.clone())
+            .map_err(|e| fail(format!("invalid endpoint `{}`: {e}", self.endpoint)))?
+            .connect_timeout(self.timeout)
+            .timeout(self.timeout);
+        let request = proto::GetConfigRequest {
+            name: request.name.clone(),
+            profile: request.profile.clone().unwrap_or_default(),
+        };
+
+        let response = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| fail(e.to_string()))?;
+            runtime.block_on(Self::get_config(endpoint, request))
+        })
+        .join()
+        .map_err(|_| fail("gRPC client thread panicked".to_string()))??;
+
+        let format = ConfigFormat::from_extension(&response.format).ok_or_else(|| {
+            fail(format!(
+                "unknown config format `{}` in response",
+                response.format
+            ))
+        })?;
+        Ok(ConfigResponse::new(response.content, format))
+    }
+
+    fn endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+}
+
+fn fail(message: String) -> Error {
+    Error::Source {
+        source_name: "grpc".to_string(),
+        message,
+    }
+}
+
+/// Configuration source reading a document from a gRPC config service.
+///
+/// The returned bytes are decoded as UTF-8 and parsed in the format the
+/// service reports. The source reports [`Source::Remote`], placing it above
+/// environment variables, and is skipped on failure when the builder is
+/// [`resilient`](crate::ConfigBuilder::resilient).
+///
+/// Every [`collect`](ConfigSource::collect) fetches the document and keeps it;
+/// [`get_value`](ConfigSource::get_value) and
+/// [`has_value`](ConfigSource::has_value) read that snapshot, fetching only when
+/// there is none yet.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::grpc::{ConfigRequest, ConfigResponse, ConfigService, GrpcSource};
+/// use gonfig::{ConfigFormat, ConfigSource};
+///
+/// struct Fixed;
+///
+/// impl ConfigService for Fixed {
+///     fn fetch(&self, _request: &ConfigRequest) -> gonfig::Result<ConfigResponse> {
+///         Ok(ConfigResponse::new("http:\n  port: 9000\n", ConfigFormat::Yaml))
+///     }
+/// }
+///
+/// let source = GrpcSource::new(Fixed, ConfigRequest::new("billing"));
+/// let value = source.collect()?;
+/// assert_eq!(value["http"]["port"], 9000);
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub struct GrpcSource {
+    service: Box<dyn ConfigService>,
+    request: ConfigRequest,
+    snapshot: Mutex<Option<Value>>,
+}
+
+impl GrpcSource {
+    /// Create a source sending `request` to `service`.
+    pub fn new(service: impl ConfigService + 'static, request: ConfigRequest) -> Self {
+        Self {
+            service: Box::new(service),
+            request,
+            snapshot: Mutex::new(None),
+        }
+    }
+
+    /// Create a source sending `request` to the `tonic` service at `endpoint`.
+    pub fn connect(endpoint: impl Into<String>, request: ConfigRequest) -> Self {
+        Self::new(GrpcClient::new(endpoint), request)
+    }
+
+    fn fetch(&self) -> Result<Value> {
+        let response = self.service.fetch(&self.request)?;
+        let content = String::from_utf8(response.content).map_err(|e| {
+            Error::Serialization(format!("Config service returned invalid UTF-8: {e}"))
+        })?;
+        response.format.parse(&content)
+    }
+}
+
+impl ConfigSource for GrpcSource {
+    fn source_type(&self) -> Source {
+        Source::Remote
+    }
+
+    fn collect(&self) -> Result<Value> {
+        let data = self.fetch()?;
+        *self.snapshot.lock().unwrap_or_else(|e| e.into_inner()) = Some(data.clone());
+        Ok(data)
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        let mut snapshot = self.snapshot.lock().unwrap_or_else(|e| e.into_inner());
+        if snapshot.is_none() {
+            *snapshot = Some(self.fetch().ok()?);
+        }
+        key.split('.')
+            .try_fold(snapshot.as_ref()?, |current, part| current.get(part))
+            .cloned()
+    }
+
+    fn describe(&self) -> String {
+        let endpoint = self.service.endpoint();
+        let mut description = format!("grpc config {}", self.request.name);
+        if let Some(profile) = &self.request.profile {
+            description.push_str(&format!(" ({profile})"));
+        }
+        if !endpoint.is_empty() {
+            description.push_str(&format!(" from {endpoint}"));
+        }
+        description
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
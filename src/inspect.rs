@@ -0,0 +1,231 @@
+//! Introspection helpers for showing the effective configuration.
+
+use crate::{
+    builder::ConfigBuilder,
+    config::ConfigFormat,
+    error::{Error, Result},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::io::{IsTerminal, Write};
+
+/// Placeholder written in place of redacted values.
+pub const REDACTED: &str = "***";
+
+const KEY_COLOR: &str = "\x1b[36m";
+const HEADER_COLOR: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Key fragments that mark a value as secret.
+const SECRET_MARKERS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "private_key",
+    "credential",
+];
+
+/// Whether a configuration key names a secret, e.g. `db_password` or `apiToken`.
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase().replace('-', "_");
+    SECRET_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Replace every value stored under a secret-looking key with [`REDACTED`].
+///
+/// Objects are walked recursively, including objects inside arrays. A secret
+/// key holding an object or array is redacted as a whole.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::inspect::redact_secrets;
+/// use serde_json::json;
+///
+/// let mut value = json!({ "host": "db", "db_password": "hunter2" });
+/// redact_secrets(&mut value);
+/// assert_eq!(value, json!({ "host": "db", "db_password": "***" }));
+/// ```
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if is_secret_key(key) && !child.is_null() {
+                    *child = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(child);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Build `T` from `builder`, redact secrets and write it to `writer` in `format`.
+///
+/// The configuration is deserialized into `T` first, so the output shows the
+/// values the application actually uses, including serde defaults, and fails
+/// the same way the application would. It is serialized back from `T`, so
+/// serde attributes such as `skip_serializing_if` apply and merged keys `T`
+/// does not know are left out. The output is never colored; see
+/// [`write_effective_config_colored`].
+///
+/// # Errors
+///
+/// Returns build errors from the builder, [`Error::Serialization`] if the
+/// configuration cannot be rendered, and [`Error::Io`] if writing fails.
+pub fn write_effective_config<T, W>(
+    builder: ConfigBuilder,
+    format: ConfigFormat,
+    writer: W,
+) -> Result<()>
+where
+    T: DeserializeOwned + Serialize,
+    W: Write,
+{
+    write_effective_config_colored::<T, W>(builder, format, writer, false)
+}
+
+/// Like [`write_effective_config`], highlighting keys and section headers with
+/// ANSI colors when `color` is set.
+///
+/// Use [`use_color`] to decide for a given stream.
+///
+/// # Errors
+///
+/// See [`write_effective_config`].
+pub fn write_effective_config_colored<T, W>(
+    builder: ConfigBuilder,
+    format: ConfigFormat,
+    mut writer: W,
+    color: bool,
+) -> Result<()>
+where
+    T: DeserializeOwned + Serialize,
+    W: Write,
+{
+    let config: T = builder.build()?;
+    let mut value = serde_json::to_value(&config)
+        .map_err(|e| Error::Serialization(format!("Failed to serialize config: {e}")))?;
+    redact_secrets(&mut value);
+
+    let mut rendered = format.serialize(&value)?;
+    if !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    if color {
+        rendered = highlight(&rendered, &format);
+    }
+    writer.write_all(rendered.as_bytes())?;
+    Ok(())
+}
+
+/// Whether output written to `stream` should be colored.
+///
+/// Colors are disabled when the `NO_COLOR` environment variable is set to a
+/// non-empty value (see <https://no-color.org>) or when `stream` is not a
+/// terminal, e.g. when output is piped to a file or another program.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::inspect::use_color;
+///
+/// let color = use_color(&std::io::stdout());
+/// # let _ = color;
+/// ```
+pub fn use_color(stream: &impl IsTerminal) -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && stream.is_terminal()
+}
+
+/// Highlight keys and section headers of `rendered` output in `format`.
+fn highlight(rendered: &str, format: &ConfigFormat) -> String {
+    let mut output = String::with_capacity(rendered.len());
+    for line in rendered.split_inclusive('\n') {
+        let indent = line.len() - line.trim_start().len();
+        let body = &line[indent..];
+        let is_header =
+            matches!(format, ConfigFormat::Toml | ConfigFormat::Ini) && body.starts_with('[');
+
+        output.push_str(&line[..indent]);
+        if is_header {
+            let end = body.trim_end_matches('\n').len();
+            output.push_str(&format!(
+                "{HEADER_COLOR}{}{RESET}{}",
+                &body[..end],
+                &body[end..]
+            ));
+        } else if let Some(end) = key_end(body, format) {
+            output.push_str(&format!(
+                "{KEY_COLOR}{}{RESET}{}",
+                &body[..end],
+                &body[end..]
+            ));
+        } else {
+            output.push_str(body);
+        }
+    }
+    output
+}
+
+/// Byte length of the key at the start of a rendered line, if it has one.
+fn key_end(body: &str, format: &ConfigFormat) -> Option<usize> {
+    match format {
+        ConfigFormat::Json => body
+            .starts_with('"')
+            .then(|| body.find("\":").map(|end| end + 1))
+            .flatten(),
+        ConfigFormat::Yaml => {
+            let start = if body.starts_with("- ") { 2 } else { 0 };
+            let rest = &body[start..];
+            let end = rest.find(": ").or_else(|| {
+                rest.trim_end()
+                    .ends_with(':')
+                    .then(|| rest.trim_end().len() - 1)
+            })?;
+            (end > 0 && !rest.starts_with(['\'', '"'])).then_some(start + end)
+        }
+        ConfigFormat::Toml | ConfigFormat::Ini => body.find(" = "),
+    }
+}
+
+/// Print the effective configuration of `T` to stdout with secrets redacted.
+///
+/// Intended for a `myapp config` style subcommand. Values under keys that look
+/// like secrets (`password`, `token`, `api_key`, ...) are replaced with
+/// [`REDACTED`]. Keys are colored when stdout is a terminal and `NO_COLOR` is
+/// not set (see [`use_color`]). See [`write_effective_config`] to write
+/// elsewhere.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gonfig::{print_effective_config, ConfigBuilder, ConfigFormat};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Config {
+///     port: u16,
+///     api_token: String,
+/// }
+///
+/// print_effective_config::<Config>(ConfigBuilder::new().with_env_flat("APP"), ConfigFormat::Yaml)?;
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// See [`write_effective_config`].
+pub fn print_effective_config<T>(builder: ConfigBuilder, format: ConfigFormat) -> Result<()>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let stdout = std::io::stdout();
+    let color = use_color(&stdout);
+    write_effective_config_colored::<T, _>(builder, format, stdout.lock(), color)
+}
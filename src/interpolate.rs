@@ -0,0 +1,140 @@
+//! `${VAR}` placeholder substitution in configuration file text.
+
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// What to do with a placeholder whose variable is not defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingVar {
+    /// Fail loading the file with [`Error::Config`].
+    #[default]
+    Error,
+    /// Replace the placeholder with an empty string.
+    Empty,
+    /// Leave the placeholder untouched.
+    Keep,
+}
+
+/// Substitutes `${VAR}` placeholders in raw configuration file text.
+///
+/// Variables are looked up in the provided map first and, when enabled, in the
+/// process environment. Write `$${VAR}` to produce a literal `${VAR}`. A `$`
+/// that does not start a placeholder is kept as is.
+///
+/// Substitution happens before the file is parsed, so placeholders can stand
+/// for any part of the document, e.g. an unquoted number in TOML.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::interpolate::{Interpolation, MissingVar};
+///
+/// let interpolation = Interpolation::from_map([("HOST", "db.internal")]);
+/// let text = interpolation.apply("url: postgres://${HOST}/app # $${LITERAL}")?;
+/// assert_eq!(text, "url: postgres://db.internal/app # ${LITERAL}");
+///
+/// let lenient = Interpolation::new().on_missing(MissingVar::Keep);
+/// assert_eq!(lenient.apply("${UNSET}")?, "${UNSET}");
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interpolation {
+    vars: HashMap<String, String>,
+    process_env: bool,
+    on_missing: MissingVar,
+}
+
+impl Interpolation {
+    /// Create an interpolation with no variables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an interpolation resolving variables from `vars`.
+    pub fn from_map<I, K, V>(vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            vars: vars
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Create an interpolation resolving variables from the process environment.
+    pub fn from_process_env() -> Self {
+        Self::new().with_process_env(true)
+    }
+
+    /// Also resolve variables missing from the map in the process environment.
+    pub fn with_process_env(mut self, enabled: bool) -> Self {
+        self.process_env = enabled;
+        self
+    }
+
+    /// Set the policy for undefined variables. Defaults to [`MissingVar::Error`].
+    pub fn on_missing(mut self, policy: MissingVar) -> Self {
+        self.on_missing = policy;
+        self
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        self.vars
+            .get(name)
+            .cloned()
+            .or_else(|| self.process_env.then(|| std::env::var(name).ok()).flatten())
+    }
+
+    /// Substitute every placeholder in `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] for an unterminated or empty placeholder, and
+    /// for an undefined variable when the policy is [`MissingVar::Error`].
+    pub fn apply(&self, text: &str) -> Result<String> {
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find('$') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            if let Some(escaped) = after.strip_prefix("${") {
+                // `$${` is an escaped placeholder
+                output.push_str("${");
+                rest = escaped;
+            } else if let Some(placeholder) = after.strip_prefix('{') {
+                let end = placeholder
+                    .find('}')
+                    .ok_or_else(|| Error::Config("Unterminated `${` placeholder".to_string()))?;
+                let name = placeholder[..end].trim();
+                if name.is_empty() {
+                    return Err(Error::Config("Empty `${}` placeholder".to_string()));
+                }
+
+                match (self.lookup(name), self.on_missing) {
+                    (Some(value), _) => output.push_str(&value),
+                    (None, MissingVar::Error) => {
+                        return Err(Error::Config(format!(
+                            "Undefined variable `{name}` in `${{{name}}}` placeholder"
+                        )))
+                    }
+                    (None, MissingVar::Empty) => {}
+                    (None, MissingVar::Keep) => output.push_str(&rest[start..start + end + 3]),
+                }
+                rest = &placeholder[end + 1..];
+            } else {
+                output.push('$');
+                rest = after;
+            }
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+}
@@ -77,18 +77,30 @@
 //!
 //! ### Environment Variables
 //!
-//! Environment variables are automatically mapped to struct fields using configurable prefixes:
+//! Environment variables are automatically mapped to struct fields using configurable prefixes.
+//! `with_env` nests variables on `_`, so `MYAPP_DATABASE_URL` fills `database.url`;
+//! `with_env_flat` reads it into a top-level `database_url` field instead:
 //!
-//! ```rust,no_run
-//! use gonfig::{Environment, ConfigBuilder};
+//! ```rust
+//! use gonfig::ConfigBuilder;
 //! use serde::Deserialize;
 //!
 //! #[derive(Deserialize)]
+//! struct Database {
+//!     url: String,
+//! }
+//!
+//! #[derive(Deserialize)]
 //! struct Config {
-//!     database_url: String,
+//!     database: Database,
 //!     port: u16,
 //! }
 //!
+//! #[derive(Deserialize)]
+//! struct FlatConfig {
+//!     database_url: String,
+//! }
+//!
 //! fn main() -> gonfig::Result<()> {
 //!     std::env::set_var("MYAPP_DATABASE_URL", "postgres://localhost/db");
 //!     std::env::set_var("MYAPP_PORT", "3000");
@@ -96,6 +108,13 @@
 //!     let config: Config = ConfigBuilder::new()
 //!         .with_env("MYAPP")
 //!         .build()?;
+//!     assert_eq!(config.database.url, "postgres://localhost/db");
+//!     assert_eq!(config.port, 3000);
+//!
+//!     let flat: FlatConfig = ConfigBuilder::new()
+//!         .with_env_flat("MYAPP")
+//!         .build()?;
+//!     assert_eq!(flat.database_url, "postgres://localhost/db");
 //!     Ok(())
 //! }
 //! ```
@@ -166,10 +185,29 @@
 //! - `#[Gonfig(env_prefix = "PREFIX")]` - Set environment variable prefix
 //! - `#[Gonfig(allow_cli)]` - Enable CLI argument support
 //! - `#[Gonfig(allow_config)]` - Enable config file support
+//! - `#[Gonfig(sources = "env,cli")]` - Choose exactly which sources are read
+//! - `#[Gonfig(wrap_scalars)]` - Accept a single value for list fields
+//! - `#[Gonfig(env_separator = "__")]` - Separator between prefix and field name
+//! - `#[Gonfig(rename_all = "camelCase")]` - Case convention for CLI flags and env names
+//! - `#[Gonfig(clap)]` - Parse the command line with the generated `clap::Command` (`clap` feature)
 //!
 //! ### Field-level attributes:
 //! - `#[gonfig(env_name = "CUSTOM_NAME")]` - Override environment variable name
+//! - `#[gonfig(env_alias = "OLD_NAME")]` - Also read the field from another variable
 //! - `#[gonfig(cli_name = "custom-name")]` - Override CLI argument name
+//! - `#[gonfig(cli_short = 'p')]` - Also accept a short flag like `-p`
+//! - `#[gonfig(sensitive)]` - Never read the field from CLI arguments
+//! - `#[gonfig(presence)]` - Read a `bool` as `true` whenever its env var is set
+//! - `#[gonfig(required)]` - Fail with the env var to set when no source provides the field
+//! - `#[gonfig(merge = "append")]` - Concatenate the field's arrays across sources
+//! - `#[gonfig(conflicts_with = "other")]` - Reject the CLI flag combined with another field's flag
+//! - `#[gonfig(try_from = "u16")]` - Validate a newtype through its `TryFrom` impl
+//! - `#[gonfig(parse_with = "parse_duration")]` - Read the field through a custom parser
+//! - `#[gonfig(default_env = "VAR")]` - Default to the value `VAR` has at compile time
+//! - `#[gonfig(example = "value")]` - Example value shown in generated help
+//! - `#[gonfig(value_name = "NAME")]` - Placeholder for the flag's value in generated help
+//! - `#[gonfig(path = "database.url")]` - Insert the env value at a dotted path
+//! - `#[gonfig(flatten)]` - Read a nested struct's fields without a prefix segment
 //! - `#[skip]` or `#[skip_gonfig]` - Skip this field from all configuration sources
 //!
 //! ## Environment Variable Naming
@@ -185,6 +223,19 @@
 //! - **Nested structs**: Each level adds to the path
 //!   - Example: `APP_PARENT_CHILD_FIELD`
 
+/// Hot reloading of configuration files.
+///
+/// Provides the [`watch::WatchedConfig`] type returned by
+/// [`ConfigBuilder::watch_with`]. Requires the `watch` feature.
+#[cfg(feature = "watch")]
+pub mod watch;
+
+/// Configuration source reading an entry from a zip or tar bundle.
+///
+/// Provides the [`archive::ArchiveSource`] type. Requires the `archive` feature.
+#[cfg(feature = "archive")]
+pub mod archive;
+
 /// Configuration builder for assembling multiple configuration sources.
 ///
 /// The builder module provides the [`ConfigBuilder`] type for combining different
@@ -203,6 +254,11 @@ pub mod cli;
 /// and [`ConfigFormat`] enum.
 pub mod config;
 
+/// `.env` file configuration source.
+///
+/// Provides the [`dotenv::DotEnv`] type used by [`ConfigBuilder::with_dotenv`].
+pub mod dotenv;
+
 /// Environment variable configuration source.
 ///
 /// The [`Environment`] type handles reading and parsing environment variables
@@ -215,12 +271,77 @@ pub mod environment;
 /// convenient [`Result`] type alias.
 pub mod error;
 
+/// Configuration source fetching a document from a gRPC config service.
+///
+/// Provides the [`grpc::GrpcSource`] type, the `tonic`-based [`grpc::GrpcClient`]
+/// and the [`grpc::ConfigService`] client abstraction. Requires the `grpc` feature.
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+/// Placeholder substitution in configuration file text.
+///
+/// Provides the [`interpolate::Interpolation`] type used by
+/// [`ConfigBuilder::with_interpolation_env`].
+pub mod interpolate;
+
+/// Introspection of the effective configuration.
+///
+/// Provides [`print_effective_config`] for `config` subcommands and the secret
+/// redaction it relies on.
+pub mod inspect;
+
 /// Configuration merging strategies and utilities.
 ///
 /// Implements different merge strategies like deep merge, replace, and append
-/// through the [`MergeStrategy`] enum and related types.
+/// through the [`MergeStrategy`] enum and related types, and the [`merge`]
+/// function applying one to a list of values.
 pub mod merge;
 
+/// Counters about configuration loading, exposed through the `metrics` crate.
+///
+/// Lists the metric names recorded by [`ConfigBuilder`]. Requires the
+/// `metrics` feature.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// Support for reloading configuration at runtime.
+///
+/// Provides the [`reload::ConfigDiff`] passed to
+/// [`ConfigBuilder::on_reload`] callbacks.
+pub mod reload;
+
+/// Configuration source fetching a JSON document over HTTP, e.g. secrets from
+/// Vault.
+///
+/// Provides the [`remote::HttpJsonSource`] type and the
+/// [`remote::RemoteSource`] transport abstraction. Requires the `remote`
+/// feature.
+#[cfg(feature = "remote")]
+pub mod remote;
+
+/// Static field metadata and help generation.
+///
+/// Provides the [`schema::FieldInfo`] type produced by `#[derive(Gonfig)]` and
+/// the [`schema::render_help`], [`schema::render_env_example`] and
+/// [`schema::render_config_example`] functions used by the generated
+/// `gonfig_help()`, `gonfig_env_example()` and `gonfig_example()`, and
+/// [`schema::field_paths`] listing the fields of any `Deserialize` type.
+pub mod schema;
+
+/// AWS SSM Parameter Store configuration source.
+///
+/// Provides the [`ssm::SsmSource`] type, the [`ssm::ParameterStore`] client
+/// abstraction and its AWS implementation [`ssm::AwsParameterStore`]. Requires
+/// the `aws` feature.
+#[cfg(feature = "aws")]
+pub mod ssm;
+
+/// Transformations of the merged configuration.
+///
+/// Provides helpers such as [`transform::combine_duration`] used by
+/// [`ConfigBuilder::transform_with`] and related builder methods.
+pub mod transform;
+
 /// Core traits and types for configuration sources.
 ///
 /// Defines the [`ConfigSource`] trait that all configuration sources implement
@@ -229,12 +350,18 @@ pub mod source;
 
 pub use gonfig_derive::Gonfig;
 
-pub use builder::ConfigBuilder;
+/// The clap version used by [`Cli::from_clap`] and the `clap::Command`
+/// generated by the derive macro. Requires the `clap` feature.
+#[cfg(feature = "clap")]
+pub use clap;
+
+pub use builder::{from_value, from_value_at, ConfigBuilder};
 pub use cli::Cli;
 pub use config::{Config, ConfigFormat};
 pub use environment::Environment;
 pub use error::{Error, Result};
-pub use merge::MergeStrategy;
+pub use inspect::print_effective_config;
+pub use merge::{merge, MergeStrategy};
 pub use source::{ConfigSource, Source};
 
 /// A configuration prefix used for environment variables
@@ -1,11 +1,85 @@
+use crate::error::{Error, Result};
 use serde_json::Value;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MergeStrategy {
     Replace,
+    /// Merge nested objects recursively, the higher-priority value winning for
+    /// each leaf. The default for [`ConfigBuilder`](crate::ConfigBuilder) and
+    /// the derive macro.
     Deep,
+    /// Replace top-level keys wholesale instead of merging nested objects.
+    ///
+    /// Each key provided by a higher-priority source replaces whatever lower
+    /// sources had under that key, including whole sections; keys it does not
+    /// provide are kept. Unlike [`MergeStrategy::Replace`], one source does not
+    /// discard the others entirely.
+    ///
+    /// # Examples
+    ///
+    /// A file providing `database.host` and `database.port`, overridden by an
+    /// environment providing only `database.host`:
+    ///
+    /// ```rust
+    /// use gonfig::MergeStrategy;
+    /// use serde_json::json;
+    ///
+    /// let file = json!({ "database": { "host": "file-db", "port": 5432 }, "debug": true });
+    /// let env = json!({ "database": { "host": "env-db" } });
+    ///
+    /// assert_eq!(
+    ///     MergeStrategy::Shallow.merge(file.clone(), env.clone()),
+    ///     json!({ "database": { "host": "env-db" }, "debug": true })
+    /// );
+    /// assert_eq!(
+    ///     MergeStrategy::Deep.merge(file, env),
+    ///     json!({ "database": { "host": "env-db", "port": 5432 }, "debug": true })
+    /// );
+    /// ```
+    Shallow,
     Append,
+    /// Deep merge where the first (lowest-priority) value for a key wins.
+    ///
+    /// Higher-priority sources only fill in keys that are still missing, so a
+    /// baked-in configuration file keeps its values and environment variables act
+    /// as fallbacks. Nested objects are merged recursively.
+    DeepPreferExisting,
+    /// Deep merge where arrays from later sources are appended to earlier ones.
+    ///
+    /// Objects are merged recursively exactly like [`MergeStrategy::Deep`] and
+    /// scalars are still replaced by the higher-priority source, but when two
+    /// sources both provide an array at the same path the incoming elements are
+    /// concatenated onto the existing ones. With `dedup`, elements already
+    /// present are not appended again.
+    ///
+    /// An array meeting a non-array value at the same path is a
+    /// [`Error::MergeConflict`] naming the path when merging through
+    /// [`try_merge`](MergeStrategy::try_merge), which the builder uses; the
+    /// infallible [`merge`](MergeStrategy::merge) lets the incoming value win.
+    /// `null` never conflicts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gonfig::MergeStrategy;
+    /// use serde_json::json;
+    ///
+    /// let strategy = MergeStrategy::DeepAppendArrays { dedup: true };
+    /// let merged = strategy.try_merge(
+    ///     json!({ "allowed_hosts": ["a", "b"], "port": 80 }),
+    ///     json!({ "allowed_hosts": ["b", "c"], "port": 443 }),
+    /// )?;
+    /// assert_eq!(merged, json!({ "allowed_hosts": ["a", "b", "c"], "port": 443 }));
+    ///
+    /// assert!(strategy
+    ///     .try_merge(json!({ "allowed_hosts": ["a"] }), json!({ "allowed_hosts": "b" }))
+    ///     .is_err());
+    /// # Ok::<(), gonfig::Error>(())
+    /// ```
+    DeepAppendArrays {
+        dedup: bool,
+    },
 }
 
 impl MergeStrategy {
@@ -13,7 +87,118 @@ impl MergeStrategy {
         match self {
             MergeStrategy::Replace => incoming,
             MergeStrategy::Deep => Self::deep_merge(base, incoming),
+            MergeStrategy::Shallow => Self::shallow_merge(base, incoming),
             MergeStrategy::Append => Self::append_merge(base, incoming),
+            MergeStrategy::DeepPreferExisting => Self::deep_merge_prefer_existing(base, incoming),
+            MergeStrategy::DeepAppendArrays { dedup } => {
+                // Without a path conflicts are resolved, so this cannot fail
+                Self::deep_append_arrays(base, incoming, *dedup, None).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Merge like [`merge`](MergeStrategy::merge), but report structural conflicts.
+    ///
+    /// # Errors
+    ///
+    /// With [`MergeStrategy::DeepAppendArrays`], returns
+    /// [`Error::MergeConflict`] when an array and a non-array value meet at
+    /// the same path. The other strategies never fail.
+    pub fn try_merge(&self, base: Value, incoming: Value) -> Result<Value> {
+        match self {
+            MergeStrategy::DeepAppendArrays { dedup } => {
+                Self::deep_append_arrays(base, incoming, *dedup, Some(""))
+                    .map_err(Error::MergeConflict)
+            }
+            strategy => Ok(strategy.merge(base, incoming)),
+        }
+    }
+
+    /// Conflicts are reported for the dotted `path` when it is given, and
+    /// resolved in favour of the incoming value otherwise.
+    fn deep_append_arrays(
+        base: Value,
+        incoming: Value,
+        dedup: bool,
+        path: Option<&str>,
+    ) -> std::result::Result<Value, String> {
+        match (base, incoming) {
+            (Value::Array(mut base_arr), Value::Array(incoming_arr)) => {
+                for value in incoming_arr {
+                    if !dedup || !base_arr.contains(&value) {
+                        base_arr.push(value);
+                    }
+                }
+                Ok(Value::Array(base_arr))
+            }
+            (Value::Object(mut base_map), Value::Object(incoming_map)) => {
+                for (key, incoming_value) in incoming_map {
+                    let child = path.map(|path| match path {
+                        "" => key.clone(),
+                        path => format!("{path}.{key}"),
+                    });
+                    let merged = match base_map.remove(&key) {
+                        Some(base_value) => Self::deep_append_arrays(
+                            base_value,
+                            incoming_value,
+                            dedup,
+                            child.as_deref(),
+                        )?,
+                        None => incoming_value,
+                    };
+                    base_map.insert(key, merged);
+                }
+                Ok(Value::Object(base_map))
+            }
+            (base, incoming)
+                if path.is_some()
+                    && !base.is_null()
+                    && !incoming.is_null()
+                    && (base.is_array() != incoming.is_array()) =>
+            {
+                let path = match path {
+                    Some("") | None => "<root>",
+                    Some(path) => path,
+                };
+                Err(format!(
+                    "`{path}` is {} in one source and {} in another; arrays can only be appended to arrays",
+                    Self::kind(&base),
+                    Self::kind(&incoming)
+                ))
+            }
+            (_, incoming) => Ok(incoming),
+        }
+    }
+
+    fn kind(value: &Value) -> &'static str {
+        match value {
+            Value::Null => "null",
+            Value::Bool(_) => "a boolean",
+            Value::Number(_) => "a number",
+            Value::String(_) => "a string",
+            Value::Array(_) => "an array",
+            Value::Object(_) => "an object",
+        }
+    }
+
+    fn deep_merge_prefer_existing(base: Value, incoming: Value) -> Value {
+        match (base, incoming) {
+            (Value::Object(mut base_map), Value::Object(incoming_map)) => {
+                for (key, incoming_value) in incoming_map {
+                    match base_map.remove(&key) {
+                        Some(base_value) => {
+                            let merged =
+                                Self::deep_merge_prefer_existing(base_value, incoming_value);
+                            base_map.insert(key, merged);
+                        }
+                        None => {
+                            base_map.insert(key, incoming_value);
+                        }
+                    }
+                }
+                Value::Object(base_map)
+            }
+            (base, _) => base,
         }
     }
 
@@ -39,6 +224,16 @@ impl MergeStrategy {
         }
     }
 
+    fn shallow_merge(base: Value, incoming: Value) -> Value {
+        match (base, incoming) {
+            (Value::Object(mut base_map), Value::Object(incoming_map)) => {
+                base_map.extend(incoming_map);
+                Value::Object(base_map)
+            }
+            (_, incoming) => incoming,
+        }
+    }
+
     fn append_merge(base: Value, incoming: Value) -> Value {
         match (base, incoming) {
             (Value::Array(mut base_arr), Value::Array(incoming_arr)) => {
@@ -65,6 +260,134 @@ impl MergeStrategy {
     }
 }
 
+/// Merge `values` with `strategy`, later values taking precedence.
+///
+/// Values are folded onto an empty object in order, which is what
+/// [`ConfigBuilder`](crate::ConfigBuilder) does with its sources sorted by
+/// priority. No values give an empty object.
+/// Conflicts that [`MergeStrategy::try_merge`] would report are resolved in
+/// favour of the later value.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::MergeStrategy;
+/// use serde_json::json;
+///
+/// let file = json!({ "database": { "host": "localhost", "port": 5432 } });
+/// let env = json!({ "database": { "port": 6543 } });
+///
+/// assert_eq!(
+///     gonfig::merge(&[file, env], MergeStrategy::Deep),
+///     json!({ "database": { "host": "localhost", "port": 6543 } })
+/// );
+/// ```
+pub fn merge(values: &[Value], strategy: MergeStrategy) -> Value {
+    merge_owned(values.iter().cloned(), strategy)
+}
+
+/// [`merge`] for owned values, moved into the result rather than cloned.
+fn merge_owned(values: impl IntoIterator<Item = Value>, strategy: MergeStrategy) -> Value {
+    let layers = values.into_iter().map(|value| (value, strategy));
+    // Resolving conflicts instead of reporting them cannot fail
+    merge_layers(layers, &[], false, None).unwrap_or_default()
+}
+
+/// Observer of the merges [`merge_layers`] performs.
+pub(crate) trait MergeTrace {
+    /// Layer number `layer` is about to merge `incoming` with `strategy` onto
+    /// `base`, the value already at the dotted `path` (empty for the root).
+    fn write(
+        &mut self,
+        layer: usize,
+        strategy: MergeStrategy,
+        base: Option<&Value>,
+        incoming: &Value,
+        path: &str,
+    );
+
+    /// `path` is about to be merged again on its own, replacing what the
+    /// layers wrote there.
+    fn reset(&mut self, path: &str);
+}
+
+/// Merge `layers` in order, each onto the ones before it with its own strategy.
+///
+/// Every path of `path_strategies` is then merged separately across the layers
+/// with its strategy, replacing that path in the result. With `strict`,
+/// conflicts [`MergeStrategy::try_merge`] reports fail the merge; otherwise the
+/// later value wins. Every merge is reported to `trace` before it happens.
+pub(crate) fn merge_layers<I>(
+    layers: I,
+    path_strategies: &[(String, MergeStrategy)],
+    strict: bool,
+    mut trace: Option<&mut dyn MergeTrace>,
+) -> Result<Value>
+where
+    I: IntoIterator<Item = (Value, MergeStrategy)>,
+{
+    let layers: Vec<(Value, MergeStrategy)> = layers.into_iter().collect();
+
+    // The layers are consumed by the merge, so copy what paths with their own
+    // strategy need first
+    let path_layers: Vec<Vec<(usize, Value)>> = path_strategies
+        .iter()
+        .map(|(path, _)| {
+            layers
+                .iter()
+                .enumerate()
+                .filter_map(|(layer, (value, _))| {
+                    path.split('.')
+                        .try_fold(value, |current, part| current.get(part))
+                        .map(|incoming| (layer, incoming.clone()))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut merged = Value::Object(serde_json::Map::new());
+    for (layer, (value, strategy)) in layers.into_iter().enumerate() {
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.write(layer, strategy, Some(&merged), &value, "");
+        }
+        merged = merge_onto(strategy, merged, value, strict)?;
+    }
+
+    // Paths with their own strategy are merged separately, in layer order
+    for ((path, strategy), path_layers) in path_strategies.iter().zip(path_layers) {
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.reset(path);
+        }
+        let mut path_value: Option<Value> = None;
+        for (layer, incoming) in path_layers {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.write(layer, *strategy, path_value.as_ref(), &incoming, path);
+            }
+            path_value = Some(match path_value {
+                Some(base) => merge_onto(*strategy, base, incoming, strict)?,
+                None => incoming,
+            });
+        }
+        if let Some(value) = path_value {
+            crate::transform::insert_path(&mut merged, path, value);
+        }
+    }
+    Ok(merged)
+}
+
+fn merge_onto(
+    strategy: MergeStrategy,
+    base: Value,
+    incoming: Value,
+    strict: bool,
+) -> Result<Value> {
+    if strict {
+        strategy.try_merge(base, incoming)
+    } else {
+        Ok(strategy.merge(base, incoming))
+    }
+}
+
 pub struct ConfigMerger {
     strategy: MergeStrategy,
 }
@@ -78,13 +401,25 @@ impl ConfigMerger {
         let mut sorted_sources = sources;
         sorted_sources.sort_by_key(|(_, priority)| *priority);
 
-        let mut result = Value::Object(serde_json::Map::new());
+        merge_owned(
+            sorted_sources.into_iter().map(|(value, _)| value),
+            self.strategy,
+        )
+    }
 
-        for (value, _) in sorted_sources {
-            result = self.strategy.merge(result, value);
-        }
+    /// Merge `sources` in priority order, failing on structural conflicts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`MergeStrategy::try_merge`].
+    pub fn try_merge_sources(&self, sources: Vec<(Value, u8)>) -> Result<Value> {
+        let mut sorted_sources = sources;
+        sorted_sources.sort_by_key(|(_, priority)| *priority);
 
-        result
+        sorted_sources.into_iter().try_fold(
+            Value::Object(serde_json::Map::new()),
+            |result, (value, _)| self.strategy.try_merge(result, value),
+        )
     }
 
     pub fn merge_with_precedence(&self, sources: HashMap<String, (Value, u8)>) -> Value {
@@ -92,12 +427,6 @@ impl ConfigMerger {
 
         values.sort_by_key(|(_, priority)| *priority);
 
-        let mut result = Value::Object(serde_json::Map::new());
-
-        for (value, _) in values {
-            result = self.strategy.merge(result, value);
-        }
-
-        result
+        merge_owned(values.into_iter().map(|(value, _)| value), self.strategy)
     }
 }
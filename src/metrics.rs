@@ -0,0 +1,48 @@
+//! Counters describing configuration loading, recorded through the `metrics` facade.
+//!
+//! Every build of a [`ConfigBuilder`](crate::ConfigBuilder) records the counters
+//! below with whatever recorder the application installed. Counters labelled by
+//! `source` use the [`Source`] variant name, e.g. `Environment`.
+
+use crate::source::Source;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Sources collected successfully, labelled by `source`.
+pub const SOURCES_LOADED: &str = "gonfig_sources_loaded_total";
+
+/// Leaf keys supplied by sources, labelled by `source`.
+pub const KEYS_COLLECTED: &str = "gonfig_keys_collected_total";
+
+/// Keys supplied by more than one source, where a higher priority source
+/// overrode a lower one.
+pub const OVERRIDES_APPLIED: &str = "gonfig_overrides_applied_total";
+
+/// Builds rejected by required keys or the validation function.
+pub const VALIDATION_FAILURES: &str = "gonfig_validation_failures_total";
+
+/// Record the sources and keys of one collection.
+pub(crate) fn record_collected(collected: &[(Value, Source)]) {
+    let mut suppliers: HashMap<String, u64> = HashMap::new();
+
+    for (value, source) in collected {
+        let mut paths = Vec::new();
+        crate::builder::leaf_paths(value, "", &mut paths);
+
+        let label = format!("{source:?}");
+        ::metrics::counter!(SOURCES_LOADED, "source" => label.clone()).increment(1);
+        ::metrics::counter!(KEYS_COLLECTED, "source" => label).increment(paths.len() as u64);
+
+        for path in paths {
+            *suppliers.entry(path).or_default() += 1;
+        }
+    }
+
+    let overrides: u64 = suppliers.values().map(|count| count - 1).sum();
+    ::metrics::counter!(OVERRIDES_APPLIED).increment(overrides);
+}
+
+/// Record a build rejected during validation.
+pub(crate) fn record_validation_failure() {
+    ::metrics::counter!(VALIDATION_FAILURES).increment(1);
+}
@@ -0,0 +1,90 @@
+//! Change tracking between two merged configurations.
+
+use crate::builder::leaf_paths;
+use serde_json::Value;
+
+/// Dotted paths that differ between two merged configurations.
+///
+/// Passed to [`ConfigBuilder::on_reload`](crate::ConfigBuilder::on_reload)
+/// callbacks so they can react only to the settings they care about. Paths
+/// name leaf values; arrays are compared as a whole.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::reload::ConfigDiff;
+/// use serde_json::json;
+///
+/// let diff = ConfigDiff::between(
+///     &json!({ "port": 8080, "log": { "level": "info" }, "debug": false }),
+///     &json!({ "port": 9090, "log": { "level": "info" }, "workers": 4 }),
+/// );
+/// assert_eq!(diff.changed, vec!["port"]);
+/// assert_eq!(diff.added, vec!["workers"]);
+/// assert_eq!(diff.removed, vec!["debug"]);
+/// assert!(diff.contains("port"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// Paths present in both configurations with different values.
+    pub changed: Vec<String>,
+    /// Paths only present in the new configuration.
+    pub added: Vec<String>,
+    /// Paths only present in the old configuration.
+    pub removed: Vec<String>,
+}
+
+impl ConfigDiff {
+    /// Compare `old` with `new`.
+    pub fn between(old: &Value, new: &Value) -> Self {
+        let (mut old_paths, mut new_paths) = (Vec::new(), Vec::new());
+        leaf_paths(old, "", &mut old_paths);
+        leaf_paths(new, "", &mut new_paths);
+
+        let lookup = |value: &Value, path: &str| {
+            path.split('.')
+                .try_fold(value, |current, part| current.get(part))
+                .cloned()
+        };
+
+        let mut diff = Self::default();
+        for path in &new_paths {
+            match lookup(old, path) {
+                Some(previous) if previous.is_object() => diff.added.push(path.clone()),
+                Some(previous) if Some(&previous) != lookup(new, path).as_ref() => {
+                    diff.changed.push(path.clone())
+                }
+                Some(_) => {}
+                None => diff.added.push(path.clone()),
+            }
+        }
+        diff.removed = old_paths
+            .into_iter()
+            .filter(|path| {
+                lookup(new, path)
+                    .filter(|value| !value.is_object())
+                    .is_none()
+            })
+            .collect();
+
+        diff.changed.sort();
+        diff.added.sort();
+        diff.removed.sort();
+        diff
+    }
+
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Whether `path`, or anything below it, was changed, added or removed.
+    pub fn contains(&self, path: &str) -> bool {
+        let prefix = format!("{path}.");
+        self.changed
+            .iter()
+            .chain(&self.added)
+            .chain(&self.removed)
+            .any(|changed| changed == path || changed.starts_with(&prefix))
+    }
+}
@@ -0,0 +1,302 @@
+//! Configuration source fetching a JSON document over HTTP or HTTPS.
+
+use crate::{
+    error::{Error, Result},
+    source::{ConfigSource, Source},
+};
+use serde_json::Value;
+use std::any::Any;
+use std::fmt;
+use std::io::Read;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// Deadline of a [`RemoteRequest`] unless set otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest response body a [`RemoteRequest`] accepts unless set otherwise.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Request for a remote configuration document.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RemoteRequest {
+    /// URL of the document.
+    pub url: String,
+    /// Token sent as `Authorization: Bearer <token>`.
+    pub bearer_token: Option<String>,
+    /// Deadline for the whole request, from resolving the host to reading the
+    /// last byte of the body.
+    pub timeout: Duration,
+    /// Largest response body to accept, in bytes.
+    pub max_body_size: usize,
+}
+
+impl RemoteRequest {
+    /// Create a request for `url` without a token, with the default deadline
+    /// and body size limit.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            bearer_token: None,
+            timeout: DEFAULT_TIMEOUT,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+// Keeps the token out of logs and panic messages
+impl fmt::Debug for RemoteRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteRequest")
+            .field("url", &self.url)
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_ref().map(|_| crate::inspect::REDACTED),
+            )
+            .field("timeout", &self.timeout)
+            .field("max_body_size", &self.max_body_size)
+            .finish()
+    }
+}
+
+/// Transport fetching remote configuration documents.
+///
+/// [`HttpClient`] implements it over `http://` and `https://`. Implement it
+/// for another client, for example one with custom certificates, or for a
+/// mock in tests. Implementations should honour the request's deadline and
+/// body size limit, and report failures as [`Error::Source`].
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::remote::{RemoteRequest, RemoteSource};
+///
+/// struct Fixed;
+///
+/// impl RemoteSource for Fixed {
+///     fn fetch(&self, _request: &RemoteRequest) -> gonfig::Result<Vec<u8>> {
+///         Ok(br#"{"port": 8080}"#.to_vec())
+///     }
+/// }
+/// ```
+pub trait RemoteSource: Send + Sync {
+    /// Fetch the raw document for `request`.
+    fn fetch(&self, request: &RemoteRequest) -> Result<Vec<u8>>;
+}
+
+/// HTTP client for `http://` and `https://` URLs, built on `ureq` with rustls.
+///
+/// Sends a `GET` request and accepts any `2xx` response. The request's
+/// [`timeout`](RemoteRequest::timeout) bounds the whole exchange and a body
+/// larger than [`max_body_size`](RemoteRequest::max_body_size) is rejected.
+/// A bearer token is only sent over `https://`, or over `http://` to a
+/// loopback address, so it never crosses the network in clear text.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpClient;
+
+impl RemoteSource for HttpClient {
+    fn fetch(&self, request: &RemoteRequest) -> Result<Vec<u8>> {
+        let fail = |message: String| Error::Source {
+            source_name: request.url.clone(),
+            message,
+        };
+
+        let mut call = ureq::get(&request.url)
+            .timeout(request.timeout)
+            .set("Accept", "application/json");
+        if let Some(token) = &request.bearer_token {
+            let url = call.request_url().map_err(|e| fail(e.to_string()))?;
+            if url.scheme() != "https" && !is_loopback(url.host()) {
+                return Err(fail(
+                    "refusing to send a bearer token over plain http:// to a remote host; \
+                     use https://"
+                        .to_string(),
+                ));
+            }
+            call = call.set("Authorization", &format!("Bearer {token}"));
+        }
+
+        let response = match call.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(fail(
+                    format!("HTTP status {status} {}", response.status_text())
+                        .trim_end()
+                        .to_string(),
+                ));
+            }
+            Err(e) => return Err(fail(e.to_string())),
+        };
+
+        // One byte past the limit tells an oversized body from one that fits
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(request.max_body_size as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| fail(e.to_string()))?;
+        if body.len() > request.max_body_size {
+            return Err(fail(format!(
+                "response body exceeds {} bytes",
+                request.max_body_size
+            )));
+        }
+        Ok(body)
+    }
+}
+
+/// Whether `host` names this machine.
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Configuration source reading a JSON object from an HTTP endpoint.
+///
+/// Meant for secrets served by Vault's KV engine or a similar service, so
+/// they stay out of environment variables and files. The document is
+/// fetched on every collection, optionally narrowed to the object at a
+/// dotted [`path`](HttpJsonSource::with_path), and merged like any other
+/// source. Any failure, including a non-`2xx` status, is an
+/// [`Error::Source`].
+///
+/// The source reports [`Source::Remote`], placing it above environment
+/// variables and below CLI arguments, unless
+/// [`at_priority`](HttpJsonSource::at_priority) says otherwise. It is skipped
+/// on failure when the builder is [`resilient`](crate::ConfigBuilder::resilient).
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::remote::{HttpJsonSource, RemoteRequest, RemoteSource};
+/// use gonfig::ConfigSource;
+///
+/// struct Vault;
+///
+/// impl RemoteSource for Vault {
+///     fn fetch(&self, request: &RemoteRequest) -> gonfig::Result<Vec<u8>> {
+///         assert_eq!(request.bearer_token.as_deref(), Some("s.token"));
+///         Ok(br#"{"data": {"data": {"db_password": "hunter2"}}}"#.to_vec())
+///     }
+/// }
+///
+/// let source = HttpJsonSource::with_client(Vault, "https://vault:8200/v1/secret/data/app")
+///     .with_bearer_token("s.token")
+///     .with_path("data.data");
+/// assert_eq!(source.collect()?["db_password"], "hunter2");
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub struct HttpJsonSource {
+    client: Box<dyn RemoteSource>,
+    request: RemoteRequest,
+    path: Option<String>,
+    source_type: Source,
+}
+
+impl HttpJsonSource {
+    /// Fetch `url` with the built-in [`HttpClient`].
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(HttpClient, url)
+    }
+
+    /// Fetch `url` through `client`, e.g. one trusting a private certificate
+    /// authority.
+    pub fn with_client(client: impl RemoteSource + 'static, url: impl Into<String>) -> Self {
+        Self {
+            client: Box::new(client),
+            request: RemoteRequest::new(url),
+            path: None,
+            source_type: Source::Remote,
+        }
+    }
+
+    /// Send `token` as `Authorization: Bearer <token>`.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.request.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Give up on the whole request after `timeout` (30 seconds by default).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.request.timeout = timeout;
+        self
+    }
+
+    /// Reject response bodies larger than `bytes` (1 MiB by default).
+    pub fn with_max_body_size(mut self, bytes: usize) -> Self {
+        self.request.max_body_size = bytes;
+        self
+    }
+
+    /// Only merge the object at the dotted `path` of the response, such as
+    /// `data.data` for Vault's KV version 2 engine.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Merge at the priority of `source_type` instead of [`Source::Remote`],
+    /// e.g. [`Source::Default`] for values every other source may override.
+    pub fn at_priority(mut self, source_type: Source) -> Self {
+        self.source_type = source_type;
+        self
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::Source {
+            source_name: self.describe(),
+            message,
+        }
+    }
+}
+
+impl ConfigSource for HttpJsonSource {
+    fn source_type(&self) -> Source {
+        self.source_type
+    }
+
+    fn collect(&self) -> Result<Value> {
+        let body = self.client.fetch(&self.request)?;
+        let document: Value = serde_json::from_slice(&body)
+            .map_err(|e| self.error(format!("invalid JSON response: {e}")))?;
+
+        let value = match &self.path {
+            Some(path) => path
+                .split('.')
+                .try_fold(&document, |current, part| current.get(part))
+                .cloned()
+                .ok_or_else(|| self.error(format!("no value at `{path}` in the response")))?,
+            None => document,
+        };
+        if !value.is_object() {
+            return Err(self.error("expected a JSON object".to_string()));
+        }
+        Ok(value)
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        let data = self.collect().ok()?;
+        key.split('.')
+            .try_fold(&data, |current, part| current.get(part))
+            .cloned()
+    }
+
+    fn describe(&self) -> String {
+        match &self.path {
+            Some(path) => format!("remote {} ({path})", self.request.url),
+            None => format!("remote {}", self.request.url),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,715 @@
+//! Static field metadata generated by `#[derive(Gonfig)]`.
+
+use crate::config::ConfigFormat;
+use serde_json::{json, Map, Value};
+
+/// Description of a single configuration field.
+///
+/// Instances are produced by the derive macro through the generated
+/// `gonfig_fields()` method and consumed by the help and sample generators.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::schema::FieldInfo;
+///
+/// let field = FieldInfo::new("port", "APP_PORT", "port")
+///     .with_default("8080")
+///     .with_example("9000");
+/// assert_eq!(field.example.as_deref(), Some("9000"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// Rust field name, also used as the key in the merged configuration.
+    pub name: String,
+    /// Environment variable the field is read from.
+    pub env_key: String,
+    /// CLI flag name, without the leading `--`; `None` for sensitive fields
+    /// that cannot be set on the command line.
+    pub cli_key: Option<String>,
+    /// Default value from `#[gonfig(default = "...")]`.
+    pub default: Option<String>,
+    /// Illustrative value from `#[gonfig(example = "...")]`.
+    pub example: Option<String>,
+    /// Field doc comment, joined into a single line.
+    pub doc: Option<String>,
+    /// Placeholder for the flag's value in help, from
+    /// `#[gonfig(value_name = "...")]`; see [`value_name`](FieldInfo::value_name)
+    /// when `None`.
+    pub value_name: Option<String>,
+    /// Short flag from `#[gonfig(cli_short = 'p')]`, without the leading `-`.
+    pub cli_short: Option<char>,
+}
+
+impl FieldInfo {
+    /// Create field metadata with the given name, env var and CLI flag.
+    pub fn new(
+        name: impl Into<String>,
+        env_key: impl Into<String>,
+        cli_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            env_key: env_key.into(),
+            cli_key: Some(cli_key.into()),
+            default: None,
+            example: None,
+            doc: None,
+            value_name: None,
+            cli_short: None,
+        }
+    }
+
+    /// Mark the field as not settable from the command line.
+    pub fn without_cli(mut self) -> Self {
+        self.cli_key = None;
+        self.cli_short = None;
+        self
+    }
+
+    /// Set the short flag, e.g. `'p'` for `-p`.
+    pub fn with_cli_short(mut self, short: char) -> Self {
+        self.cli_short = Some(short);
+        self
+    }
+
+    /// Set the default value.
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Set the example value.
+    pub fn with_example(mut self, example: impl Into<String>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+
+    /// Set the doc comment.
+    pub fn with_doc(mut self, doc: impl Into<String>) -> Self {
+        self.doc = Some(doc.into());
+        self
+    }
+
+    /// Set the placeholder shown for the flag's value in help.
+    pub fn with_value_name(mut self, value_name: impl Into<String>) -> Self {
+        self.value_name = Some(value_name.into());
+        self
+    }
+
+    /// Placeholder shown for the flag's value in help, defaulting to the
+    /// uppercased last segment of the field name.
+    pub fn value_name(&self) -> String {
+        self.value_name.clone().unwrap_or_else(|| {
+            let name = self.name.rsplit('.').next().unwrap_or(&self.name);
+            name.to_uppercase()
+        })
+    }
+}
+
+/// Render `--help` style text for a set of fields.
+///
+/// Each field is listed with its CLI flag and value name, doc comment,
+/// environment variable, default and example value. Fields without a CLI flag
+/// are listed by their environment variable only.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::schema::{render_help, FieldInfo};
+///
+/// let help = render_help(&[
+///     FieldInfo::new("port", "APP_PORT", "port").with_example("9000"),
+///     FieldInfo::new("host", "APP_HOST", "host").with_cli_short('H'),
+/// ]);
+/// assert!(help.contains("--port <PORT>"));
+/// assert!(help.contains("[example: 9000]"));
+/// assert!(help.contains("-H, --host <HOST>"));
+/// ```
+pub fn render_help(fields: &[FieldInfo]) -> String {
+    let flags: Vec<String> = fields
+        .iter()
+        .map(|f| match (&f.cli_key, f.cli_short) {
+            (Some(cli_key), Some(short)) => {
+                format!("-{short}, --{cli_key} <{}>", f.value_name())
+            }
+            (Some(cli_key), None) => format!("--{cli_key} <{}>", f.value_name()),
+            (None, _) => format!("({}, env only)", f.name),
+        })
+        .collect();
+    let width = flags.iter().map(|f| f.len()).max().unwrap_or(0);
+
+    let mut out = String::from("Options:\n");
+    for (field, flag) in fields.iter().zip(flags) {
+        let mut line = format!("  {flag:<width$}");
+        if let Some(doc) = &field.doc {
+            line.push_str("  ");
+            line.push_str(doc);
+        }
+        line.push_str(&format!("  [env: {}]", field.env_key));
+        if let Some(default) = &field.default {
+            line.push_str(&format!(" [default: {default}]"));
+        }
+        if let Some(example) = &field.example {
+            line.push_str(&format!(" [example: {example}]"));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a `.env.example` file for a set of fields.
+///
+/// Each field becomes a `KEY=value` line preceded by its doc comment as a
+/// `#` comment. The value is the default, or the example when there is no
+/// default, and is left empty otherwise. Values containing whitespace, `#` or
+/// quotes are double-quoted.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::schema::{render_env_example, FieldInfo};
+///
+/// let text = render_env_example(&[
+///     FieldInfo::new("port", "APP_PORT", "port")
+///         .with_default("8080")
+///         .with_doc("Port to listen on"),
+///     FieldInfo::new("token", "APP_TOKEN", "token"),
+/// ]);
+/// assert_eq!(text, "# Port to listen on\nAPP_PORT=8080\n\nAPP_TOKEN=\n");
+/// ```
+pub fn render_env_example(fields: &[FieldInfo]) -> String {
+    let entries: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let mut entry = String::new();
+            if let Some(doc) = &field.doc {
+                entry.push_str(&format!("# {doc}\n"));
+            }
+            let value = field
+                .default
+                .as_deref()
+                .or(field.example.as_deref())
+                .unwrap_or_default();
+            entry.push_str(&format!("{}={}\n", field.env_key, quote_env_value(value)));
+            entry
+        })
+        .collect();
+    entries.join("\n")
+}
+
+/// Render a template configuration file for a set of fields.
+///
+/// Field names may be dotted paths such as `database.url`; each segment
+/// before the last becomes a nested section. Every field is written with its
+/// default, or its example when there is no default, or a `<VALUE_NAME>`
+/// placeholder otherwise. In TOML, YAML and INI output each field is preceded
+/// by its doc comment and a comment naming its environment variable; JSON
+/// cannot hold comments, so it only lists the values.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::schema::{render_config_example, FieldInfo};
+/// use gonfig::ConfigFormat;
+///
+/// let text = render_config_example(
+///     &[
+///         FieldInfo::new("port", "APP_PORT", "port").with_default("8080"),
+///         FieldInfo::new("database.url", "APP_DATABASE_URL", "database-url"),
+///     ],
+///     ConfigFormat::Toml,
+/// );
+/// assert_eq!(
+///     text,
+///     "# env: APP_PORT\nport = 8080\n\n[database]\n# env: APP_DATABASE_URL\nurl = \"<URL>\"\n"
+/// );
+/// ```
+pub fn render_config_example(fields: &[FieldInfo], format: ConfigFormat) -> String {
+    if format == ConfigFormat::Json {
+        let mut value = Value::Object(Map::new());
+        for field in fields {
+            crate::transform::insert_path(&mut value, &field.name, example_value(field));
+        }
+        let mut out = serde_json::to_string_pretty(&value).unwrap_or_default();
+        out.push('\n');
+        return out;
+    }
+
+    let mut root = ExampleSection::default();
+    for field in fields {
+        root.insert(&field.name.split('.').collect::<Vec<_>>(), field);
+    }
+
+    let mut out = String::new();
+    match format {
+        ConfigFormat::Yaml => root.write_nested(&format, 0, &mut out),
+        _ => root.write_tables(&format, "", &mut out),
+    }
+    out
+}
+
+/// Fields and subsections of one level of a configuration example, in
+/// declaration order.
+#[derive(Default)]
+struct ExampleSection<'a> {
+    fields: Vec<(&'a str, &'a FieldInfo)>,
+    sections: Vec<(&'a str, ExampleSection<'a>)>,
+}
+
+impl<'a> ExampleSection<'a> {
+    fn insert(&mut self, path: &[&'a str], field: &'a FieldInfo) {
+        match path {
+            [] => {}
+            [key] => self.fields.push((key, field)),
+            [section, rest @ ..] => {
+                let index = match self.sections.iter().position(|(name, _)| name == section) {
+                    Some(index) => index,
+                    None => {
+                        self.sections.push((section, ExampleSection::default()));
+                        self.sections.len() - 1
+                    }
+                };
+                self.sections[index].1.insert(rest, field);
+            }
+        }
+    }
+
+    /// Write fields under `[dotted.section]` headers (TOML and INI).
+    fn write_tables(&self, format: &ConfigFormat, name: &str, out: &mut String) {
+        if !name.is_empty() && !self.fields.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{name}]\n"));
+        }
+        for (key, field) in &self.fields {
+            write_example_field(format, key, field, "", out);
+        }
+        for (key, section) in &self.sections {
+            let child = if name.is_empty() {
+                key.to_string()
+            } else {
+                format!("{name}.{key}")
+            };
+            section.write_tables(format, &child, out);
+        }
+    }
+
+    /// Write fields as indented mappings (YAML).
+    fn write_nested(&self, format: &ConfigFormat, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        for (key, field) in &self.fields {
+            write_example_field(format, key, field, &indent, out);
+        }
+        for (key, section) in &self.sections {
+            out.push_str(&format!("{indent}{key}:\n"));
+            section.write_nested(format, depth + 1, out);
+        }
+    }
+}
+
+/// Write a field's comments and `key = value` line(s), indented by `indent`.
+fn write_example_field(
+    format: &ConfigFormat,
+    key: &str,
+    field: &FieldInfo,
+    indent: &str,
+    out: &mut String,
+) {
+    if let Some(doc) = &field.doc {
+        out.push_str(&format!("{indent}# {doc}\n"));
+    }
+    out.push_str(&format!("{indent}# env: {}\n", field.env_key));
+
+    let value = example_value(field);
+    let rendered = format
+        .serialize(&json!({ key: value }))
+        .unwrap_or_else(|_| format!("{key} = {value}\n"));
+    for line in rendered.lines() {
+        out.push_str(&format!("{indent}{line}\n"));
+    }
+}
+
+/// Default or example value of a field, parsed like attribute defaults, or a
+/// placeholder naming the value.
+fn example_value(field: &FieldInfo) -> Value {
+    field
+        .default
+        .iter()
+        .chain(&field.example)
+        .map(|text| {
+            text.parse::<Value>()
+                .unwrap_or_else(|_| Value::String(text.clone()))
+        })
+        .find(|value| !value.is_null())
+        .unwrap_or_else(|| Value::String(format!("<{}>", field.value_name())))
+}
+
+/// Double-quote `value` when a dotenv parser would otherwise misread it.
+fn quote_env_value(value: &str) -> String {
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\''))
+    {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Dotted paths of the leaf fields of `T`, e.g. `http.port`.
+///
+/// Found by walking `T`'s `Deserialize` implementation without any input, so
+/// paths use serde's names (after `rename` and `rename_all`) and nested
+/// structs are descended into, including through `Option`. Maps, sequences,
+/// enums and durations are leaves. Fields of `#[serde(flatten)]` structs are
+/// not seen. A type rejecting placeholder values, such as `NonZeroU16` or a
+/// validated newtype, ends the walk with a warning; every field declared by
+/// the structs entered so far is still listed, those not reached as leaves.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Http {
+///     port: u16,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     http: Http,
+///     #[serde(rename = "log-level")]
+///     log_level: Option<String>,
+/// }
+///
+/// let paths = gonfig::schema::field_paths::<Config>();
+/// assert_eq!(paths, ["http.port", "log-level"]);
+/// ```
+pub fn field_paths<T: serde::de::DeserializeOwned>() -> Vec<String> {
+    let mut paths = Vec::new();
+    let walked = T::deserialize(Probe {
+        path: String::new(),
+        paths: &mut paths,
+    });
+    if let Err(e) = walked {
+        let at = e.path.as_deref().unwrap_or("the root");
+        tracing::warn!(
+            "Could not walk the fields of {} past `{}`: {}; fields not reached are treated as leaves",
+            std::any::type_name::<T>(),
+            at,
+            e.message
+        );
+    }
+    paths
+}
+
+/// Error ending a [`field_paths`] walk.
+#[derive(Debug)]
+struct ProbeError {
+    message: String,
+    /// Path of the innermost field whose value failed.
+    path: Option<String>,
+}
+
+impl std::fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+impl serde::de::Error for ProbeError {
+    fn custom<M: std::fmt::Display>(msg: M) -> Self {
+        Self {
+            message: msg.to_string(),
+            path: None,
+        }
+    }
+}
+
+/// Deserializer answering with placeholder values, recording the path of
+/// every leaf it is asked for.
+///
+/// A struct replaces its own entry in `paths` with one per declared field
+/// before any of them is visited, so a field whose type fails the walk does
+/// not hide its siblings; fields left unvisited stay listed as leaves.
+struct Probe<'a> {
+    path: String,
+    paths: &'a mut Vec<String>,
+}
+
+impl Probe<'_> {
+    fn leaf(self) {
+        if !self.path.is_empty() && !self.paths.contains(&self.path) {
+            self.paths.push(self.path);
+        }
+    }
+
+    /// Replace this struct's entry with the paths of its `fields`.
+    fn declare(&mut self, fields: &[&str]) {
+        let children = fields.iter().map(|field| {
+            if self.path.is_empty() {
+                field.to_string()
+            } else {
+                format!("{}.{field}", self.path)
+            }
+        });
+        match self.paths.iter().position(|path| *path == self.path) {
+            Some(index) => {
+                self.paths.splice(index..=index, children);
+            }
+            None => self.paths.extend(children),
+        }
+    }
+}
+
+macro_rules! probe_leaf {
+    ($($method:ident => $visit:ident($($value:expr)?)),* $(,)?) => {
+        $(
+            fn $method<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+                self.leaf();
+                visitor.$visit($($value)?)
+            }
+        )*
+    };
+}
+
+impl<'de> serde::Deserializer<'de> for Probe<'_> {
+    type Error = ProbeError;
+
+    probe_leaf! {
+        deserialize_any => visit_unit(),
+        deserialize_bool => visit_bool(false),
+        deserialize_i8 => visit_i64(0),
+        deserialize_i16 => visit_i64(0),
+        deserialize_i32 => visit_i64(0),
+        deserialize_i64 => visit_i64(0),
+        deserialize_i128 => visit_i64(0),
+        deserialize_u8 => visit_u64(0),
+        deserialize_u16 => visit_u64(0),
+        deserialize_u32 => visit_u64(0),
+        deserialize_u64 => visit_u64(0),
+        deserialize_u128 => visit_u64(0),
+        deserialize_f32 => visit_f64(0.0),
+        deserialize_f64 => visit_f64(0.0),
+        deserialize_char => visit_char(' '),
+        deserialize_str => visit_str(""),
+        deserialize_string => visit_str(""),
+        deserialize_bytes => visit_bytes(&[]),
+        deserialize_byte_buf => visit_bytes(&[]),
+        deserialize_unit => visit_unit(),
+        deserialize_seq => visit_seq(Empty),
+        deserialize_map => visit_map(Empty),
+        deserialize_identifier => visit_str(""),
+        deserialize_ignored_any => visit_unit(),
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.leaf();
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V: serde::de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.leaf();
+        visitor.visit_seq(Empty)
+    }
+
+    fn deserialize_tuple_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.leaf();
+        visitor.visit_seq(Empty)
+    }
+
+    fn deserialize_struct<V: serde::de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        // `std::time::Duration` is a struct to serde but a single setting here
+        if name == "Duration" {
+            self.leaf();
+            let parts = [0_u64, 0].into_iter();
+            return visitor.visit_seq(serde::de::value::SeqDeserializer::new(parts));
+        }
+        let mut probe = self;
+        probe.declare(fields);
+        visitor.visit_map(Fields {
+            path: probe.path,
+            fields: fields.iter(),
+            current: "",
+            paths: probe.paths,
+        })
+    }
+
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.leaf();
+        match variants.first() {
+            Some(variant) => visitor.visit_enum(Variant(variant)),
+            None => Err(serde::de::Error::custom("enum without variants")),
+        }
+    }
+}
+
+/// Map offering every field of a struct, each answered by a nested [`Probe`].
+struct Fields<'a> {
+    path: String,
+    fields: std::slice::Iter<'static, &'static str>,
+    current: &'static str,
+    paths: &'a mut Vec<String>,
+}
+
+impl<'de> serde::de::MapAccess<'de> for Fields<'_> {
+    type Error = ProbeError;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ProbeError> {
+        let Some(field) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.current = field;
+        seed.deserialize(serde::de::value::StrDeserializer::new(field))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ProbeError> {
+        let path = if self.path.is_empty() {
+            self.current.to_string()
+        } else {
+            format!("{}.{}", self.path, self.current)
+        };
+        seed.deserialize(Probe {
+            path: path.clone(),
+            paths: &mut *self.paths,
+        })
+        .map_err(|mut e| {
+            e.path.get_or_insert(path);
+            e
+        })
+    }
+}
+
+/// First variant of an enum, with placeholder content.
+struct Variant(&'static str);
+
+impl<'de> serde::de::EnumAccess<'de> for Variant {
+    type Error = ProbeError;
+    type Variant = Self;
+
+    fn variant_seed<V: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), ProbeError> {
+        let variant = seed.deserialize(serde::de::value::StrDeserializer::new(self.0))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for Variant {
+    type Error = ProbeError;
+
+    fn unit_variant(self) -> Result<(), ProbeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, ProbeError> {
+        // The variant's content is part of the enum's leaf
+        seed.deserialize(Probe {
+            path: String::new(),
+            paths: &mut Vec::new(),
+        })
+    }
+
+    fn tuple_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_seq(Empty)
+    }
+
+    fn struct_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_map(Empty)
+    }
+}
+
+/// Empty sequence or map.
+struct Empty;
+
+impl<'de> serde::de::SeqAccess<'de> for Empty {
+    type Error = ProbeError;
+
+    fn next_element_seed<T: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        _seed: T,
+    ) -> Result<Option<T::Value>, ProbeError> {
+        Ok(None)
+    }
+}
+
+impl<'de> serde::de::MapAccess<'de> for Empty {
+    type Error = ProbeError;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        _seed: K,
+    ) -> Result<Option<K::Value>, ProbeError> {
+        Ok(None)
+    }
+
+    fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(
+        &mut self,
+        _seed: V,
+    ) -> Result<V::Value, ProbeError> {
+        Err(serde::de::Error::custom("empty map has no values"))
+    }
+}
@@ -7,15 +7,27 @@ pub enum Source {
     ConfigFile,
     Cli,
     Default,
+    /// Remote stores such as AWS SSM Parameter Store, layered above the environment.
+    Remote,
+    /// `.env` files, layered above defaults and below config files.
+    DotEnv,
 }
 
 impl Source {
+    /// Merge priority; sources with a higher number override lower ones.
+    ///
+    /// The numbers are only meaningful relative to each other and change when
+    /// a source type is added (adding [`Source::Remote`] moved
+    /// [`Source::Cli`] from 3 to 4, adding [`Source::DotEnv`] moved it to 5),
+    /// so compare priorities with each other rather than with literals.
     pub fn priority(&self) -> u8 {
         match self {
             Source::Default => 0,
-            Source::ConfigFile => 1,
-            Source::Environment => 2,
-            Source::Cli => 3,
+            Source::DotEnv => 1,
+            Source::ConfigFile => 2,
+            Source::Environment => 3,
+            Source::Remote => 4,
+            Source::Cli => 5,
         }
     }
 }
@@ -29,6 +41,12 @@ pub trait ConfigSource: Any + Send + Sync {
 
     fn get_value(&self, key: &str) -> Option<serde_json::Value>;
 
+    /// Short human-readable description of where this source reads from,
+    /// used by [`ConfigBuilder::sources_summary`](crate::ConfigBuilder::sources_summary).
+    fn describe(&self) -> String {
+        String::new()
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -0,0 +1,406 @@
+//! AWS SSM Parameter Store configuration source.
+
+use crate::{
+    environment::Environment,
+    error::{Error, Result},
+    source::{ConfigSource, Source},
+};
+use aws_config::{BehaviorVersion, SdkConfig};
+use aws_credential_types::{provider::ProvideCredentials, Credentials};
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::sign::v4;
+use serde_json::{json, Map, Value};
+use std::any::Any;
+use std::time::{Duration, SystemTime};
+
+/// Type of an SSM parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterType {
+    /// Plain text value.
+    String,
+    /// Comma-separated list, collected as an array.
+    StringList,
+    /// KMS-encrypted value, returned decrypted by the store.
+    SecureString,
+}
+
+/// A single parameter returned by a [`ParameterStore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// Full parameter name, e.g. `/myapp/prod/database/url`.
+    pub name: String,
+    /// Parameter value (already decrypted for `SecureString`).
+    pub value: String,
+    /// Parameter type.
+    pub kind: ParameterType,
+}
+
+impl Parameter {
+    /// Create a plain `String` parameter.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            kind: ParameterType::String,
+        }
+    }
+
+    /// Set the parameter type.
+    pub fn kind(mut self, kind: ParameterType) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+/// Client abstraction over the SSM `GetParametersByPath` API.
+///
+/// [`AwsParameterStore`] implements it against AWS using the standard
+/// credential chain. Implement it for a mock in tests, or to reach a store
+/// through another client. Implementations must return every parameter below
+/// `path`, recursively, following pagination.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::ssm::{Parameter, ParameterStore};
+///
+/// struct Fixed;
+///
+/// impl ParameterStore for Fixed {
+///     fn parameters_by_path(&self, _path: &str, _with_decryption: bool) -> gonfig::Result<Vec<Parameter>> {
+///         Ok(vec![Parameter::new("/app/port", "8080")])
+///     }
+/// }
+/// ```
+pub trait ParameterStore: Send + Sync {
+    /// Fetch all parameters under `path`, decrypting `SecureString` values when
+    /// `with_decryption` is set.
+    fn parameters_by_path(&self, path: &str, with_decryption: bool) -> Result<Vec<Parameter>>;
+}
+
+/// Largest page `GetParametersByPath` allows.
+const PAGE_SIZE: u32 = 10;
+
+/// [`ParameterStore`] calling the SSM API of AWS.
+///
+/// Credentials and region come from the standard AWS chain (environment
+/// variables, shared config and credentials files, SSO, container and
+/// instance metadata), unless a configuration is passed to
+/// [`from_config`](AwsParameterStore::from_config). Requests are signed with
+/// SigV4 and sent over HTTPS to the region's SSM endpoint, or to the
+/// configuration's `endpoint_url` when set.
+#[derive(Debug, Clone)]
+pub struct AwsParameterStore {
+    config: Option<SdkConfig>,
+    timeout: Duration,
+}
+
+impl AwsParameterStore {
+    /// Use the standard credential chain, resolved on every fetch.
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Use the credentials, region and endpoint of `config`.
+    pub fn from_config(config: SdkConfig) -> Self {
+        Self {
+            config: Some(config),
+            ..Self::new()
+        }
+    }
+
+    /// Give up on each request after `timeout` (30 seconds by default).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Load the configuration and credentials on a thread of its own, so it
+    /// also works when called from within an async runtime.
+    fn resolve(&self) -> Result<(SdkConfig, Credentials)> {
+        let config = self.config.clone();
+        let resolved = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| fail(e.to_string()))?;
+            runtime.block_on(async move {
+                let config = match config {
+                    Some(config) => config,
+                    None => aws_config::load_defaults(BehaviorVersion::latest()).await,
+                };
+                let provider = config
+                    .credentials_provider()
+                    .ok_or_else(|| fail("no AWS credentials provider configured".to_string()))?;
+                let credentials = provider
+                    .provide_credentials()
+                    .await
+                    .map_err(|e| fail(format!("loading AWS credentials failed: {e}")))?;
+                Ok((config, credentials))
+            })
+        })
+        .join();
+        resolved.unwrap_or_else(|_| Err(fail("loading AWS credentials panicked".to_string())))
+    }
+
+    /// Send one signed `GetParametersByPath` request.
+    fn request_page(
+        &self,
+        endpoint: &str,
+        region: &str,
+        credentials: &Credentials,
+        body: &[u8],
+    ) -> Result<Value> {
+        let headers = [
+            ("content-type", "application/x-amz-json-1.1"),
+            ("x-amz-target", "AmazonSSM.GetParametersByPath"),
+        ];
+        let identity = credentials.clone().into();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(region)
+            .name("ssm")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|e| fail(e.to_string()))?
+            .into();
+        let signable = SignableRequest::new(
+            "POST",
+            endpoint,
+            headers.iter().copied(),
+            SignableBody::Bytes(body),
+        )
+        .map_err(|e| fail(e.to_string()))?;
+        let (instructions, _) = sign(signable, &params)
+            .map_err(|e| fail(e.to_string()))?
+            .into_parts();
+
+        let mut request = ureq::post(endpoint).timeout(self.timeout);
+        for (name, value) in headers.into_iter().chain(instructions.headers()) {
+            request = request.set(name, value);
+        }
+        let response = match request.send_bytes(body) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                return Err(fail(format!("HTTP status {status}: {detail}")));
+            }
+            Err(e) => return Err(fail(e.to_string())),
+        };
+        let body = response.into_string().map_err(|e| fail(e.to_string()))?;
+        serde_json::from_str(&body).map_err(|e| fail(format!("invalid JSON response: {e}")))
+    }
+}
+
+impl Default for AwsParameterStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParameterStore for AwsParameterStore {
+    fn parameters_by_path(&self, path: &str, with_decryption: bool) -> Result<Vec<Parameter>> {
+        let (config, credentials) = self.resolve()?;
+        let region = config
+            .region()
+            .ok_or_else(|| fail("no AWS region configured".to_string()))?
+            .to_string();
+        let endpoint = match config.endpoint_url() {
+            Some(url) => url.to_string(),
+            None => format!("https://ssm.{region}.amazonaws.com/"),
+        };
+
+        let mut parameters = Vec::new();
+        let mut next_token: Option<String> = None;
+        loop {
+            let mut request = json!({
+                "Path": path,
+                "Recursive": true,
+                "WithDecryption": with_decryption,
+                "MaxResults": PAGE_SIZE,
+            });
+            if let Some(token) = &next_token {
+                request["NextToken"] = json!(token);
+            }
+            let page = self.request_page(
+                &endpoint,
+                &region,
+                &credentials,
+                request.to_string().as_bytes(),
+            )?;
+
+            for parameter in page["Parameters"].as_array().into_iter().flatten() {
+                let (Some(name), Some(value)) =
+                    (parameter["Name"].as_str(), parameter["Value"].as_str())
+                else {
+                    continue;
+                };
+                let kind = match parameter["Type"].as_str() {
+                    Some("StringList") => ParameterType::StringList,
+                    Some("SecureString") => ParameterType::SecureString,
+                    _ => ParameterType::String,
+                };
+                parameters.push(Parameter::new(name, value).kind(kind));
+            }
+
+            match page["NextToken"].as_str() {
+                Some(token) if !token.is_empty() => next_token = Some(token.to_string()),
+                _ => return Ok(parameters),
+            }
+        }
+    }
+}
+
+fn fail(message: String) -> Error {
+    Error::Source {
+        source_name: "aws ssm".to_string(),
+        message,
+    }
+}
+
+/// Configuration source reading parameters under an SSM path prefix.
+///
+/// Parameter names are made relative to the prefix and each `/` becomes a level
+/// of nesting, so `/myapp/prod/database/url` under `/myapp/prod` is collected as
+/// `{"database": {"url": ...}}`. Only names continuing the prefix at a `/` are
+/// used; `/myapp/production/url` is not below `/myapp/prod`. Values are
+/// type-coerced the same way as environment variables and `StringList`
+/// parameters become arrays.
+///
+/// Parameters are read from AWS through an [`AwsParameterStore`] unless another
+/// [`ParameterStore`] is passed to [`with_store`](SsmSource::with_store). The
+/// source reports [`Source::Remote`], placing it above environment variables
+/// and below CLI arguments.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::ssm::{Parameter, ParameterStore, SsmSource};
+/// use gonfig::ConfigSource;
+///
+/// struct Fixed;
+///
+/// impl ParameterStore for Fixed {
+///     fn parameters_by_path(&self, _path: &str, _with_decryption: bool) -> gonfig::Result<Vec<Parameter>> {
+///         Ok(vec![Parameter::new("/myapp/prod/http/port", "9000")])
+///     }
+/// }
+///
+/// let source = SsmSource::with_store(Fixed, "/myapp/prod");
+/// let value = source.collect()?;
+/// assert_eq!(value["http"]["port"], 9000);
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub struct SsmSource {
+    store: Box<dyn ParameterStore>,
+    path_prefix: String,
+    with_decryption: bool,
+}
+
+impl SsmSource {
+    /// Read every parameter under `path_prefix` from AWS, using the standard
+    /// credential chain.
+    ///
+    /// `SecureString` parameters are decrypted by default.
+    pub fn new(path_prefix: impl Into<String>) -> Self {
+        Self::with_store(AwsParameterStore::new(), path_prefix)
+    }
+
+    /// Read every parameter under `path_prefix` through `store`.
+    pub fn with_store(
+        store: impl ParameterStore + 'static,
+        path_prefix: impl Into<String>,
+    ) -> Self {
+        Self {
+            store: Box::new(store),
+            path_prefix: path_prefix.into(),
+            with_decryption: true,
+        }
+    }
+
+    /// Control whether `SecureString` parameters are requested decrypted.
+    pub fn with_decryption(mut self, decrypt: bool) -> Self {
+        self.with_decryption = decrypt;
+        self
+    }
+
+    /// Path segments of a parameter name relative to the configured prefix,
+    /// or `None` when the name is not below it.
+    fn relative_parts(&self, name: &str) -> Option<Vec<String>> {
+        let prefix = self.path_prefix.trim_end_matches('/');
+        let rest = if name == prefix {
+            ""
+        } else {
+            name.strip_prefix(prefix)?.strip_prefix('/')?
+        };
+        Some(
+            rest.split('/')
+                .filter(|part| !part.is_empty())
+                .map(|part| part.to_string())
+                .collect(),
+        )
+    }
+
+    fn parse_parameter(parameter: &Parameter) -> Value {
+        match parameter.kind {
+            ParameterType::StringList => Value::Array(
+                parameter
+                    .value
+                    .split(',')
+                    .map(|item| Environment::parse_env_value(item.trim()))
+                    .collect(),
+            ),
+            ParameterType::String | ParameterType::SecureString => {
+                Environment::parse_env_value(&parameter.value)
+            }
+        }
+    }
+}
+
+impl ConfigSource for SsmSource {
+    fn source_type(&self) -> Source {
+        Source::Remote
+    }
+
+    fn collect(&self) -> Result<Value> {
+        let parameters = self
+            .store
+            .parameters_by_path(&self.path_prefix, self.with_decryption)?;
+
+        let mut result = Map::new();
+        for parameter in &parameters {
+            let Some(parts) = self.relative_parts(&parameter.name) else {
+                continue;
+            };
+            if parts.is_empty() {
+                continue;
+            }
+            Environment::insert_nested(&mut result, &parts, Self::parse_parameter(parameter));
+        }
+
+        Ok(Value::Object(result))
+    }
+
+    fn has_value(&self, key: &str) -> bool {
+        self.get_value(key).is_some()
+    }
+
+    fn get_value(&self, key: &str) -> Option<Value> {
+        let data = self.collect().ok()?;
+        key.split('.')
+            .try_fold(&data, |current, part| current.get(part))
+            .cloned()
+    }
+
+    fn describe(&self) -> String {
+        format!("ssm path {}", self.path_prefix)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,331 @@
+//! Transformations applied to the merged configuration before deserialization.
+
+use crate::error::{Error, Result};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/// Combine a numeric value field and a sibling unit field into a duration.
+///
+/// Reads the number at `value_path` and the unit at `unit_path`, removes both,
+/// and writes the duration at `target_path` in the representation expected by
+/// `std::time::Duration`'s `Deserialize` impl. Paths are dotted, e.g.
+/// `server.timeout_value`. Supported units are `ns`, `us`, `ms`, `s`, `m`, `h`
+/// and `d`, plus their long forms (`seconds`, `minutes`, ...).
+///
+/// Nothing happens when neither field is present.
+///
+/// # Errors
+///
+/// Returns [`Error::Validation`] if only one of the two fields is present, the
+/// value is not a non-negative number, or the unit is not recognized.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::combine_duration;
+/// use serde_json::json;
+/// use std::time::Duration;
+///
+/// let mut value = json!({ "timeout_value": 30, "timeout_unit": "s" });
+/// combine_duration(&mut value, "timeout_value", "timeout_unit", "timeout")?;
+///
+/// let timeout: Duration = serde_json::from_value(value["timeout"].clone()).unwrap();
+/// assert_eq!(timeout, Duration::from_secs(30));
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub fn combine_duration(
+    value: &mut Value,
+    value_path: &str,
+    unit_path: &str,
+    target_path: &str,
+) -> Result<()> {
+    let amount = remove_path(value, value_path);
+    let unit = remove_path(value, unit_path);
+
+    let (amount, unit) = match (amount, unit) {
+        (None, None) => return Ok(()),
+        (Some(amount), Some(unit)) => (amount, unit),
+        (Some(_), None) => {
+            return Err(Error::Validation(format!(
+                "`{value_path}` is set but its unit `{unit_path}` is missing"
+            )))
+        }
+        (None, Some(_)) => {
+            return Err(Error::Validation(format!(
+                "`{unit_path}` is set but its value `{value_path}` is missing"
+            )))
+        }
+    };
+
+    let amount = amount
+        .as_f64()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+        .ok_or_else(|| {
+            Error::Validation(format!(
+                "`{value_path}` must be a non-negative number, got {amount}"
+            ))
+        })?;
+    let unit = match unit.as_str().map(str::trim) {
+        Some(unit) if !unit.is_empty() => unit.to_string(),
+        _ => {
+            return Err(Error::Validation(format!(
+                "Missing duration unit in `{unit_path}`, expected one of {ACCEPTED_UNITS}"
+            )))
+        }
+    };
+    let nanos_per_unit = unit_nanos(&unit).ok_or_else(|| {
+        Error::Validation(format!(
+            "Unknown duration unit `{unit}` in `{unit_path}`, expected one of {ACCEPTED_UNITS}"
+        ))
+    })?;
+
+    let total_nanos = (amount * nanos_per_unit as f64).round() as u128;
+    let duration = json!({
+        "secs": (total_nanos / 1_000_000_000) as u64,
+        "nanos": (total_nanos % 1_000_000_000) as u32,
+    });
+    insert_path(value, target_path, duration);
+    Ok(())
+}
+
+/// Wrap a single scalar at a dotted `path` into a one-element array.
+///
+/// Strings, numbers and booleans become `[value]`; arrays, objects, `null` and
+/// missing paths are left alone. This lets a list field accept either form, as
+/// YAML users often expect.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::wrap_scalar;
+/// use serde_json::json;
+///
+/// let mut value = json!({ "server": { "hosts": "localhost" } });
+/// wrap_scalar(&mut value, "server.hosts");
+/// assert_eq!(value["server"]["hosts"], json!(["localhost"]));
+/// ```
+pub fn wrap_scalar(value: &mut Value, path: &str) {
+    if let Some(target) = get_path_mut(value, path) {
+        if matches!(target, Value::String(_) | Value::Number(_) | Value::Bool(_)) {
+            *target = Value::Array(vec![target.take()]);
+        }
+    }
+}
+
+/// Turn a string at a dotted `path` spelled `true` or `false` in any case into
+/// a boolean.
+///
+/// Other values and missing paths are left alone, so a typo still surfaces as
+/// a deserialization error.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::normalize_bool;
+/// use serde_json::json;
+///
+/// let mut value = json!({ "server": { "tls": "TRUE" } });
+/// normalize_bool(&mut value, "server.tls");
+/// assert_eq!(value["server"]["tls"], json!(true));
+/// ```
+pub fn normalize_bool(value: &mut Value, path: &str) {
+    if let Some(target) = get_path_mut(value, path) {
+        let parsed = match target.as_str() {
+            Some(text) if text.eq_ignore_ascii_case("true") => true,
+            Some(text) if text.eq_ignore_ascii_case("false") => false,
+            _ => return,
+        };
+        *target = Value::Bool(parsed);
+    }
+}
+
+/// Replace a string at a dotted `path` with the entry of `variants` it matches
+/// ignoring ASCII case.
+///
+/// Lets a unit enum field accept `PROD` or `prod` for a `Prod` variant.
+/// Unmatched values and missing paths are left alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::normalize_variant;
+/// use serde_json::json;
+///
+/// let mut value = json!({ "mode": "PROD" });
+/// normalize_variant(&mut value, "mode", &["Dev", "Prod"]);
+/// assert_eq!(value["mode"], json!("Prod"));
+/// ```
+pub fn normalize_variant(value: &mut Value, path: &str, variants: &[&str]) {
+    if let Some(target) = get_path_mut(value, path) {
+        let canonical = target.as_str().and_then(|text| {
+            variants
+                .iter()
+                .find(|variant| variant.eq_ignore_ascii_case(text))
+        });
+        if let Some(canonical) = canonical {
+            *target = Value::String((*canonical).to_string());
+        }
+    }
+}
+
+/// Replace the scalar at a dotted `path` with the value `parser` produces
+/// from its text.
+///
+/// Strings are passed as they are; numbers and booleans, which environment
+/// variables and CLI arguments are coerced into, are passed in their text
+/// form. Arrays, objects, `null` and missing paths are left alone.
+/// [`ConfigBuilder::with_value_parser`](crate::ConfigBuilder::with_value_parser)
+/// applies it to each source that is not read as raw text, such as a file.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] naming `path` when `parser` fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::parse_with;
+/// use serde_json::{json, Value};
+///
+/// fn parse_size(text: &str) -> Result<Value, String> {
+///     let megabytes = text.strip_suffix("MB").ok_or("expected a size in MB")?;
+///     let megabytes: u64 = megabytes.parse().map_err(|e| format!("{e}"))?;
+///     Ok(json!(megabytes * 1024 * 1024))
+/// }
+///
+/// let mut value = json!({ "cache": { "size": "10MB" } });
+/// parse_with(&mut value, "cache.size", parse_size)?;
+/// assert_eq!(value["cache"]["size"], json!(10_485_760));
+///
+/// let mut value = json!({ "cache": { "size": "huge" } });
+/// assert!(parse_with(&mut value, "cache.size", parse_size).is_err());
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub fn parse_with<E, F>(value: &mut Value, path: &str, parser: F) -> Result<()>
+where
+    E: std::fmt::Display,
+    F: Fn(&str) -> std::result::Result<Value, E>,
+{
+    if let Some(target) = get_path_mut(value, path) {
+        let text = match target {
+            Value::String(text) => text.clone(),
+            Value::Number(number) => number.to_string(),
+            Value::Bool(flag) => flag.to_string(),
+            _ => return Ok(()),
+        };
+        *target = parser(&text).map_err(|e| Error::Parse {
+            path: path.to_string(),
+            message: e.to_string(),
+            context: String::new(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Parser registered for one field with
+/// [`ConfigBuilder::with_value_parser`](crate::ConfigBuilder::with_value_parser)
+/// or [`Environment::with_value_parser`](crate::Environment::with_value_parser).
+#[derive(Clone)]
+pub(crate) struct ValueParser(Arc<ParseFn>);
+
+/// Type alias for a value parser with its error already formatted.
+type ParseFn = dyn Fn(&str) -> std::result::Result<Value, String> + Send + Sync;
+
+impl ValueParser {
+    pub(crate) fn new<E, F>(parser: F) -> Self
+    where
+        E: std::fmt::Display,
+        F: Fn(&str) -> std::result::Result<Value, E> + Send + Sync + 'static,
+    {
+        Self(Arc::new(move |text| {
+            parser(text).map_err(|e| e.to_string())
+        }))
+    }
+
+    /// Parse `text`, the raw value of the field at `path`.
+    pub(crate) fn parse(&self, path: &str, text: &str) -> Result<Value> {
+        (self.0)(text).map_err(|message| Error::Parse {
+            path: path.to_string(),
+            message,
+            context: String::new(),
+        })
+    }
+
+    /// Replace the scalar at `path` in a collected value, see [`parse_with`].
+    pub(crate) fn parse_at(&self, value: &mut Value, path: &str) -> Result<()> {
+        parse_with(value, path, |text| (self.0)(text))
+    }
+}
+
+impl std::fmt::Debug for ValueParser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ValueParser")
+    }
+}
+
+fn get_path_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.')
+        .try_fold(value, |current, part| current.get_mut(part))
+}
+
+/// Units listed in duration error messages.
+const ACCEPTED_UNITS: &str = "ns, us, ms, s, m, h, d";
+
+/// Length of one `unit` in nanoseconds.
+fn unit_nanos(unit: &str) -> Option<u64> {
+    let nanos = match unit.trim().to_lowercase().as_str() {
+        "ns" | "nanos" | "nanoseconds" => 1,
+        "us" | "micros" | "microseconds" => 1_000,
+        "ms" | "millis" | "milliseconds" => 1_000_000,
+        "s" | "sec" | "secs" | "seconds" => 1_000_000_000,
+        "m" | "min" | "mins" | "minutes" => 60 * 1_000_000_000,
+        "h" | "hr" | "hours" => 3_600 * 1_000_000_000,
+        "d" | "days" => 86_400 * 1_000_000_000,
+        _ => return None,
+    };
+    Some(nanos)
+}
+
+fn remove_path(value: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent
+                .split('.')
+                .try_fold(&mut *value, |current, part| current.get_mut(part))?,
+            key,
+        ),
+        None => (value, path),
+    };
+    parent.as_object_mut()?.remove(key)
+}
+
+/// Insert `new_value` at a dotted `path`, creating intermediate objects and
+/// replacing non-object values along the way.
+///
+/// # Examples
+///
+/// ```rust
+/// use gonfig::transform::insert_path;
+/// use serde_json::json;
+///
+/// let mut value = json!({});
+/// insert_path(&mut value, "database.port", json!(5432));
+/// assert_eq!(value, json!({ "database": { "port": 5432 } }));
+/// ```
+pub fn insert_path(value: &mut Value, path: &str, new_value: Value) {
+    let mut current = value;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let map = current.as_object_mut().expect("just ensured an object");
+        if parts.peek().is_none() {
+            map.insert(part.to_string(), new_value);
+            return;
+        }
+        current = map
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
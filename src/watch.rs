@@ -0,0 +1,382 @@
+//! Hot reloading of file-backed configuration.
+
+use crate::{
+    builder::{deserialize_merged, ConfigBuilder},
+    error::{Error, Result},
+};
+use notify::event::{AccessKind, AccessMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How long [`ConfigBuilder::watch_with`] waits for the watched files to stop
+/// changing before reloading.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Configuration kept up to date with the files it was built from.
+///
+/// Returned by [`ConfigBuilder::watch_with`]. A background thread creates the
+/// builder, watches the directory of every configuration file registered on
+/// it through [`notify`], and, when one of the files changes, runs the
+/// builder's full pipeline again: sources are re-collected and merged,
+/// transforms and [`validate_with`](ConfigBuilder::validate_with) run, and
+/// the result is deserialized into `T`. [`on_reload`](ConfigBuilder::on_reload)
+/// callbacks run as for [`ConfigBuilder::reload`].
+///
+/// A reload that fails keeps the last good value and reports the error to
+/// [`subscribe_errors`](WatchedConfig::subscribe_errors) receivers instead.
+/// A change is only applied once no further events arrived for the debounce
+/// delay, so a file caught halfway through being written is not loaded, and
+/// files are compared by content, so rewriting a file with identical content
+/// does not trigger a reload. Files whose directory does not exist are not
+/// watched. With [`reload_on_sighup`](ConfigBuilder::reload_on_sighup),
+/// `SIGHUP` also triggers a reload. The thread stops when the
+/// `WatchedConfig` is dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use gonfig::ConfigBuilder;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     workers: u32,
+/// }
+///
+/// let watched = ConfigBuilder::watch_with::<Config, _>(|| ConfigBuilder::new().with_file("app.toml"))?;
+///
+/// let updates = watched.subscribe();
+/// for config in updates {
+///     println!("now running {} workers", config.workers);
+/// }
+/// # Ok::<(), gonfig::Error>(())
+/// ```
+pub struct WatchedConfig<T> {
+    shared: Arc<Shared<T>>,
+    events: Sender<Event>,
+    handle: Option<JoinHandle<()>>,
+}
+
+struct Shared<T> {
+    current: RwLock<Arc<T>>,
+    subscribers: Mutex<Vec<Sender<Arc<T>>>>,
+    error_subscribers: Mutex<Vec<Sender<Arc<Error>>>>,
+}
+
+/// Message to the watch thread.
+enum Event {
+    /// A watched file was created, written, renamed or removed.
+    Changed,
+    /// The process received `SIGHUP`.
+    #[cfg_attr(not(all(feature = "signal", unix)), allow(dead_code))]
+    Hangup,
+    /// The `WatchedConfig` was dropped.
+    Stop,
+}
+
+/// State owned by the watch thread.
+struct Watch {
+    builder: ConfigBuilder,
+    paths: Vec<PathBuf>,
+    applied: Vec<Option<u64>>,
+    // Dropping the watcher and the signal listener stops their events
+    _watcher: RecommendedWatcher,
+    _hangups: Option<sighup::Listener>,
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static> WatchedConfig<T> {
+    /// Create the builder with `make` on a thread of its own, build `T` and
+    /// start watching its files.
+    pub(crate) fn start<F>(make: F, debounce: Duration) -> Result<Self>
+    where
+        F: FnOnce() -> Result<ConfigBuilder> + Send + 'static,
+    {
+        let (events, received) = mpsc::channel();
+        let (ready, started) = mpsc::sync_channel(1);
+
+        let handle = {
+            let events = events.clone();
+            thread::spawn(move || match Watch::start::<T, F>(make, events) {
+                Ok((watch, initial)) => {
+                    let shared = Arc::new(Shared {
+                        current: RwLock::new(Arc::new(initial)),
+                        subscribers: Mutex::new(Vec::new()),
+                        error_subscribers: Mutex::new(Vec::new()),
+                    });
+                    if ready.send(Ok(Arc::clone(&shared))).is_ok() {
+                        watch_loop(watch, &shared, &received, debounce);
+                    }
+                }
+                Err(e) => {
+                    let _ = ready.send(Err(e));
+                }
+            })
+        };
+
+        let shared = match started.recv() {
+            Ok(shared) => shared?,
+            // The thread only hangs up without answering when `make` panicked
+            Err(_) => match handle.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("watch thread exited without reporting"),
+            },
+        };
+
+        Ok(Self {
+            shared,
+            events,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl<T> WatchedConfig<T> {
+    /// The most recent configuration that loaded successfully.
+    pub fn current(&self) -> Arc<T> {
+        Arc::clone(
+            &self
+                .shared
+                .current
+                .read()
+                .unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+
+    /// Receive every configuration loaded after this call.
+    ///
+    /// The channel closes when the `WatchedConfig` is dropped.
+    pub fn subscribe(&self) -> Receiver<Arc<T>> {
+        let (sender, receiver) = mpsc::channel();
+        lock(&self.shared.subscribers).push(sender);
+        receiver
+    }
+
+    /// Receive the error of every failed reload after this call.
+    ///
+    /// The channel closes when the `WatchedConfig` is dropped.
+    pub fn subscribe_errors(&self) -> Receiver<Arc<Error>> {
+        let (sender, receiver) = mpsc::channel();
+        lock(&self.shared.error_subscribers).push(sender);
+        receiver
+    }
+}
+
+impl<T> Drop for WatchedConfig<T> {
+    fn drop(&mut self) {
+        let _ = self.events.send(Event::Stop);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Watch {
+    fn start<T, F>(make: F, events: Sender<Event>) -> Result<(Self, T)>
+    where
+        T: DeserializeOwned,
+        F: FnOnce() -> Result<ConfigBuilder>,
+    {
+        let builder = make()?;
+        let paths = watched_paths(&builder.file_paths());
+        // Watching starts before the initial build so no change in between is missed
+        let hangups = if builder.reloads_on_sighup() {
+            Some(sighup::Listener::start(events.clone())?)
+        } else {
+            None
+        };
+        let watcher = watch_files(&paths, events)?;
+        let applied = fingerprint(&paths);
+        let initial = deserialize_merged(builder.collect_value()?, PhantomData)?;
+        Ok((
+            Self {
+                builder,
+                paths,
+                applied,
+                _watcher: watcher,
+                _hangups: hangups,
+            },
+            initial,
+        ))
+    }
+}
+
+fn watch_loop<T: DeserializeOwned>(
+    mut watch: Watch,
+    shared: &Shared<T>,
+    received: &Receiver<Event>,
+    debounce: Duration,
+) {
+    let mut pending = false;
+    let mut hung_up = false;
+
+    loop {
+        // Only reload once no event arrived for the debounce delay; with
+        // nothing pending, sleep until the next event
+        let event = if pending {
+            received.recv_timeout(debounce)
+        } else {
+            received.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        };
+        match event {
+            Ok(Event::Changed) => {
+                pending = true;
+                continue;
+            }
+            Ok(Event::Hangup) => {
+                pending = true;
+                hung_up = true;
+                continue;
+            }
+            Ok(Event::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+        pending = false;
+        let now = fingerprint(&watch.paths);
+        // A signal reloads even when no file changed
+        if !std::mem::take(&mut hung_up) && now == watch.applied {
+            continue;
+        }
+        watch.applied = now;
+
+        let reloaded = watch
+            .builder
+            .reload()
+            .and_then(|value| deserialize_merged(value, PhantomData));
+        match reloaded {
+            Ok(config) => {
+                let config = Arc::new(config);
+                let mut current = shared.current.write().unwrap_or_else(|e| e.into_inner());
+                *current = Arc::clone(&config);
+                drop(current);
+                lock(&shared.subscribers).retain(|sender| sender.send(Arc::clone(&config)).is_ok());
+            }
+            Err(e) => {
+                tracing::warn!("Keeping previous configuration, reload failed: {}", e);
+                let error = Arc::new(e);
+                lock(&shared.error_subscribers)
+                    .retain(|sender| sender.send(Arc::clone(&error)).is_ok());
+            }
+        }
+    }
+}
+
+/// Absolute path of each file whose directory exists, as `notify` reports it.
+fn watched_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            Some(fs::canonicalize(dir).ok()?.join(path.file_name()?))
+        })
+        .collect()
+}
+
+/// Watch the directories of `paths`, so files replaced by a rename are still
+/// followed, and send [`Event::Changed`] for events touching one of them.
+fn watch_files(paths: &[PathBuf], events: Sender<Event>) -> Result<RecommendedWatcher> {
+    let files = paths.to_vec();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        match event {
+            // Reading a file, including our own reloads, is not a change
+            Ok(event)
+                if matches!(event.kind, EventKind::Access(kind)
+                    if kind != AccessKind::Close(AccessMode::Write)) => {}
+            Ok(event) if event.paths.iter().any(|path| files.contains(path)) => {
+                let _ = events.send(Event::Changed);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Configuration file watcher failed: {}", e),
+        }
+    })
+    .map_err(|e| Error::Config(format!("Failed to start the file watcher: {e}")))?;
+
+    let dirs: BTreeSet<&Path> = paths.iter().filter_map(|path| path.parent()).collect();
+    for dir in dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| Error::io_at(dir, std::io::Error::other(e)))?;
+    }
+    Ok(watcher)
+}
+
+/// Content hash of each file, `None` for files that cannot be read.
+fn fingerprint(paths: &[PathBuf]) -> Vec<Option<u64>> {
+    paths
+        .iter()
+        .map(|path| {
+            let content = fs::read(path).ok()?;
+            let mut hasher = DefaultHasher::new();
+            content.hash(&mut hasher);
+            Some(hasher.finish())
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "signal", unix))]
+mod sighup {
+    use super::Event;
+    use crate::error::Result;
+    use signal_hook::consts::SIGHUP;
+    use signal_hook::iterator::{Handle, Signals};
+    use std::sync::mpsc::Sender;
+    use std::thread;
+
+    /// Thread forwarding every `SIGHUP` to the watch thread as [`Event::Hangup`].
+    pub(super) struct Listener {
+        handle: Handle,
+    }
+
+    impl Listener {
+        pub(super) fn start(events: Sender<Event>) -> Result<Self> {
+            let mut signals = Signals::new([SIGHUP])?;
+            let handle = signals.handle();
+            thread::spawn(move || {
+                for _ in signals.forever() {
+                    if events.send(Event::Hangup).is_err() {
+                        break;
+                    }
+                }
+            });
+            Ok(Self { handle })
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            // Unregisters the handler and ends the thread's iteration
+            self.handle.close();
+        }
+    }
+}
+
+#[cfg(not(all(feature = "signal", unix)))]
+mod sighup {
+    use super::Event;
+    use crate::error::Result;
+    use std::sync::mpsc::Sender;
+
+    pub(super) enum Listener {}
+
+    impl Listener {
+        pub(super) fn start(_events: Sender<Event>) -> Result<Self> {
+            unreachable!("reload_on_sighup needs the `signal` feature on Unix")
+        }
+    }
+}
+
+fn lock<V>(mutex: &Mutex<V>) -> std::sync::MutexGuard<'_, V> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
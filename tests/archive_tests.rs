@@ -0,0 +1,97 @@
+#![cfg(feature = "archive")]
+
+use gonfig::archive::ArchiveSource;
+use gonfig::{ConfigBuilder, ConfigFormat, ConfigSource};
+use serde::Deserialize;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+
+fn zip_bundle(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        writer
+            .start_file(*name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[derive(Debug, Deserialize)]
+struct BundleConfig {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn test_archive_reads_entry_from_in_memory_zip() {
+    let bundle = zip_bundle(&[
+        ("config.yaml", "name: bundled\nport: 8080\n"),
+        ("other.json", r#"{"name": "other"}"#),
+    ]);
+
+    let source =
+        ArchiveSource::from_zip_reader(Cursor::new(bundle), "config.yaml", ConfigFormat::Yaml)
+            .unwrap();
+
+    assert_eq!(source.entry(), "config.yaml");
+    let value = source.collect().unwrap();
+    assert_eq!(value["name"], "bundled");
+    assert_eq!(value["port"], 8080);
+}
+
+#[test]
+fn test_archive_missing_entry_is_an_error() {
+    let bundle = zip_bundle(&[("config.yaml", "name: bundled\n")]);
+
+    let result =
+        ArchiveSource::from_zip_reader(Cursor::new(bundle), "missing.yaml", ConfigFormat::Yaml);
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("missing.yaml"), "{message}");
+}
+
+#[test]
+fn test_builder_with_archive_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bundle.zip");
+    std::fs::write(
+        &path,
+        zip_bundle(&[("app/config.toml", "name = \"from-zip\"\nport = 9000\n")]),
+    )
+    .unwrap();
+
+    let config: BundleConfig = ConfigBuilder::new()
+        .with_archive_entry(&path, "app/config.toml", ConfigFormat::Toml)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(config.name, "from-zip");
+    assert_eq!(config.port, 9000);
+}
+
+#[test]
+fn test_builder_with_tar_archive_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bundle.tar");
+
+    let content = br#"{"name": "from-tar", "port": 7000}"#;
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "config.json", &content[..])
+        .unwrap();
+    std::fs::write(&path, builder.into_inner().unwrap()).unwrap();
+
+    let config: BundleConfig = ConfigBuilder::new()
+        .with_archive_entry(&path, "config.json", ConfigFormat::Json)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(config.name, "from-tar");
+    assert_eq!(config.port, 7000);
+}
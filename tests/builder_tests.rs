@@ -1,4 +1,4 @@
-use gonfig::{ConfigBuilder, ConfigFormat, Error, MergeStrategy};
+use gonfig::{Cli, ConfigBuilder, ConfigFormat, ConfigSource, Error, MergeStrategy, Source};
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::io::Write;
@@ -17,7 +17,7 @@ fn test_builder_basic() {
     env::set_var("TEST_DATABASE_URL", "postgres://localhost");
     env::set_var("TEST_PORT", "5432");
 
-    let config: AppConfig = ConfigBuilder::new().with_env("TEST").build().unwrap();
+    let config: AppConfig = ConfigBuilder::new().with_env_flat("TEST").build().unwrap();
 
     assert_eq!(config.database_url, "postgres://localhost");
     assert_eq!(config.port, 5432);
@@ -68,7 +68,7 @@ port = 3000
     let config: AppConfig = ConfigBuilder::new()
         .with_merge_strategy(MergeStrategy::Deep)
         .with_file_format(file.path(), ConfigFormat::Toml)?
-        .with_env("MERGE")
+        .with_env_flat("MERGE")
         .build()?;
 
     assert_eq!(config.database_url, "postgres://fromenv");
@@ -110,7 +110,7 @@ fn test_builder_optional_config_file() -> Result<(), Box<dyn std::error::Error>>
     // Non-existent file should not cause error
     let config: AppConfig = ConfigBuilder::new()
         .with_file_optional("/non/existent/file.toml")?
-        .with_env("OPT")
+        .with_env_flat("OPT")
         .build()?;
 
     assert_eq!(config.database_url, "postgres://fromenv");
@@ -142,7 +142,7 @@ debug = false
     // For this test we'll simulate by just testing env > file priority
     let config: AppConfig = ConfigBuilder::new()
         .with_file_format(file.path(), ConfigFormat::Toml)?
-        .with_env("PRIO")
+        .with_env_flat("PRIO")
         .build()?;
 
     // Env should override file
@@ -154,3 +154,1474 @@ debug = false
     env::remove_var("PRIO_DEBUG");
     Ok(())
 }
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct PoolConfig {
+    size: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct DatabaseSection {
+    url: String,
+    pool: PoolConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct NestedAppConfig {
+    database: DatabaseSection,
+}
+
+#[test]
+fn test_builder_deserialize_error_includes_path() {
+    let result: Result<NestedAppConfig, _> = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "database": {
+                "url": "postgres://localhost",
+                "pool": { "size": "not-a-number" }
+            }
+        }))
+        .unwrap()
+        .build();
+
+    let err = result.unwrap_err();
+    match &err {
+        Error::Deserialize { path, source, .. } => {
+            assert_eq!(path, "/database/pool/size");
+            assert!(source.to_string().contains("invalid type"), "{source}");
+        }
+        other => panic!("expected a deserialization error, got {other:?}"),
+    }
+    let message = err.to_string();
+    assert!(
+        message.contains("`/database/pool/size`"),
+        "error should name the failing path: {message}"
+    );
+    // serde's error stays reachable through the standard error chain
+    let source = std::error::Error::source(&err).expect("serde error as source");
+    assert!(source.is::<serde_json::Error>());
+}
+
+#[test]
+fn test_from_value_reports_json_pointer() {
+    let err = gonfig::from_value::<std::collections::HashMap<String, Vec<u16>>>(
+        serde_json::json!({ "ports": [80, "http"] }),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::Deserialize { ref path, .. } if path == "/ports/1"),
+        "{err}"
+    );
+
+    let err = gonfig::from_value::<NestedAppConfig>(serde_json::json!([])).unwrap_err();
+    assert!(
+        matches!(err, Error::Deserialize { ref path, .. } if path.is_empty()),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_builder_build_with_seed() {
+    use serde::de::{DeserializeSeed, Deserializer};
+
+    struct PortOffset(u16);
+
+    impl<'de> DeserializeSeed<'de> for PortOffset {
+        type Value = u16;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<u16, D::Error> {
+            #[derive(Deserialize)]
+            struct Raw {
+                port: u16,
+            }
+            Ok(Raw::deserialize(deserializer)?.port + self.0)
+        }
+    }
+
+    let port = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 8000 }))
+        .unwrap()
+        .build_with_seed(PortOffset(80))
+        .unwrap();
+
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn test_builder_sources_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "port = 3000")?;
+
+    let builder = ConfigBuilder::new()
+        .with_file_format(file.path(), ConfigFormat::Toml)?
+        .with_file_optional("/non/existent/summary.toml")?
+        .with_env("SUMMARY");
+
+    let summary = builder.sources_summary();
+
+    assert!(summary.starts_with("Configuration sources (3"));
+    assert!(summary.contains(&format!("file {} (exists)", file.path().display())));
+    assert!(summary.contains("/non/existent/summary.toml (missing, optional)"));
+    assert!(summary.contains("ConfigFile   priority=2"));
+    assert!(summary.contains("Environment  priority=3  env prefix SUMMARY_"));
+    Ok(())
+}
+
+#[test]
+fn test_builder_sources_are_boxed_in_merge_order() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = ConfigBuilder::new()
+        .with_env("BOXEDSOURCES")
+        .with_defaults(serde_json::json!({ "port": 1 }))?;
+
+    let sources: &[Box<dyn ConfigSource>] = builder.sources();
+    assert_eq!(sources.len(), 2);
+    assert_eq!(sources[0].source_type(), Source::Default);
+    assert!(sources[1]
+        .as_any()
+        .downcast_ref::<gonfig::Environment>()
+        .is_some());
+    assert!(builder.get_source::<gonfig::Environment>().is_some());
+    Ok(())
+}
+
+#[test]
+fn test_builder_deep_prefer_existing_keeps_file_value() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::new()?;
+    writeln!(
+        file,
+        r#"
+database_url = "postgres://fromfile"
+port = 3000
+"#
+    )?;
+
+    env::set_var("PREFER_DATABASE_URL", "postgres://fromenv");
+    env::set_var("PREFER_DEBUG", "true");
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_merge_strategy(MergeStrategy::DeepPreferExisting)
+        .with_file_format(file.path(), ConfigFormat::Toml)?
+        .with_env("PREFER")
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://fromfile");
+    assert_eq!(config.port, 3000);
+    assert!(config.debug); // Only provided by env, so it fills the gap
+
+    env::remove_var("PREFER_DATABASE_URL");
+    env::remove_var("PREFER_DEBUG");
+    Ok(())
+}
+
+#[test]
+fn test_builder_fallback_builder_defaults_overridden_by_env(
+) -> Result<(), Box<dyn std::error::Error>> {
+    // A library ships a pre-configured builder with its own defaults
+    let library = ConfigBuilder::new().with_defaults(serde_json::json!({
+        "database_url": "postgres://library",
+        "port": 5432,
+        "debug": true
+    }))?;
+
+    env::set_var("FALLBACK_PORT", "7000");
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_env("FALLBACK")
+        .with_fallback_builder(library)
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://library");
+    assert_eq!(config.port, 7000);
+    assert!(config.debug);
+
+    env::remove_var("FALLBACK_PORT");
+    Ok(())
+}
+
+#[test]
+fn test_builder_fallback_builder_beneath_app_defaults() -> Result<(), Box<dyn std::error::Error>> {
+    let library = ConfigBuilder::new().with_defaults(serde_json::json!({
+        "database_url": "postgres://library",
+        "port": 5432
+    }))?;
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_fallback_builder(library)
+        .with_defaults(serde_json::json!({ "port": 8080 }))?
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://library");
+    assert_eq!(config.port, 8080);
+    Ok(())
+}
+
+#[test]
+fn test_builder_fallback_builder_collected_at_build() -> Result<(), Box<dyn std::error::Error>> {
+    // Registering the fallback does not collect it yet
+    let library = ConfigBuilder::new().with_env("LAZYFALLBACK");
+    let app = ConfigBuilder::new().with_fallback_builder(library);
+
+    env::set_var("LAZYFALLBACK_PORT", "6000");
+    let value = app.build_value();
+    env::remove_var("LAZYFALLBACK_PORT");
+    assert_eq!(value?["port"], 6000);
+
+    let failing =
+        ConfigBuilder::new().validate_with(|_| Err(Error::Validation("library rejected".into())));
+    let app = ConfigBuilder::new().with_fallback_builder(failing);
+    let err = app.build_value().unwrap_err();
+    assert!(err.to_string().contains("library rejected"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_builder_cli_env_override_precedence() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("PRECEDENCE_PORT", "7000");
+    env::set_var("PRECEDENCE_DATABASE_URL", "postgres://env");
+
+    let cli = || {
+        Cli::from_vec(vec![
+            "program".to_string(),
+            "--port".to_string(),
+            "9000".to_string(),
+        ])
+    };
+
+    // Default: CLI beats env
+    let config: AppConfig = ConfigBuilder::new()
+        .with_env_flat("PRECEDENCE")
+        .with_cli_custom(cli())
+        .build()?;
+    assert_eq!(config.port, 9000);
+
+    // Override: env beats CLI
+    let config: AppConfig = ConfigBuilder::new()
+        .with_env_flat("PRECEDENCE")
+        .with_cli_custom(cli())
+        .with_cli_env_override(true)
+        .build()?;
+    assert_eq!(config.port, 7000);
+    assert_eq!(config.database_url, "postgres://env");
+
+    env::remove_var("PRECEDENCE_PORT");
+    env::remove_var("PRECEDENCE_DATABASE_URL");
+    Ok(())
+}
+
+#[test]
+fn test_builder_config_reports_source_of_values() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("PROVENANCE_DATABASE_URL", "postgres://env");
+
+    let config = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "database_url": "postgres://default",
+            "port": 8080
+        }))?
+        .with_env_flat("PROVENANCE")
+        .with_cli_custom(Cli::from_vec(vec![
+            "program".to_string(),
+            "--debug".to_string(),
+        ]))
+        .build_config()?;
+
+    assert_eq!(config.source_of("database_url"), Some(Source::Environment));
+    assert_eq!(config.source_of("port"), Some(Source::Default));
+    assert_eq!(config.source_of("debug"), Some(Source::Cli));
+    assert_eq!(config.source_of("missing"), None);
+    assert_eq!(
+        config.get_value("database_url"),
+        Some(serde_json::json!("postgres://env"))
+    );
+
+    env::remove_var("PROVENANCE_DATABASE_URL");
+    Ok(())
+}
+
+#[test]
+fn test_config_try_deserialize_path() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    let config = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "name": "billing",
+            "database": { "host": "db.internal", "port": 5432 }
+        }))?
+        .build_config()?;
+
+    let database: Database = config.try_deserialize_path("database")?;
+    assert_eq!(
+        database,
+        Database {
+            host: "db.internal".to_string(),
+            port: 5432
+        }
+    );
+
+    let whole: serde_json::Value = config.try_deserialize()?;
+    assert_eq!(whole["name"], "billing");
+
+    let err = config
+        .try_deserialize_path::<Database>("cache")
+        .unwrap_err();
+    assert!(matches!(err, Error::Config(_)), "{err}");
+    let err = config.try_deserialize_path::<u16>("database").unwrap_err();
+    assert!(
+        matches!(err, Error::Deserialize { ref path, .. } if path == "/database"),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_multi_document_yaml_merges_in_order() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::new()?;
+    writeln!(
+        file,
+        r#"database_url: postgres://base
+port: 3000
+debug: false
+---
+port: 4000
+debug: true
+---
+"#
+    )?;
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_file_format(file.path(), ConfigFormat::Yaml)?
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://base");
+    assert_eq!(config.port, 4000);
+    assert!(config.debug);
+    Ok(())
+}
+
+/// Source that counts how often it is collected.
+struct CountingSource {
+    collects: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl ConfigSource for CountingSource {
+    fn source_type(&self) -> Source {
+        Source::ConfigFile
+    }
+
+    fn collect(&self) -> gonfig::Result<serde_json::Value> {
+        self.collects
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(serde_json::json!({ "database_url": "postgres://counted", "port": 1234 }))
+    }
+
+    fn has_value(&self, _key: &str) -> bool {
+        false
+    }
+
+    fn get_value(&self, _key: &str) -> Option<serde_json::Value> {
+        None
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_builder_cache_sources_collects_once() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let collects = Arc::new(AtomicUsize::new(0));
+    let builder = ConfigBuilder::new()
+        .add_source(Box::new(CountingSource {
+            collects: Arc::clone(&collects),
+        }))
+        .cache_sources(true);
+
+    let preview = builder.collect_value()?;
+    assert_eq!(preview["port"], 1234);
+    let config: AppConfig = builder.build()?;
+    assert_eq!(config.port, 1234);
+    assert_eq!(collects.load(Ordering::SeqCst), 1);
+
+    // Without caching every call collects again
+    let uncached_collects = Arc::new(AtomicUsize::new(0));
+    let builder = ConfigBuilder::new().add_source(Box::new(CountingSource {
+        collects: Arc::clone(&uncached_collects),
+    }));
+    builder.collect_value()?;
+    builder.build_value()?;
+    assert_eq!(uncached_collects.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[test]
+fn test_builder_provided_paths_skip_defaults_and_share_cache(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let collects = Arc::new(AtomicUsize::new(0));
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "debug": true }))?
+        .add_source(Box::new(CountingSource {
+            collects: Arc::clone(&collects),
+        }))
+        .cache_sources(true);
+
+    assert_eq!(builder.provided_paths()?, vec!["database_url", "port"]);
+    let config: AppConfig = builder.build()?;
+    assert!(config.debug);
+    assert_eq!(collects.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn test_builder_strict_types_reports_type_mismatch() {
+    env::set_var("STRICTTYPES_DATABASE_URL", "postgres://strict");
+    env::set_var("STRICTTYPES_PORT", "8080");
+
+    let err = ConfigBuilder::new()
+        .with_env_flat("STRICTTYPES")
+        .strict_types(true)
+        .build::<AppConfig>()
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("`/port`"), "unexpected message: {message}");
+    assert!(
+        message.contains("expected u16"),
+        "unexpected message: {message}"
+    );
+
+    // Without strict mode the value is coerced
+    let config: AppConfig = ConfigBuilder::new()
+        .with_env_flat("STRICTTYPES")
+        .build()
+        .unwrap();
+    assert_eq!(config.port, 8080);
+
+    env::remove_var("STRICTTYPES_DATABASE_URL");
+    env::remove_var("STRICTTYPES_PORT");
+}
+
+#[test]
+fn test_builder_layered_files_mixed_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.toml");
+    let overlay = dir.path().join("override.yaml");
+    std::fs::write(
+        &base,
+        "database_url = \"postgres://base\"\nport = 3000\ndebug = false\n",
+    )?;
+    std::fs::write(&overlay, "port: 4000\ndebug: true\n")?;
+
+    // Listed out of order: the priority decides the winner
+    let config: AppConfig = ConfigBuilder::new()
+        .with_layered_files(&[(&overlay, 10), (&base, 0)])?
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://base");
+    assert_eq!(config.port, 4000);
+    assert!(config.debug);
+    Ok(())
+}
+
+#[test]
+fn test_builder_errors_name_failing_phase() {
+    let err = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "database_url": "x", "port": "nope", "debug": false }))
+        .unwrap()
+        .build::<AppConfig>()
+        .unwrap_err();
+    assert!(matches!(err, Error::Deserialize { ref path, .. } if path == "/port"));
+    let message = err.to_string();
+    assert!(
+        message.starts_with("Deserialization error at `/port`"),
+        "unexpected message: {message}"
+    );
+    assert!(
+        message.contains("during deserialization"),
+        "unexpected message: {message}"
+    );
+
+    let err = ConfigBuilder::new()
+        .validate_with(|_| Err(Error::Validation("always fails".to_string())))
+        .build_value()
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.contains("during validation: always fails"),
+        "unexpected message: {message}"
+    );
+}
+
+#[test]
+fn test_builder_skip_files_env() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("skipbase.json");
+    let overrides = dir.path().join("skipoverride.json");
+    std::fs::write(&base, r#"{ "port": 8080, "host": "base" }"#)?;
+    std::fs::write(&overrides, r#"{ "port": 9090 }"#)?;
+    let builder = || -> gonfig::Result<ConfigBuilder> {
+        ConfigBuilder::new().with_file(&base)?.with_file(&overrides)
+    };
+
+    env::set_var(gonfig::builder::SKIP_FILES_VAR, "skipoverride.json");
+    let value = builder()?.build_value()?;
+    assert_eq!(value, serde_json::json!({ "port": 8080, "host": "base" }));
+
+    env::set_var(
+        gonfig::builder::SKIP_FILES_VAR,
+        " skipbase.json , skipoverride.json ",
+    );
+    assert_eq!(builder()?.build_value()?, serde_json::json!({}));
+
+    env::remove_var(gonfig::builder::SKIP_FILES_VAR);
+    let value = builder()?.build_value()?;
+    assert_eq!(value, serde_json::json!({ "port": 9090, "host": "base" }));
+
+    // A skipped file is not parsed when it is added, so a broken one can be
+    // left out while bisecting
+    let broken = dir.path().join("skipbroken.json");
+    std::fs::write(&broken, "{ not json")?;
+    assert!(ConfigBuilder::new().with_file(&broken).is_err());
+    env::set_var(gonfig::builder::SKIP_FILES_VAR, "skipbroken.json");
+    let value = ConfigBuilder::new()
+        .with_file(&base)?
+        .with_file(&broken)?
+        .build_value();
+    env::remove_var(gonfig::builder::SKIP_FILES_VAR);
+    assert_eq!(value?, serde_json::json!({ "port": 8080, "host": "base" }));
+    Ok(())
+}
+
+#[test]
+fn test_builder_xdg_config() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir(dir.path().join("xdgtool"))?;
+    std::fs::write(
+        dir.path().join("xdgtool").join("config.toml"),
+        "database_url = \"postgres://xdg\"\nport = 7000\ndebug = true\n",
+    )?;
+
+    let previous = env::var_os("XDG_CONFIG_HOME");
+    env::set_var("XDG_CONFIG_HOME", dir.path());
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_xdg_config("xdgtool", "config.toml")?
+        .build()?;
+    // A missing file is skipped
+    let missing = ConfigBuilder::new()
+        .with_xdg_config("xdgtool", "absent.toml")?
+        .build_value()?;
+
+    match previous {
+        Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+        None => env::remove_var("XDG_CONFIG_HOME"),
+    }
+
+    assert_eq!(config.database_url, "postgres://xdg");
+    assert_eq!(config.port, 7000);
+    assert_eq!(missing, serde_json::json!({}));
+    Ok(())
+}
+
+#[test]
+fn test_builder_interpolates_templated_yaml() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("templated.yaml");
+    std::fs::write(
+        &path,
+        "database_url: postgres://${DB_HOST}/app?note=$${KEEP}\nport: ${PORT}\ndebug: false\n",
+    )?;
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_interpolation_env([("DB_HOST", "db.internal"), ("PORT", "6543")])
+        .with_file(&path)?
+        .build()?;
+    assert_eq!(
+        config.database_url,
+        "postgres://db.internal/app?note=${KEEP}"
+    );
+    assert_eq!(config.port, 6543);
+
+    // Undefined variables fail by default
+    let result = ConfigBuilder::new()
+        .with_interpolation_env([("DB_HOST", "db.internal")])
+        .with_file(&path);
+    match result {
+        Err(Error::Config(msg)) => assert!(msg.contains("PORT"), "unexpected message: {msg}"),
+        Err(e) => panic!("unexpected error: {e}"),
+        Ok(_) => panic!("expected an undefined variable error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_builder_validate_required_paths() {
+    let result = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "database": { "pool": 5 } }))
+        .unwrap()
+        .validate_required(&["database.url", "database.pool"])
+        .build_value();
+    match result {
+        Err(Error::Validation(msg)) => {
+            assert!(msg.contains("database.url"), "unexpected message: {msg}");
+            assert!(!msg.contains("database.pool"), "unexpected message: {msg}");
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "database": { "url": "postgres://x" } }))
+        .unwrap()
+        .validate_required(&["database.url"])
+        .build_value()
+        .unwrap();
+    assert_eq!(value["database"]["url"], "postgres://x");
+}
+
+#[derive(Debug, Deserialize)]
+struct PortView {
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseView {
+    database_url: String,
+}
+
+#[test]
+fn test_builder_build_as_collects_once() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let collects = Arc::new(AtomicUsize::new(0));
+    let builder = ConfigBuilder::new().add_source(Box::new(CountingSource {
+        collects: Arc::clone(&collects),
+    }));
+
+    let ports: PortView = builder.build_as()?;
+    let database: DatabaseView = builder.build_as()?;
+    assert_eq!(ports.port, 1234);
+    assert_eq!(database.database_url, "postgres://counted");
+    assert_eq!(collects.load(Ordering::SeqCst), 1);
+
+    let (ports, database): (PortView, DatabaseView) = ConfigBuilder::new()
+        .add_source(Box::new(CountingSource {
+            collects: Arc::clone(&collects),
+        }))
+        .build_all()?;
+    assert_eq!(ports.port, 1234);
+    assert_eq!(database.database_url, "postgres://counted");
+    assert_eq!(collects.load(Ordering::SeqCst), 2);
+
+    // The snapshot of `build_as` is not reused by a later `cache_sources` build
+    let builder = ConfigBuilder::new().add_source(Box::new(CountingSource {
+        collects: Arc::clone(&collects),
+    }));
+    let _: PortView = builder.build_as()?;
+    let builder = builder.cache_sources(true);
+    builder.collect_value()?;
+    builder.build_value()?;
+    assert_eq!(collects.load(Ordering::SeqCst), 4);
+    Ok(())
+}
+
+#[test]
+fn test_builder_profile_selector_loads_overlay() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(
+        dir.path().join("config.toml"),
+        "database_url = \"postgres://base\"\nport = 3000\ndebug = true\n",
+    )?;
+    std::fs::write(dir.path().join("config.prod.toml"), "debug = false\n")?;
+    env::set_var("PROFILESEL_ENV", "prod");
+
+    let template = dir.path().join("config.{profile}.toml");
+    let config: AppConfig = ConfigBuilder::new()
+        .with_file(dir.path().join("config.toml"))?
+        .with_env("PROFILESEL")
+        .with_profile_selector(template.to_str().unwrap(), |merged| {
+            merged["env"].as_str().map(str::to_string)
+        })?
+        .build()?;
+
+    assert_eq!(config.database_url, "postgres://base");
+    assert!(!config.debug);
+
+    env::remove_var("PROFILESEL_ENV");
+    Ok(())
+}
+
+#[test]
+fn test_builder_missing_file_error_names_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("does-not-exist.toml");
+
+    let err = ConfigBuilder::new().with_file(&path).err().unwrap();
+    let message = err.to_string();
+    assert!(
+        message.contains(&path.display().to_string()),
+        "unexpected message: {message}"
+    );
+    match err {
+        Error::Io {
+            path: Some(err_path),
+            source,
+        } => {
+            assert_eq!(err_path, path);
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("expected an I/O error, got {other:?}"),
+    }
+
+    let err = ConfigBuilder::new()
+        .with_file_format(&path, ConfigFormat::Json)
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::Io { path: Some(_), .. }));
+}
+
+#[test]
+fn test_builder_required_env_lists_missing() {
+    env::set_var("REQENV_PRESENT", "");
+    env::remove_var("REQENV_MISSING_A");
+    env::remove_var("REQENV_MISSING_B");
+
+    let result = ConfigBuilder::new()
+        .with_required_env(&["REQENV_PRESENT", "REQENV_MISSING_A"])
+        .with_required_env(&["REQENV_MISSING_B"])
+        .build_value();
+    match result {
+        Err(Error::Environment(msg)) => {
+            assert!(
+                msg.contains("REQENV_MISSING_A, REQENV_MISSING_B"),
+                "unexpected message: {msg}"
+            );
+            assert!(!msg.contains("REQENV_PRESENT"), "unexpected message: {msg}");
+        }
+        other => panic!("expected an environment error, got {other:?}"),
+    }
+
+    assert!(ConfigBuilder::new()
+        .with_required_env(&["REQENV_PRESENT"])
+        .build_value()
+        .is_ok());
+    env::remove_var("REQENV_PRESENT");
+}
+
+#[test]
+fn test_builder_env_configured_nested() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("ENVCONF__HTTP__PORT", "9000");
+    env::set_var("ENVCONF__HTTP__HOST", "0.0.0.0");
+
+    let value = ConfigBuilder::new()
+        .with_env_configured("ENVCONF", |env| env.separator("__").nested(true))
+        .build_value()?;
+    assert_eq!(value["http"]["port"], 9000);
+    assert_eq!(value["http"]["host"], "0.0.0.0");
+
+    env::remove_var("ENVCONF__HTTP__PORT");
+    env::remove_var("ENVCONF__HTTP__HOST");
+    Ok(())
+}
+
+#[test]
+fn test_builder_case_insensitive_file_values() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Mode {
+        Dev,
+        Prod,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ModeConfig {
+        mode: Mode,
+        debug: bool,
+    }
+
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile()?;
+    writeln!(file, "mode: PROD\ndebug: \"False\"")?;
+
+    let config: ModeConfig = ConfigBuilder::new()
+        .with_file(file.path())?
+        .case_insensitive_enum("mode", &["Dev", "Prod"])
+        .case_insensitive_bool("debug")
+        .build()?;
+    assert_eq!(config.mode, Mode::Prod);
+    assert!(!config.debug);
+
+    env::set_var("CASEINS_MODE", "dev");
+    let config: ModeConfig = ConfigBuilder::new()
+        .with_file(file.path())?
+        .with_env("CASEINS")
+        .case_insensitive_enum("mode", &["Dev", "Prod"])
+        .case_insensitive_bool("debug")
+        .build()?;
+    assert_eq!(config.mode, Mode::Dev);
+    env::remove_var("CASEINS_MODE");
+    Ok(())
+}
+
+struct SlowRemoteSource {
+    delay: std::time::Duration,
+}
+
+impl ConfigSource for SlowRemoteSource {
+    fn source_type(&self) -> Source {
+        Source::Remote
+    }
+
+    fn collect(&self) -> gonfig::Result<serde_json::Value> {
+        std::thread::sleep(self.delay);
+        Ok(serde_json::json!({ "remote": true }))
+    }
+
+    fn has_value(&self, _key: &str) -> bool {
+        false
+    }
+
+    fn get_value(&self, _key: &str) -> Option<serde_json::Value> {
+        None
+    }
+
+    fn describe(&self) -> String {
+        "slow remote".to_string()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_builder_timeout_on_slow_remote_source() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::{Duration, Instant};
+
+    let slow = || {
+        Box::new(SlowRemoteSource {
+            delay: Duration::from_secs(2),
+        })
+    };
+
+    let started = Instant::now();
+    let result = ConfigBuilder::new()
+        .add_source(slow())
+        .with_timeout(Duration::from_millis(50))
+        .build_value();
+    assert!(started.elapsed() < Duration::from_secs(1));
+    match result {
+        Err(Error::Timeout {
+            source_name,
+            timeout,
+        }) => {
+            assert_eq!(source_name, "slow remote");
+            assert_eq!(timeout, Duration::from_millis(50));
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "remote": false }))?
+        .add_source(slow())
+        .with_timeout(Duration::from_millis(50))
+        .resilient(true)
+        .build_value()?;
+    assert_eq!(value["remote"], false);
+
+    let value = ConfigBuilder::new()
+        .add_source(Box::new(SlowRemoteSource {
+            delay: Duration::from_millis(1),
+        }))
+        .with_timeout(Duration::from_secs(5))
+        .build_value()?;
+    assert_eq!(value["remote"], true);
+    Ok(())
+}
+
+#[test]
+fn test_builder_on_reload_receives_updated_config() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Deserialize)]
+    struct ReloadConfig {
+        port: u16,
+        name: String,
+    }
+
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile()?;
+    writeln!(file, "port: 8080\nname: svc")?;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = Arc::clone(&seen);
+    let mut builder = ConfigBuilder::new()
+        .with_file(file.path())?
+        .on_reload(|_: &ReloadConfig, _| panic!("misbehaving subscriber"))
+        .on_reload(move |config: &ReloadConfig, diff| {
+            recorder
+                .lock()
+                .unwrap()
+                .push((config.port, config.name.clone(), diff.changed.clone()));
+        });
+
+    std::fs::write(file.path(), "port: 9090\nname: svc\n")?;
+    let value = builder.reload()?;
+    assert_eq!(value["port"], 9090);
+
+    std::fs::write(file.path(), "port: 9090\nname: renamed\n")?;
+    builder.reload()?;
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        *seen,
+        vec![
+            (9090, "svc".to_string(), vec!["port".to_string()]),
+            (9090, "renamed".to_string(), vec!["name".to_string()]),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_on_reload_skips_callback_that_fails_to_deserialize(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug, Deserialize)]
+    struct MissingField {
+        #[allow(dead_code)]
+        absent: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct PortConfig {
+        port: u16,
+    }
+
+    let mut file = tempfile::Builder::new().suffix(".yaml").tempfile()?;
+    writeln!(file, "port: 8080")?;
+
+    let last_port = Arc::new(AtomicUsize::new(0));
+    let recorder = Arc::clone(&last_port);
+    let mut builder = ConfigBuilder::new()
+        .with_file(file.path())?
+        .on_reload(|_: &MissingField, _| unreachable!("config has no `absent` field"))
+        .on_reload(move |config: &PortConfig, _| {
+            recorder.store(config.port.into(), Ordering::SeqCst);
+        });
+
+    std::fs::write(file.path(), "port: 9090\n")?;
+    let value = builder.reload()?;
+    assert_eq!(value["port"], 9090);
+    assert_eq!(last_port.load(Ordering::SeqCst), 9090);
+    assert_eq!(builder.build_value()?["port"], 9090);
+    Ok(())
+}
+
+#[test]
+fn test_builder_build_with_provenance() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+    writeln!(
+        file,
+        r#"{{ "database": {{ "pool": {{ "maxsize": 10, "timeout": 5 }} }}, "name": "file" }}"#
+    )?;
+    env::set_var("PROVAPP_DATABASE_POOL_MAXSIZE", "32");
+
+    let (value, provenance) = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "name": "default", "debug": false }))?
+        .with_file(file.path())?
+        .with_env_custom(
+            gonfig::Environment::new()
+                .with_prefix("PROVAPP")
+                .nested(true),
+        )
+        .build_with_provenance()?;
+
+    assert_eq!(value["database"]["pool"]["maxsize"], 32);
+    assert_eq!(provenance["database.pool.maxsize"], Source::Environment);
+    assert_eq!(provenance["database.pool.timeout"], Source::ConfigFile);
+    assert_eq!(provenance["name"], Source::ConfigFile);
+    assert_eq!(provenance["debug"], Source::Default);
+    assert_eq!(provenance.len(), 4);
+
+    env::remove_var("PROVAPP_DATABASE_POOL_MAXSIZE");
+    Ok(())
+}
+
+#[test]
+fn test_builder_provenance_follows_merge_not_values() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = || {
+        ConfigBuilder::new()
+            .with_defaults(serde_json::json!({ "port": 8080, "host": "localhost" }))
+            .map(|builder| {
+                builder.with_env_custom(
+                    gonfig::Environment::new()
+                        .override_with("PORT", "8080")
+                        .override_with("HOST", "example.com"),
+                )
+            })
+    };
+
+    // Both sources hold the same port; the environment wrote it last
+    let (_, provenance) = builder()?.build_with_provenance()?;
+    assert_eq!(provenance["port"], Source::Environment);
+
+    // Keeping existing values, the defaults win even where values are equal
+    let config = builder()?
+        .with_merge_strategy(MergeStrategy::DeepPreferExisting)
+        .build_config()?;
+    assert_eq!(config.source_of("port"), Some(Source::Default));
+    assert_eq!(config.source_of("host"), Some(Source::Default));
+    Ok(())
+}
+
+#[test]
+fn test_builder_provenance_follows_path_strategies() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+    writeln!(
+        file,
+        r#"{{ "server": {{ "port": 9000, "host": "example.com" }}, "limits": {{ "rps": 5 }} }}"#
+    )?;
+
+    let (value, provenance) = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "server": { "port": 8080 },
+            "limits": { "rps": 1, "burst": 10 }
+        }))?
+        .with_file(file.path())?
+        .with_merge_strategy_at("server", MergeStrategy::DeepPreferExisting)
+        .with_merge_strategy_at("limits", MergeStrategy::Replace)
+        .build_with_provenance()?;
+
+    assert_eq!(value["server"]["port"], 8080);
+    assert_eq!(provenance["server.port"], Source::Default);
+    assert_eq!(provenance["server.host"], Source::ConfigFile);
+    assert_eq!(value["limits"], serde_json::json!({ "rps": 5 }));
+    assert_eq!(provenance["limits.rps"], Source::ConfigFile);
+    assert!(!provenance.contains_key("limits.burst"));
+    Ok(())
+}
+
+#[test]
+fn test_builder_into_parts() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+    writeln!(
+        file,
+        r#"{{ "database_url": "postgres://file", "port": 5432 }}"#
+    )?;
+
+    let parts = ConfigBuilder::new()
+        .with_env_custom(
+            gonfig::Environment::new()
+                .with_prefix("PARTSAPP")
+                .override_with("PARTSAPP_PORT", "6543"),
+        )
+        .with_file(file.path())?
+        .with_defaults(serde_json::json!({ "debug": true }))?
+        .validate_with(|value| match value["port"].as_u64() {
+            Some(port) if port < 6000 => Ok(()),
+            _ => Err(Error::Validation("port too high".to_string())),
+        })
+        .into_parts()?;
+
+    let kinds: Vec<Source> = parts.sources.iter().map(|part| part.source).collect();
+    assert_eq!(
+        kinds,
+        vec![Source::Default, Source::ConfigFile, Source::Environment]
+    );
+    assert!(parts.sources[1].description.starts_with("file "));
+    assert_eq!(parts.sources[2].value, serde_json::json!({ "port": 6543 }));
+    assert_eq!(
+        parts.merged,
+        serde_json::json!({ "database_url": "postgres://file", "port": 6543, "debug": true })
+    );
+    assert!(matches!(parts.validation, Err(Error::Validation(_))));
+    Ok(())
+}
+
+#[test]
+fn test_builder_deep_append_arrays() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::Builder::new().suffix(".json").tempfile()?;
+    writeln!(
+        file,
+        r#"{{ "allowed_hosts": ["a.example", "b.example"], "port": 80 }}"#
+    )?;
+
+    let value = ConfigBuilder::new()
+        .with_file(file.path())?
+        .with_env_custom(
+            gonfig::Environment::new()
+                .with_prefix("APPENDAPP")
+                .override_with("APPENDAPP_ALLOWED_HOSTS", r#"["b.example", "c.example"]"#),
+        )
+        .with_merge_strategy(MergeStrategy::DeepAppendArrays { dedup: true })
+        .build_value()?;
+    assert_eq!(
+        value["allowed_hosts"],
+        serde_json::json!(["a.example", "b.example", "c.example"])
+    );
+    assert_eq!(value["port"], 80);
+
+    let err = ConfigBuilder::new()
+        .with_file(file.path())?
+        .with_env_custom(
+            gonfig::Environment::new()
+                .with_prefix("APPENDAPP")
+                .override_with("APPENDAPP_ALLOWED_HOSTS", "c.example"),
+        )
+        .with_merge_strategy(MergeStrategy::DeepAppendArrays { dedup: true })
+        .build_value()
+        .unwrap_err();
+    assert!(matches!(err, Error::MergeConflict(_)), "{err}");
+    assert!(err.to_string().contains("`allowed_hosts`"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_builder_ini_file() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Deserialize)]
+    struct IniConfig {
+        name: String,
+        database: IniDatabase,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct IniDatabase {
+        host: String,
+        port: u16,
+        replica: IniReplica,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct IniReplica {
+        enabled: bool,
+        label: String,
+    }
+
+    let mut file = tempfile::Builder::new().suffix(".ini").tempfile()?;
+    writeln!(
+        file,
+        r#"; legacy service config
+name = billing
+
+[database]
+host = localhost ; inline comment
+port = 5432
+host = db.internal
+
+[database.replica]
+enabled = true
+label = "5432"
+
+[database]
+port = 6543"#
+    )?;
+
+    let config: IniConfig = ConfigBuilder::new()
+        .with_file_format(file.path(), ConfigFormat::Ini)?
+        .build()?;
+    assert_eq!(config.name, "billing");
+    assert_eq!(config.database.host, "db.internal");
+    assert_eq!(config.database.port, 6543);
+    assert!(config.database.replica.enabled);
+    assert_eq!(config.database.replica.label, "5432");
+
+    // Detected from the extension as well
+    let value = ConfigBuilder::new().with_file(file.path())?.build_value()?;
+    assert_eq!(value["database"]["port"], 6543);
+    Ok(())
+}
+
+#[test]
+fn test_ini_format_round_trip_and_errors() {
+    let value = serde_json::json!({
+        "name": "billing",
+        "port": 80,
+        "database": { "host": "localhost", "port": "5432", "tags": ["a", "b"] }
+    });
+    let rendered = ConfigFormat::Ini.serialize(&value).unwrap();
+    assert_eq!(ConfigFormat::Ini.parse(&rendered).unwrap(), value);
+
+    let err = ConfigFormat::Ini.parse("[database\nhost = x").unwrap_err();
+    assert!(err.to_string().contains("line 1"), "{err}");
+    let err = ConfigFormat::Ini
+        .parse("database = x\n[database]\nhost = y")
+        .unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{err}");
+}
+
+#[test]
+fn test_builder_with_file_detects_format_from_extension() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut yml = tempfile::Builder::new().suffix(".yml").tempfile()?;
+    writeln!(yml, "port: 7000\nname: detected")?;
+    let value = ConfigBuilder::new().with_file(yml.path())?.build_value()?;
+    assert_eq!(value["port"], 7000);
+    assert_eq!(value["name"], "detected");
+
+    let mut conf = tempfile::Builder::new().suffix(".conf").tempfile()?;
+    writeln!(conf, "port = 7000")?;
+    let err = ConfigBuilder::new().with_file(conf.path()).err().unwrap();
+    assert!(
+        matches!(err, Error::UnknownFormat { ref extension, .. } if extension == "conf"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("\"conf\""), "{err}");
+
+    let err = ConfigBuilder::new().with_file("Configfile").err().unwrap();
+    assert!(
+        matches!(err, Error::UnknownFormat { ref extension, .. } if extension.is_empty()),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_ini_section() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Deserialize)]
+    struct BillingConfig {
+        host: String,
+        port: u16,
+        #[serde(default)]
+        name: Option<String>,
+    }
+
+    let mut file = tempfile::Builder::new().suffix(".conf").tempfile()?;
+    writeln!(
+        file,
+        "name = shared\n\n[billing]\nhost = billing.internal\nport = 7000\n\n[search]\nhost = search.internal\nport = 9200"
+    )?;
+
+    let value = ConfigBuilder::new()
+        .with_file_ini_section(file.path(), "billing")?
+        .build_value()?;
+    assert_eq!(
+        value,
+        serde_json::json!({ "host": "billing.internal", "port": 7000 })
+    );
+    let config: BillingConfig = serde_json::from_value(value)?;
+    assert_eq!(config.host, "billing.internal");
+    assert_eq!(config.port, 7000);
+    assert_eq!(config.name, None);
+
+    let err = ConfigBuilder::new()
+        .with_file_ini_section(file.path(), "payments")
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string().contains("Section `payments` not found"),
+        "{err}"
+    );
+    let err = ConfigBuilder::new()
+        .with_file_ini_section(file.path(), "name")
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("not a section"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_builder_merge_strategy_per_source_type() -> Result<(), Box<dyn std::error::Error>> {
+    let builder = || -> gonfig::Result<ConfigBuilder> {
+        Ok(ConfigBuilder::new()
+            .with_defaults(serde_json::json!({
+                "database": { "url": "postgres://default", "pool": 4 },
+                "port": 8080
+            }))?
+            .with_env_configured("PERSOURCE", |env| {
+                env.nested(true)
+                    .override_with("PERSOURCE_DATABASE_POOL", "8")
+            })
+            .with_cli_custom(Cli::from_vec(vec![
+                "program".to_string(),
+                "--port".to_string(),
+                "9000".to_string(),
+            ]))
+            .with_merge_strategy(MergeStrategy::Replace)
+            .merge_strategy_for(Source::Environment, MergeStrategy::Deep))
+    };
+
+    // Env deep-merges into the defaults, the CLI falls back to Replace
+    let value = builder()?.build_value()?;
+    assert_eq!(value, serde_json::json!({ "port": 9000 }));
+
+    // Without the CLI replacing, the deep-merged env result shows through
+    let value = builder()?
+        .merge_strategy_for(Source::Cli, MergeStrategy::Deep)
+        .build_value()?;
+    assert_eq!(value["database"]["url"], "postgres://default");
+    assert_eq!(value["database"]["pool"], 8);
+    assert_eq!(value["port"], 9000);
+    Ok(())
+}
+
+#[test]
+fn test_builder_with_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let write = |name: &str, content: &str| std::fs::write(dir.path().join(name), content);
+    write("20-override.yaml", "port: 9000\nlog:\n  level: debug\n")?;
+    write(
+        "10-base.yml",
+        "port: 8080\nname: base\nlog:\n  level: info\n",
+    )?;
+    write(".hidden.yaml", "name: hidden\n")?;
+    write("notes.toml", "name = \"toml\"\n")?;
+    std::fs::create_dir(dir.path().join("99-nested"))?;
+    write("99-nested/extra.yaml", "name: nested\n")?;
+
+    let value = ConfigBuilder::new()
+        .with_directory(dir.path(), ConfigFormat::Yaml)?
+        .build_value()?;
+    assert_eq!(value["port"], 9000);
+    assert_eq!(value["name"], "base");
+    assert_eq!(value["log"]["level"], "debug");
+
+    let value = ConfigBuilder::new()
+        .with_directory_recursive(dir.path(), ConfigFormat::Yaml)?
+        .build_value()?;
+    assert_eq!(value["name"], "nested");
+    assert_eq!(value["port"], 9000);
+
+    let err = ConfigBuilder::new()
+        .with_directory(dir.path().join("missing"), ConfigFormat::Yaml)
+        .err()
+        .unwrap();
+    assert!(matches!(err, Error::Io { path: Some(_), .. }), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_builder_with_fragment() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = NamedTempFile::with_suffix(".json")?;
+    writeln!(file, r#"{{ "name": "cache", "ttl": 60 }}"#)?;
+
+    env::set_var("FRAGMENTAUTH_NAME", "auth");
+    env::set_var("FRAGMENTAUTH_ENABLED", "true");
+
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "name": "host" }))?
+        .with_fragment("cache", Box::new(gonfig::Config::from_file(file.path())?))
+        .with_fragment(
+            "plugins.auth",
+            Box::new(gonfig::Environment::new().with_prefix("FRAGMENTAUTH")),
+        );
+    assert!(builder.sources_summary().contains("under `plugins.auth`"));
+
+    let value = builder.build_value()?;
+    assert_eq!(value["name"], "host");
+    assert_eq!(value["cache"]["name"], "cache");
+    assert_eq!(value["cache"]["ttl"], 60);
+    assert_eq!(value["plugins"]["auth"]["name"], "auth");
+    assert_eq!(value["plugins"]["auth"]["enabled"], true);
+
+    env::remove_var("FRAGMENTAUTH_NAME");
+    env::remove_var("FRAGMENTAUTH_ENABLED");
+    Ok(())
+}
+
+#[test]
+fn test_builder_with_env_nests_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("NESTDEF_HTTP_PORT", "9000");
+    env::set_var("NESTDEF_NAME", "svc");
+
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "http": { "host": "localhost", "port": 80 } }))?
+        .with_env("NESTDEF")
+        .build_value()?;
+    assert_eq!(value["http"]["port"], 9000);
+    assert_eq!(value["http"]["host"], "localhost");
+    assert_eq!(value["name"], "svc");
+    assert!(value.get("http_port").is_none());
+
+    let value = ConfigBuilder::new()
+        .with_env_flat("NESTDEF")
+        .build_value()?;
+    assert_eq!(value["http_port"], 9000);
+    assert!(value.get("http").is_none());
+
+    env::remove_var("NESTDEF_HTTP_PORT");
+    env::remove_var("NESTDEF_NAME");
+    Ok(())
+}
+
+#[test]
+fn test_builder_get_single_value() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("GETAPP_HTTP_PORT", "9000");
+    let builder = || {
+        ConfigBuilder::new()
+            .with_defaults(serde_json::json!({ "http": { "host": "localhost", "port": 80 } }))
+            .map(|builder| builder.with_env("GETAPP"))
+    };
+
+    assert_eq!(builder()?.get::<u16>("http.port")?, Some(9000));
+    assert_eq!(
+        builder()?.get::<String>("http.host")?.as_deref(),
+        Some("localhost")
+    );
+    assert_eq!(builder()?.get::<u16>("http.timeout")?, None);
+    assert_eq!(builder()?.get::<u16>("missing.port")?, None);
+
+    match builder()?.get::<Vec<String>>("http.port") {
+        Err(Error::Deserialize { path, .. }) => assert_eq!(path, "/http/port"),
+        other => panic!("expected a deserialization error, got {other:?}"),
+    }
+
+    env::remove_var("GETAPP_HTTP_PORT");
+    Ok(())
+}
+
+#[test]
+fn test_builder_value_parser_runs_per_source() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::Builder::new().suffix(".toml").tempfile()?;
+    writeln!(file, "size = 10\nlabel = 1.50")?;
+
+    let parse_size = |text: &str| -> Result<serde_json::Value, String> {
+        let megabytes: u64 = text
+            .trim_end_matches("MB")
+            .parse()
+            .map_err(|e| format!("`{text}`: {e}"))?;
+        Ok(serde_json::json!(megabytes * 1024 * 1024))
+    };
+    let builder = || -> Result<ConfigBuilder, Error> {
+        Ok(ConfigBuilder::new()
+            .with_file(file.path())?
+            .with_value_parser("size", parse_size)
+            .with_value_parser("label", |text: &str| Ok::<_, String>(text.into())))
+    };
+
+    // A typed file value is passed in its text form
+    let value = builder()?.build_value()?;
+    assert_eq!(value["size"], 10 * 1024 * 1024);
+    assert_eq!(value["label"], "1.5");
+
+    // The environment's text reaches the parser as written
+    let value = builder()?
+        .add_source(Box::new(
+            gonfig::Environment::new()
+                .override_with("SIZE", "20MB")
+                .override_with("LABEL", "1.50"),
+        ))
+        .build_value()?;
+    assert_eq!(value["size"], 20 * 1024 * 1024);
+    assert_eq!(value["label"], "1.50");
+
+    let err = builder()?
+        .add_source(Box::new(
+            gonfig::Environment::new().override_with("SIZE", "big"),
+        ))
+        .build_value()
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Parse { ref path, .. } if path == "size"),
+        "{err}"
+    );
+    Ok(())
+}
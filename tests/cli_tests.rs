@@ -1,4 +1,5 @@
 use gonfig::{Cli, ConfigSource};
+use serde_json::json;
 
 #[test]
 fn test_cli_basic_parsing() {
@@ -108,3 +109,134 @@ fn test_cli_safe_float_parsing() {
         Some("NaN")
     );
 }
+
+#[test]
+fn test_cli_field_mapping_exposes_field_name_and_exclude() {
+    let args = vec![
+        "program".to_string(),
+        "--database-url".to_string(),
+        "postgres://cli".to_string(),
+        "--password".to_string(),
+        "hunter2".to_string(),
+    ];
+
+    let cli = Cli::from_vec(args)
+        .with_field_mapping("database_url", "database-url")
+        .exclude("password");
+
+    // The source itself reports flags as passed
+    let result = cli.collect().unwrap();
+    assert_eq!(result, json!({ "database-url": "postgres://cli" }));
+    assert!(!cli.has_value("password"));
+
+    // The builder merges each mapped value once, under its field name
+    let value = gonfig::ConfigBuilder::new()
+        .with_cli_custom(cli)
+        .build_value()
+        .unwrap();
+    assert_eq!(value, json!({ "database_url": "postgres://cli" }));
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn test_derive_clap_command() -> Result<(), Box<dyn std::error::Error>> {
+    use gonfig::{ConfigBuilder, Gonfig};
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, Gonfig)]
+    #[serde(default)]
+    struct Logging {
+        /// Minimum level to log
+        level: String,
+    }
+
+    #[derive(Debug, Deserialize, Gonfig)]
+    #[Gonfig(env_prefix = "CLAPCMD", allow_cli, clap)]
+    struct Config {
+        /// Port to listen on
+        #[gonfig(default = "8080")]
+        port: u16,
+        #[serde(default)]
+        verbose: bool,
+        #[serde(default)]
+        #[gonfig(cli_name = "host")]
+        hosts: Vec<String>,
+        #[gonfig(sensitive)]
+        #[serde(default)]
+        token: String,
+        #[gonfig(flatten)]
+        #[serde(default)]
+        log: Logging,
+    }
+
+    let mut command = Config::gonfig_clap_command();
+    let help = command.render_help().to_string();
+    assert!(help.contains("--port <PORT>"), "{help}");
+    assert!(help.contains("Port to listen on"), "{help}");
+    assert!(help.contains("[default: 8080]"), "{help}");
+    assert!(help.contains("--level <LEVEL>"), "{help}");
+    assert!(!help.contains("--token"), "{help}");
+
+    // Typed value parsing and unknown flags are reported by clap
+    let err =
+        Config::gonfig_clap_cli(vec!["app".into(), "--port".into(), "http".into()]).unwrap_err();
+    assert!(matches!(err, gonfig::Error::Cli(_)), "{err}");
+    assert!(Config::gonfig_clap_cli(vec!["app".into(), "--token".into(), "x".into()]).is_err());
+
+    let cli = Config::gonfig_clap_cli(
+        [
+            "app",
+            "--verbose",
+            "--host",
+            "a",
+            "--host",
+            "b",
+            "--level",
+            "debug",
+        ]
+        .map(String::from)
+        .to_vec(),
+    )?;
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 9000 }))?
+        .with_cli_custom(cli)
+        .build_value()?;
+    let config: Config = serde_json::from_value(value)?;
+    // The clap default only shows in `--help`; it does not beat lower sources
+    assert_eq!(config.port, 9000);
+    assert!(config.verbose);
+    assert_eq!(config.hosts, ["a", "b"]);
+    assert_eq!(config.log.level, "debug");
+    assert!(config.token.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_cli_repeated_flag_last_wins() {
+    let cli = |args: &[&str]| Cli::from_vec(args.iter().map(|arg| arg.to_string()).collect());
+
+    let parsed = cli(&["app", "--port", "1", "--port", "2", "--port=3"]);
+    assert_eq!(parsed.get_value("port"), Some(json!(3)));
+
+    let parsed = cli(&["app", "--port=3", "--level", "info", "--port", "4"]);
+    assert_eq!(parsed.get_value("port"), Some(json!(4)));
+    assert_eq!(parsed.get_value("level"), Some(json!("info")));
+
+    // Across a long flag and its short alias, the later spelling wins too
+    let parsed = cli(&["app", "-p", "1", "--port", "2"]).with_short_flag('p', "port");
+    assert_eq!(parsed.get_value("port"), Some(json!(2)));
+    let parsed = cli(&["app", "--port", "2", "-p=5"]).with_short_flag('p', "port");
+    assert_eq!(parsed.get_value("port"), Some(json!(5)));
+}
+
+#[test]
+fn test_builder_later_cli_source_wins() -> Result<(), gonfig::Error> {
+    let cli = |args: &[&str]| Cli::from_vec(args.iter().map(|arg| arg.to_string()).collect());
+
+    let value = gonfig::ConfigBuilder::new()
+        .with_cli_custom(cli(&["app", "--port", "1", "--debug"]))
+        .with_cli_custom(cli(&["app", "--port", "2"]))
+        .build_value()?;
+    assert_eq!(value, json!({ "port": 2, "debug": true }));
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use gonfig::Gonfig;
+use gonfig::{ConfigBuilder, Gonfig};
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -105,3 +105,233 @@ fn test_partial_override() {
     assert_eq!(config.port, 8080); // Should use default
     assert!(!config.debug); // Should use default
 }
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct TrimmedDefaultConfig {
+    #[gonfig(
+        env_name = "GONFIG_TEST_TRIMMED_NAME",
+        default = "  padded-service  ",
+        trim_default
+    )]
+    pub service_name: String,
+
+    #[gonfig(env_name = "GONFIG_TEST_UNTRIMMED_NAME", default = "  padded  ")]
+    pub label: String,
+}
+
+#[test]
+fn test_trim_default() {
+    let _cleanup =
+        TestEnvironmentGuard::new(&["GONFIG_TEST_TRIMMED_NAME", "GONFIG_TEST_UNTRIMMED_NAME"]);
+
+    let config = TrimmedDefaultConfig::from_gonfig().unwrap();
+
+    assert_eq!(config.service_name, "padded-service");
+    assert_eq!(config.label, "  padded  ");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct LayeredDefaultConfig {
+    #[gonfig(env_name = "GONFIG_TEST_LAYERED_PORT", default = "8080")]
+    pub port: u16,
+
+    #[gonfig(env_name = "GONFIG_TEST_LAYERED_HOST", default = "\"localhost\"")]
+    pub host: String,
+}
+
+#[test]
+fn test_with_defaults_overrides_attribute_default() {
+    let _cleanup =
+        TestEnvironmentGuard::new(&["GONFIG_TEST_LAYERED_PORT", "GONFIG_TEST_LAYERED_HOST"]);
+
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 9000 }))
+        .unwrap();
+    let config = LayeredDefaultConfig::from_gonfig_with_builder(builder).unwrap();
+
+    // `with_defaults` wins over the attribute default for the same key
+    assert_eq!(config.port, 9000);
+    // Keys it doesn't mention keep their attribute default
+    assert_eq!(config.host, "localhost");
+}
+
+#[test]
+fn test_later_with_defaults_call_wins() {
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 1, "host": "first" }))
+        .unwrap()
+        .with_defaults(serde_json::json!({ "port": 2 }))
+        .unwrap()
+        .build_value()
+        .unwrap();
+
+    assert_eq!(value["port"], 2);
+    assert_eq!(value["host"], "first");
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Dev,
+    Prod,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct StageConfig {
+    #[gonfig(env_name = "GONFIG_TEST_STAGE", default_variant = "Prod")]
+    pub stage: Stage,
+
+    #[gonfig(env_name = "GONFIG_TEST_FALLBACK_STAGE", default_variant = "Dev")]
+    pub fallback_stage: Option<Stage>,
+}
+
+#[test]
+fn test_enum_default_by_variant_name() {
+    let _cleanup = TestEnvironmentGuard::new(&["GONFIG_TEST_STAGE", "GONFIG_TEST_FALLBACK_STAGE"]);
+
+    let config = StageConfig::from_gonfig().unwrap();
+    assert_eq!(config.stage, Stage::Prod);
+    assert_eq!(config.fallback_stage, Some(Stage::Dev));
+
+    // The environment still uses serde's representation
+    env::set_var("GONFIG_TEST_STAGE", "dev");
+    let config = StageConfig::from_gonfig().unwrap();
+    assert_eq!(config.stage, Stage::Dev);
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct AppendDefaultConfig {
+    #[gonfig(
+        env_name = "GONFIG_TEST_APPEND_HOSTS",
+        default = r#"["a"]"#,
+        merge = "append"
+    )]
+    pub hosts: Vec<String>,
+
+    #[gonfig(env_name = "GONFIG_TEST_REPLACE_HOSTS", default = r#"["a"]"#)]
+    pub replaced_hosts: Vec<String>,
+}
+
+#[test]
+fn test_append_merge_keeps_default_array() {
+    let _cleanup =
+        TestEnvironmentGuard::new(&["GONFIG_TEST_APPEND_HOSTS", "GONFIG_TEST_REPLACE_HOSTS"]);
+    env::set_var("GONFIG_TEST_APPEND_HOSTS", r#"["b"]"#);
+    env::set_var("GONFIG_TEST_REPLACE_HOSTS", r#"["b"]"#);
+
+    let config = AppendDefaultConfig::from_gonfig().unwrap();
+    assert_eq!(config.hosts, vec!["a", "b"]);
+    assert_eq!(config.replaced_hosts, vec!["b"]);
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct OptionalDefaultConfig {
+    #[gonfig(env_name = "GONFIG_TEST_NULL_LIMIT", default = "null")]
+    pub limit: Option<u32>,
+
+    #[gonfig(env_name = "GONFIG_TEST_SET_LIMIT", default = "42")]
+    pub fallback_limit: Option<u32>,
+
+    #[gonfig(env_name = "GONFIG_TEST_UNSET_LIMIT")]
+    pub unset_limit: Option<u32>,
+}
+
+#[test]
+fn test_option_default_null_is_explicit() {
+    let _cleanup = TestEnvironmentGuard::new(&[
+        "GONFIG_TEST_NULL_LIMIT",
+        "GONFIG_TEST_SET_LIMIT",
+        "GONFIG_TEST_UNSET_LIMIT",
+    ]);
+
+    let config = OptionalDefaultConfig::from_gonfig().unwrap();
+    assert_eq!(config.limit, None);
+    assert_eq!(config.fallback_limit, Some(42));
+    assert_eq!(config.unset_limit, None);
+
+    // `null` is kept as a value, while a field without a default is absent
+    let defaults = OptionalDefaultConfig::gonfig_default_values().unwrap();
+    assert!(defaults.contains(&("limit".to_string(), serde_json::Value::Null)));
+    assert!(!defaults.iter().any(|(path, _)| path == "unset_limit"));
+    let mut defaults_json = serde_json::json!({});
+    for (path, value) in defaults {
+        gonfig::transform::insert_path(&mut defaults_json, &path, value);
+    }
+    let value = ConfigBuilder::new()
+        .with_field_defaults(defaults_json)
+        .build_value()
+        .unwrap();
+    assert_eq!(value["limit"], serde_json::Value::Null);
+    assert!(value.as_object().unwrap().contains_key("limit"));
+    assert!(!value.as_object().unwrap().contains_key("unset_limit"));
+
+    env::set_var("GONFIG_TEST_NULL_LIMIT", "7");
+    let config = OptionalDefaultConfig::from_gonfig().unwrap();
+    assert_eq!(config.limit, Some(7));
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct BuildInfoConfig {
+    // Set in .cargo/config.toml, so it is visible while this derive expands
+    #[gonfig(
+        env_name = "GONFIG_TEST_APP_VERSION",
+        default_env = "GONFIG_TEST_BUILD_VERSION"
+    )]
+    pub version: String,
+}
+
+#[test]
+fn test_default_env_resolved_at_compile_time() {
+    let _cleanup = TestEnvironmentGuard::new(&["GONFIG_TEST_APP_VERSION"]);
+
+    let config = BuildInfoConfig::from_gonfig().unwrap();
+    assert_eq!(config.version, "1.2.3-test");
+
+    let help = BuildInfoConfig::gonfig_help();
+    assert!(help.contains("1.2.3-test"), "unexpected help: {help}");
+
+    // Like any default, it only applies when no source sets the field
+    env::set_var("GONFIG_TEST_APP_VERSION", "9.9.9");
+    let config = BuildInfoConfig::from_gonfig().unwrap();
+    assert_eq!(config.version, "9.9.9");
+}
+
+#[test]
+fn test_later_with_defaults_call_wins_among_other_sources() {
+    let _cleanup = TestEnvironmentGuard::new(&["GONFIG_TEST_ORDER_PORT", "GONFIG_TEST_ORDER_HOST"]);
+    env::set_var("GONFIG_TEST_ORDER_HOST", "from-env");
+
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 1, "db": { "pool": 1, "name": "first" } }))
+        .unwrap()
+        .with_env("GONFIG_TEST_ORDER")
+        .with_defaults(serde_json::json!({ "port": 2, "host": "default", "db": { "pool": 2 } }))
+        .unwrap()
+        .build_value()
+        .unwrap();
+
+    // The later call wins even though an env source was added in between
+    assert_eq!(value["port"], 2);
+    assert_eq!(value["db"]["pool"], 2);
+    assert_eq!(value["db"]["name"], "first");
+    // Both calls still sit beneath the environment
+    assert_eq!(value["host"], "from-env");
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Name(String);
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+pub struct NewtypeDefaultConfig {
+    // A capitalized default on a newtype is a plain string, not a variant
+    #[gonfig(env_name = "GONFIG_TEST_NEWTYPE_NAME", default = "Alice")]
+    pub name: Name,
+}
+
+#[test]
+fn test_capitalized_default_on_newtype_is_a_string() {
+    let _cleanup = TestEnvironmentGuard::new(&["GONFIG_TEST_NEWTYPE_NAME"]);
+
+    let config = NewtypeDefaultConfig::from_gonfig().unwrap();
+    assert_eq!(config.name, Name("Alice".to_string()));
+}
@@ -0,0 +1,136 @@
+use gonfig::dotenv::DotEnv;
+use gonfig::{ConfigBuilder, ConfigSource, Error, Source};
+use serde::Deserialize;
+use std::env;
+use std::io::Write;
+
+#[derive(Debug, Deserialize)]
+struct DotEnvConfig {
+    database_url: String,
+    port: u16,
+    debug: bool,
+    greeting: String,
+}
+
+#[test]
+fn test_dotenv_parses_quotes_comments_and_exports() {
+    let source = DotEnv::parse(
+        r#"
+# database settings
+export DATABASE_URL=postgres://localhost/app   # trailing comment
+PORT=5432
+DEBUG='true'
+GREETING="hello # not a comment\nworld"
+"#,
+    )
+    .unwrap();
+
+    let value = source.collect().unwrap();
+    assert_eq!(value["database_url"], "postgres://localhost/app");
+    assert_eq!(value["port"], 5432);
+    assert_eq!(value["debug"], true);
+    assert_eq!(value["greeting"], "hello # not a comment\nworld");
+}
+
+#[test]
+fn test_dotenv_rejects_malformed_lines() {
+    match DotEnv::parse("PORT=8080\nnot a pair\n") {
+        Err(Error::Config(msg)) => assert!(msg.contains("line 2"), "unexpected message: {msg}"),
+        other => panic!("expected a config error, got {other:?}"),
+    }
+    assert!(DotEnv::parse("NAME=\"unterminated\n").is_err());
+}
+
+#[test]
+fn test_dotenv_sits_below_real_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = tempfile::NamedTempFile::new()?;
+    writeln!(
+        file,
+        "DOTAPP_DATABASE_URL=postgres://dotenv\nDOTAPP_PORT=5432\nDOTAPP_DEBUG=false\nDOTAPP_GREETING=hi"
+    )?;
+    env::set_var("DOTAPP_PORT", "6543");
+
+    let config: DotEnvConfig = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 1, "greeting": "default" }))?
+        .add_source(Box::new(
+            DotEnv::from_path(file.path())?.with_prefix("DOTAPP"),
+        ))
+        .with_env("DOTAPP")
+        .build()?;
+    assert_eq!(config.database_url, "postgres://dotenv");
+    assert_eq!(config.port, 6543);
+    assert!(!config.debug);
+    assert_eq!(config.greeting, "hi");
+
+    env::remove_var("DOTAPP_PORT");
+    Ok(())
+}
+
+#[test]
+fn test_dotenv_sits_below_config_files() -> Result<(), Box<dyn std::error::Error>> {
+    let mut dotenv = tempfile::NamedTempFile::new()?;
+    writeln!(dotenv, "PORT=5432\nGREETING=from-dotenv")?;
+    let mut file = tempfile::Builder::new().suffix(".toml").tempfile()?;
+    writeln!(file, "port = 7000")?;
+
+    // Registered after the config file, the .env still merges beneath it
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "greeting": "default" }))?
+        .with_file(file.path())?
+        .with_dotenv(dotenv.path())?;
+    let value = builder.build_value()?;
+    assert_eq!(value["port"], 7000);
+    assert_eq!(value["greeting"], "from-dotenv");
+
+    assert!(Source::DotEnv.priority() > Source::Default.priority());
+    assert!(Source::DotEnv.priority() < Source::ConfigFile.priority());
+    Ok(())
+}
+
+#[test]
+fn test_dotenv_missing_file() {
+    match ConfigBuilder::new().with_dotenv("does-not-exist.env") {
+        Err(Error::Io { path, .. }) => {
+            assert_eq!(
+                path.as_deref(),
+                Some(std::path::Path::new("does-not-exist.env"))
+            )
+        }
+        other => panic!("expected an I/O error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_dotenv_auto_without_file() {
+    // The crate root has no `.env`, which `auto` treats as empty
+    let source = DotEnv::auto().unwrap();
+    assert_eq!(source.collect().unwrap(), serde_json::json!({}));
+}
+
+#[test]
+fn test_env_file_override_sits_at_environment_priority() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config_file = tempfile::NamedTempFile::with_suffix(".json")?;
+    writeln!(
+        config_file,
+        r#"{{ "database_url": "postgres://file", "port": 1, "debug": true, "greeting": "file" }}"#
+    )?;
+    let mut overrides = tempfile::NamedTempFile::new()?;
+    writeln!(overrides, "OVERAPP_PORT=9000\nOVERAPP_GREETING=override")?;
+    env::set_var("OVERAPP_GREETING", "exported");
+
+    // The exported variable wins whichever source is added last
+    let config: DotEnvConfig = ConfigBuilder::new()
+        .with_env("OVERAPP")
+        .with_file(config_file.path())?
+        .with_env_file_override(overrides.path(), "OVERAPP")?
+        .build()?;
+    assert_eq!(config.database_url, "postgres://file");
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.greeting, "exported");
+
+    let source = DotEnv::from_path(overrides.path())?.at_env_priority();
+    assert_eq!(source.shadowed_vars(), ["OVERAPP_GREETING"]);
+
+    env::remove_var("OVERAPP_GREETING");
+    Ok(())
+}
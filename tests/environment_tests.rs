@@ -1,4 +1,5 @@
-use gonfig::{ConfigSource, Environment};
+use gonfig::environment::{Case, ListSyntax};
+use gonfig::{ConfigBuilder, ConfigSource, Environment};
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -134,3 +135,765 @@ fn test_environment_overrides() {
 
     env::remove_var("OVERRIDE_TEST");
 }
+
+#[test]
+fn test_environment_overrides_consistent_across_collection_paths() {
+    env::set_var("OVRPATH_PORT", "1111");
+    env::set_var("OVRPATH_HOST", "env-host");
+
+    // Prefix-matching path (no field mappings)
+    let flat = Environment::new()
+        .with_prefix("OVRPATH")
+        .override_with("OVRPATH_PORT", "2222")
+        .override_with("OVRPATH_NAME", "from-override")
+        .collect()
+        .unwrap();
+
+    // Field-mapping path, with only `port` mapped explicitly
+    let mapped = Environment::new()
+        .with_prefix("OVRPATH")
+        .with_field_mapping("port", "OVRPATH_PORT")
+        .override_with("OVRPATH_PORT", "2222")
+        .override_with("OVRPATH_NAME", "from-override")
+        .collect()
+        .unwrap();
+
+    for result in [&flat, &mapped] {
+        assert_eq!(result["port"], 2222);
+        assert_eq!(result["host"], "env-host");
+        assert_eq!(result["name"], "from-override");
+    }
+
+    env::remove_var("OVRPATH_PORT");
+    env::remove_var("OVRPATH_HOST");
+}
+
+#[test]
+fn test_environment_override_key_is_case_insensitive() {
+    let env = Environment::new()
+        .with_prefix("OVRCASE")
+        .override_with("ovrcase_debug", "true");
+
+    assert_eq!(env.get_value("debug"), Some(serde_json::json!(true)));
+    assert_eq!(env.collect().unwrap()["debug"], true);
+}
+
+#[test]
+fn test_environment_trim_values() {
+    env::set_var("TRIMTEST_PORT", " 8080 ");
+    env::set_var("TRIMTEST_DEBUG", "true\n");
+    env::set_var("TRIMTEST_BANNER", "  hello  ");
+
+    let untrimmed = Environment::new()
+        .with_prefix("TRIMTEST")
+        .collect()
+        .unwrap();
+    assert_eq!(untrimmed["port"], " 8080 ");
+
+    let trimmed = Environment::new()
+        .with_prefix("TRIMTEST")
+        .trim_values(true)
+        .collect()
+        .unwrap();
+    assert_eq!(trimmed["port"], 8080);
+    assert_eq!(trimmed["debug"], true);
+    // Plain strings keep their whitespace
+    assert_eq!(trimmed["banner"], "  hello  ");
+
+    env::remove_var("TRIMTEST_PORT");
+    env::remove_var("TRIMTEST_DEBUG");
+    env::remove_var("TRIMTEST_BANNER");
+}
+
+#[test]
+fn test_environment_field_mapping_to_nested_path() {
+    env::set_var("NESTPATH_DATABASE_URL", "postgres://nested");
+
+    let result = Environment::new()
+        .with_field_mapping("database.url", "NESTPATH_DATABASE_URL")
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["database"]["url"], "postgres://nested");
+    assert!(result.get("database.url").is_none());
+
+    env::remove_var("NESTPATH_DATABASE_URL");
+}
+
+#[test]
+fn test_environment_numeric_formats_is_opt_in() {
+    let result = Environment::new()
+        .override_with("NUMFMT_MAX", "1_000_000")
+        .override_with("NUMFMT_MASK", "0xFF")
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["numfmt_max"], "1_000_000");
+    assert_eq!(result["numfmt_mask"], "0xFF");
+}
+
+#[test]
+fn test_environment_numeric_formats_underscores() {
+    let result = Environment::new()
+        .with_prefix("NUMFMT")
+        .override_with("NUMFMT_MAX", "1_000_000")
+        .override_with("NUMFMT_OFFSET", "-12_500")
+        .override_with("NUMFMT_PLAIN", "42")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["max"], 1_000_000);
+    assert_eq!(result["offset"], -12_500);
+    assert_eq!(result["plain"], 42);
+}
+
+#[test]
+fn test_environment_numeric_formats_rejects_malformed() {
+    let result = Environment::new()
+        .with_prefix("NUMBAD")
+        .override_with("NUMBAD_DOUBLED", "1__000")
+        .override_with("NUMBAD_PLUS", "+1_000")
+        .override_with("NUMBAD_HEX_PLUS", "0x+FF")
+        .override_with("NUMBAD_TRAILING", "0xFF_")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["doubled"], "1__000");
+    assert_eq!(result["plus"], "+1_000");
+    assert_eq!(result["hex_plus"], "0x+FF");
+    assert_eq!(result["trailing"], "0xFF_");
+}
+
+#[test]
+fn test_environment_numeric_formats_hex() {
+    let result = Environment::new()
+        .with_prefix("NUMHEX")
+        .override_with("NUMHEX_MASK", "0xFF")
+        .override_with("NUMHEX_UPPER", "0Xdead_beef")
+        .override_with("NUMHEX_LARGE", "0xFFFFFFFFFFFFFFFF")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["mask"], 255);
+    assert_eq!(result["upper"], 0xdead_beef_i64);
+    assert_eq!(result["large"], u64::MAX);
+}
+
+#[test]
+fn test_environment_numeric_formats_octal() {
+    let result = Environment::new()
+        .with_prefix("NUMOCT")
+        .override_with("NUMOCT_MODE", "0o755")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["mode"], 0o755);
+}
+
+#[test]
+fn test_environment_numeric_formats_binary() {
+    let result = Environment::new()
+        .with_prefix("NUMBIN")
+        .override_with("NUMBIN_FLAGS", "0b1010")
+        .override_with("NUMBIN_NEGATIVE", "-0b11")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["flags"], 10);
+    assert_eq!(result["negative"], -3);
+}
+
+#[test]
+fn test_environment_numeric_formats_leaves_other_strings() {
+    let result = Environment::new()
+        .with_prefix("NUMSTR")
+        .override_with("NUMSTR_TAG", "v1_2")
+        .override_with("NUMSTR_EDGE", "_100")
+        .override_with("NUMSTR_HASH", "0xZZ")
+        .override_with("NUMSTR_EMPTY", "0x")
+        .numeric_formats(true)
+        .collect()
+        .unwrap();
+
+    assert_eq!(result["tag"], "v1_2");
+    assert_eq!(result["edge"], "_100");
+    assert_eq!(result["hash"], "0xZZ");
+    assert_eq!(result["empty"], "0x");
+}
+
+#[test]
+fn test_environment_prefix_separator_distinct_from_nesting() {
+    env::set_var("PFXSEP__DB_HOST", "db.internal");
+    env::set_var("PFXSEP__DB_PORT", "5432");
+    env::set_var("PFXSEP__NAME", "svc");
+
+    let env_source = Environment::new()
+        .with_prefix("PFXSEP")
+        .prefix_separator("__")
+        .nested(true);
+    let result = env_source.collect().unwrap();
+
+    assert_eq!(result["db"]["host"], "db.internal");
+    assert_eq!(result["db"]["port"], 5432);
+    assert_eq!(result["name"], "svc");
+    assert_eq!(env_source.get_value("name"), Some(serde_json::json!("svc")));
+
+    env::remove_var("PFXSEP__DB_HOST");
+    env::remove_var("PFXSEP__DB_PORT");
+    env::remove_var("PFXSEP__NAME");
+}
+
+#[test]
+fn test_environment_list_collected_keys() {
+    env::set_var("LISTKEYS_HOST", "localhost");
+    env::set_var("LISTKEYS_PORT", "8080");
+    env::set_var("UNLISTED_OTHER", "unrelated");
+
+    let env_source = Environment::new()
+        .with_prefix("LISTKEYS")
+        .override_with("LISTKEYS_DEBUG", "true");
+    assert!(env_source.list_collected_keys().is_empty());
+
+    env_source.collect().unwrap();
+    let keys: Vec<String> = env_source.list_collected_keys().into_iter().collect();
+    assert_eq!(
+        keys,
+        vec!["LISTKEYS_DEBUG", "LISTKEYS_HOST", "LISTKEYS_PORT"]
+    );
+
+    env::remove_var("LISTKEYS_HOST");
+    env::remove_var("LISTKEYS_PORT");
+    env::remove_var("UNLISTED_OTHER");
+}
+
+#[test]
+fn test_environment_custom_list_delimiters() {
+    env::set_var("LISTDELIM_HOSTS", "(a.example|b.example| c.example )");
+    env::set_var("LISTDELIM_PORTS", "(80|443)");
+    env::set_var("LISTDELIM_EMPTY", "()");
+    env::set_var("LISTDELIM_JSON", r#"["x","y"]"#);
+    env::set_var("LISTDELIM_NAME", "(unclosed");
+
+    let value = Environment::new()
+        .with_prefix("LISTDELIM")
+        .with_list_syntax(ListSyntax::bracketed('(', ')', '|'))
+        .collect()
+        .unwrap();
+
+    assert_eq!(
+        value["hosts"],
+        serde_json::json!(["a.example", "b.example", "c.example"])
+    );
+    assert_eq!(value["ports"], serde_json::json!([80, 443]));
+    assert_eq!(value["empty"], serde_json::json!([]));
+    assert_eq!(value["json"], serde_json::json!(["x", "y"]));
+    assert_eq!(value["name"], "(unclosed");
+
+    for var in [
+        "LISTDELIM_HOSTS",
+        "LISTDELIM_PORTS",
+        "LISTDELIM_EMPTY",
+        "LISTDELIM_JSON",
+        "LISTDELIM_NAME",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+fn test_environment_list_separator() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Hosts {
+        hosts: Vec<String>,
+        ports: Vec<u16>,
+        tags: Vec<String>,
+    }
+
+    env::set_var("LISTSEP_HOSTS", "a.example, b.example,c.example");
+    env::set_var("LISTSEP_PORTS", "80,443");
+    env::set_var("LISTSEP_TAGS", "");
+    env::set_var("LISTSEP_JSON", r#"["x","y"]"#);
+
+    let env_source = Environment::new()
+        .with_prefix("LISTSEP")
+        .with_list_syntax(ListSyntax::separated(','));
+    let value = env_source.collect().unwrap();
+    assert_eq!(value["json"], serde_json::json!(["x", "y"]));
+
+    let hosts: Hosts = serde_json::from_value(value).unwrap();
+    assert_eq!(hosts.hosts, ["a.example", "b.example", "c.example"]);
+    assert_eq!(hosts.ports, [80, 443]);
+    assert!(hosts.tags.is_empty());
+
+    for var in [
+        "LISTSEP_HOSTS",
+        "LISTSEP_PORTS",
+        "LISTSEP_TAGS",
+        "LISTSEP_JSON",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+fn test_environment_list_items_use_source_parsing() {
+    let env_source = || {
+        Environment::new()
+            .override_with("MASKS", "0x10, 0b11")
+            .override_with("PORTS", "(80|443)")
+            .with_list_syntax(ListSyntax::separated(','))
+    };
+
+    let value = env_source().numeric_formats(true).collect().unwrap();
+    assert_eq!(value["masks"], serde_json::json!([16, 3]));
+
+    let value = env_source().strict_types(true).collect().unwrap();
+    assert_eq!(value["masks"], serde_json::json!(["0x10", "0b11"]));
+    assert_eq!(value["ports"], "(80|443)");
+
+    let value = env_source()
+        .with_list_syntax(ListSyntax::bracketed('(', ')', '|'))
+        .strict_types(true)
+        .collect()
+        .unwrap();
+    assert_eq!(value["masks"], "0x10, 0b11");
+    assert_eq!(value["ports"], serde_json::json!(["80", "443"]));
+}
+
+#[test]
+fn test_environment_override_with_typed_value() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        database: Database,
+        version: String,
+    }
+
+    env::set_var("TYPEDOVR_DATABASE", "ignored");
+
+    let env_source = Environment::new()
+        .with_prefix("TYPEDOVR")
+        .strict_types(true)
+        .override_with_value(
+            "TYPEDOVR_DATABASE",
+            serde_json::json!({ "host": "db", "port": 5432 }),
+        )
+        .override_with_value("TYPEDOVR_VERSION", serde_json::json!("1.10"));
+    let config: Config = serde_json::from_value(env_source.collect().unwrap()).unwrap();
+    assert_eq!(config.database.host, "db");
+    assert_eq!(config.database.port, 5432);
+    assert_eq!(config.version, "1.10");
+
+    // A later string override replaces the typed one
+    let value = env_source
+        .override_with("TYPEDOVR_VERSION", "2")
+        .collect()
+        .unwrap();
+    assert_eq!(value["version"], "2");
+
+    env::remove_var("TYPEDOVR_DATABASE");
+}
+
+#[test]
+fn test_environment_fallback_prefixes() {
+    env::set_var("MULTIPFX_HOST", "new.example");
+    env::set_var("OLDMULTIPFX_HOST", "old.example");
+    env::set_var("OLDMULTIPFX_PORT", "8080");
+
+    let value = Environment::new()
+        .with_prefixes(vec!["MULTIPFX", "OLDMULTIPFX"])
+        .collect()
+        .unwrap();
+    assert_eq!(value["host"], "new.example");
+    assert_eq!(value["port"], 8080);
+
+    // `with_prefix` replaces the whole list
+    let value = Environment::new()
+        .with_prefixes(vec!["MULTIPFX", "OLDMULTIPFX"])
+        .with_prefix("MULTIPFX")
+        .collect()
+        .unwrap();
+    assert!(value.get("port").is_none());
+
+    for var in ["MULTIPFX_HOST", "OLDMULTIPFX_HOST", "OLDMULTIPFX_PORT"] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+fn test_environment_fallback_prefixes_in_every_lookup_mode() {
+    env::set_var("MULTIPFXK_DB_HOST", "new.example");
+    env::set_var("OLDMULTIPFXK_DB_HOST", "old.example");
+    env::set_var("OLDMULTIPFXK_DB_PORT", "5432");
+    env::set_var("OLDMULTIPFXK_NAME", "legacy");
+
+    // Known paths try each prefix in order
+    let value = Environment::new()
+        .with_prefixes(vec!["MULTIPFXK", "OLDMULTIPFXK"])
+        .with_known_paths(["db.host", "db.port"])
+        .collect()
+        .unwrap();
+    assert_eq!(value["db"]["host"], "new.example");
+    assert_eq!(value["db"]["port"], 5432);
+
+    // So do field mappings named under the primary prefix, while unmapped
+    // variables still come from any prefix
+    let env = Environment::new()
+        .with_prefixes(vec!["MULTIPFXK", "OLDMULTIPFXK"])
+        .with_field_mapping("db.host", "MULTIPFXK_DB_HOST")
+        .with_field_mapping("db.port", "MULTIPFXK_DB_PORT");
+    let value = env.collect().unwrap();
+    assert_eq!(value["db"]["host"], "new.example");
+    assert_eq!(value["db"]["port"], 5432);
+    assert_eq!(value["name"], "legacy");
+    assert!(value.get("db_port").is_none());
+    // Values under two prefixes are a fallback, not an alias conflict
+    assert!(env.alias_conflicts().is_empty());
+
+    for var in [
+        "MULTIPFXK_DB_HOST",
+        "OLDMULTIPFXK_DB_HOST",
+        "OLDMULTIPFXK_DB_PORT",
+        "OLDMULTIPFXK_NAME",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_collect_for_struct_composes_keys() {
+    env::set_var("STRUCTKEYS_SERVERCONFIG_MAX_POOL_SIZE", "16");
+    env::set_var("SERVERCONFIG_MAX_POOL_SIZE", "8");
+    env::set_var("STRUCTKEYS__SERVERCONFIG__HOST", "db.example");
+    env::set_var("STRUCTKEYS_CUSTOM_HOST", "custom.example");
+
+    let fields = [("max_pool_size", None), ("host", None)];
+    let value = Environment::new()
+        .with_prefix("structkeys")
+        .collect_for_struct("ServerConfig", &fields);
+    assert_eq!(value["max_pool_size"], 16);
+    assert!(!value.contains_key("host"));
+
+    // Without a prefix there is no leading separator
+    let value = Environment::new().collect_for_struct("ServerConfig", &fields);
+    assert_eq!(value["max_pool_size"], 8);
+
+    // Parts are joined with the configured separator, overrides are verbatim
+    let value = Environment::new()
+        .with_prefix("STRUCTKEYS")
+        .separator("__")
+        .collect_for_struct(
+            "ServerConfig",
+            &[("host", None), ("port", Some("STRUCTKEYS_CUSTOM_HOST"))],
+        );
+    assert_eq!(value["host"], "db.example");
+    assert_eq!(value["port"], "custom.example");
+
+    for var in [
+        "STRUCTKEYS_SERVERCONFIG_MAX_POOL_SIZE",
+        "SERVERCONFIG_MAX_POOL_SIZE",
+        "STRUCTKEYS__SERVERCONFIG__HOST",
+        "STRUCTKEYS_CUSTOM_HOST",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+fn test_environment_merge_combines_mappings() {
+    env::set_var("ENVMERGE_DB", "postgres://merged");
+    env::set_var("ENVMERGE_LOG", "debug");
+    env::set_var("ENVMERGE_OTHER_DB", "postgres://other");
+
+    let base = Environment::new()
+        .with_field_mapping("database_url", "ENVMERGE_DB")
+        .override_with("ENVMERGE_TIMEOUT", "5");
+    let app = Environment::new()
+        .with_field_mapping("log_level", "ENVMERGE_LOG")
+        .with_field_mapping("timeout", "ENVMERGE_TIMEOUT")
+        .override_with("ENVMERGE_TIMEOUT", "30");
+
+    let value = base.clone().merge(app.clone(), false).collect().unwrap();
+    assert_eq!(value["database_url"], "postgres://merged");
+    assert_eq!(value["log_level"], "debug");
+    assert_eq!(value["timeout"], 5);
+
+    let value = base.merge(app, true).collect().unwrap();
+    assert_eq!(value["timeout"], 30);
+
+    // `self` keeps its mapping unless `other` is preferred
+    let value = Environment::new()
+        .with_field_mapping("database_url", "ENVMERGE_DB")
+        .merge(
+            Environment::new().with_field_mapping("database_url", "ENVMERGE_OTHER_DB"),
+            false,
+        )
+        .collect()
+        .unwrap();
+    assert_eq!(value["database_url"], "postgres://merged");
+
+    env::remove_var("ENVMERGE_DB");
+    env::remove_var("ENVMERGE_LOG");
+    env::remove_var("ENVMERGE_OTHER_DB");
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TaggedStorage {
+    S3 { bucket: String, region: String },
+    Local { path: String, max_size: u64 },
+}
+
+#[derive(Debug, Deserialize)]
+struct TaggedConfig {
+    storage: TaggedStorage,
+}
+
+#[test]
+fn test_environment_internally_tagged_enum() {
+    env::set_var("TAGGEDENV__STORAGE__TYPE", "s3");
+    env::set_var("TAGGEDENV__STORAGE__BUCKET", "assets");
+    env::set_var("TAGGEDENV__STORAGE__REGION", "eu-west-1");
+
+    let config: TaggedConfig = gonfig::ConfigBuilder::new()
+        .with_env_custom(
+            Environment::new()
+                .with_prefix("TAGGEDENV")
+                .separator("__")
+                .nested(true),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(
+        config.storage,
+        TaggedStorage::S3 {
+            bucket: "assets".to_string(),
+            region: "eu-west-1".to_string(),
+        }
+    );
+
+    // Variant fields containing the separator resolve through known paths
+    env::set_var("TAGGEDLOCAL_STORAGE_TYPE", "local");
+    env::set_var("TAGGEDLOCAL_STORAGE_PATH", "/var/data");
+    env::set_var("TAGGEDLOCAL_STORAGE_MAX_SIZE", "1024");
+
+    let config: TaggedConfig = gonfig::ConfigBuilder::new()
+        .with_env_custom(
+            Environment::new()
+                .with_prefix("TAGGEDLOCAL")
+                .with_known_paths(["storage.type", "storage.path", "storage.max_size"]),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(
+        config.storage,
+        TaggedStorage::Local {
+            path: "/var/data".to_string(),
+            max_size: 1024,
+        }
+    );
+
+    for var in [
+        "TAGGEDENV__STORAGE__TYPE",
+        "TAGGEDENV__STORAGE__BUCKET",
+        "TAGGEDENV__STORAGE__REGION",
+        "TAGGEDLOCAL_STORAGE_TYPE",
+        "TAGGEDLOCAL_STORAGE_PATH",
+        "TAGGEDLOCAL_STORAGE_MAX_SIZE",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[test]
+fn test_environment_nest_on_dotted_keys() {
+    env::set_var("dotnest.http.port", "9000");
+    env::set_var("dotnest.http.host", "0.0.0.0");
+    env::set_var("DOTNEST_NAME", "svc");
+
+    let value = Environment::new()
+        .with_prefix("DOTNEST")
+        .nest_on('.')
+        .collect()
+        .unwrap();
+
+    assert_eq!(value["http"]["port"], 9000);
+    assert_eq!(value["http"]["host"], "0.0.0.0");
+    assert_eq!(value["name"], "svc");
+
+    env::remove_var("dotnest.http.port");
+    env::remove_var("dotnest.http.host");
+    env::remove_var("DOTNEST_NAME");
+}
+
+#[test]
+fn test_env_alias_conflict_is_reported() {
+    env::set_var("ALIASAPP_DATABASE_URL", "postgres://new");
+    env::set_var("ALIAS_LEGACY_DB_URL", "postgres://old");
+
+    let env_source = || {
+        Environment::new()
+            .with_prefix("ALIASAPP")
+            .with_field_mapping("database_url", "ALIASAPP_DATABASE_URL")
+            .with_field_alias("database_url", "ALIAS_LEGACY_DB_URL")
+    };
+
+    let conflicts = env_source().alias_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].field, "database_url");
+    assert_eq!(
+        conflicts[0].names,
+        vec!["ALIASAPP_DATABASE_URL", "ALIAS_LEGACY_DB_URL"]
+    );
+
+    // Lenient by default: the canonical name wins
+    let value = env_source().collect().unwrap();
+    assert_eq!(value["database_url"], "postgres://new");
+
+    match env_source().strict_aliases(true).collect() {
+        Err(gonfig::Error::Environment(msg)) => {
+            assert!(
+                msg.contains("ALIASAPP_DATABASE_URL, ALIAS_LEGACY_DB_URL"),
+                "unexpected message: {msg}"
+            );
+        }
+        other => panic!("expected an environment error, got {other:?}"),
+    }
+
+    // Same value under both names is not a conflict
+    env::set_var("ALIAS_LEGACY_DB_URL", "postgres://new");
+    assert!(env_source().alias_conflicts().is_empty());
+
+    // Only the alias set
+    env::remove_var("ALIASAPP_DATABASE_URL");
+    env::set_var("ALIAS_LEGACY_DB_URL", "postgres://old");
+    let value = env_source().collect().unwrap();
+    assert_eq!(value["database_url"], "postgres://old");
+    assert!(value.get("legacy_db_url").is_none());
+
+    env::remove_var("ALIAS_LEGACY_DB_URL");
+}
+
+#[test]
+fn test_env_alias_with_known_paths() {
+    // Mapped field read only through its alias
+    let value = Environment::new()
+        .with_known_paths(["database_url"])
+        .with_field_mapping("database_url", "KNOWNALIAS_DATABASE_URL")
+        .with_field_alias("database_url", "KNOWNALIAS_DB_URL")
+        .override_with("KNOWNALIAS_DB_URL", "postgres://legacy")
+        .collect()
+        .unwrap();
+    assert_eq!(value["database_url"], "postgres://legacy");
+
+    // Known path without a mapping, under a prefix
+    let env_source = || {
+        Environment::new()
+            .with_prefix("KNOWNALIAS")
+            .with_known_paths(["database.url", "port"])
+            .with_field_alias("database.url", "KNOWNALIAS_LEGACY_URL")
+            .override_with("KNOWNALIAS_DATABASE_URL", "postgres://new")
+            .override_with("KNOWNALIAS_LEGACY_URL", "postgres://old")
+    };
+    let value = env_source().collect().unwrap();
+    assert_eq!(value["database"]["url"], "postgres://new");
+    assert_eq!(env_source().alias_conflicts()[0].field, "database.url");
+
+    match env_source().strict_aliases(true).collect() {
+        Err(gonfig::Error::Environment(msg)) => {
+            assert!(
+                msg.contains("KNOWNALIAS_DATABASE_URL, KNOWNALIAS_LEGACY_URL"),
+                "unexpected message: {msg}"
+            );
+        }
+        other => panic!("expected an environment error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_environment_struct_infix() {
+    env::set_var("INFIXAPP_SERVERCONFIG_MAX_POOL_SIZE", "16");
+    env::set_var("INFIXAPP_SERVERCONFIG_HOST", "db.internal");
+    env::set_var("INFIXAPP_OTHER_HOST", "ignored");
+
+    let value = Environment::new()
+        .with_prefix("InfixApp")
+        .with_struct_infix("ServerConfig")
+        .collect()
+        .unwrap();
+    assert_eq!(value["max_pool_size"], 16);
+    assert_eq!(value["host"], "db.internal");
+    assert!(value.get("other_host").is_none());
+
+    // The infix is joined with the prefix separator, fields use the separator
+    env::set_var("INFIXAPP__SERVERCONFIG__LIMITS_MAX", "8");
+    let value = Environment::new()
+        .with_prefix("INFIXAPP")
+        .with_struct_infix("ServerConfig")
+        .prefix_separator("__")
+        .nested(true)
+        .collect()
+        .unwrap();
+    assert_eq!(value["limits"]["max"], 8);
+
+    // Case sensitive lookups use the infix exactly as given
+    env::set_var("infixapp_ServerConfig_port", "8080");
+    let env = Environment::new()
+        .with_prefix("infixapp")
+        .with_struct_infix("ServerConfig")
+        .case_sensitive(true);
+    assert_eq!(env.get_value("port"), Some(serde_json::json!(8080)));
+    assert!(env.describe().contains("infixapp_ServerConfig_"));
+
+    // Without a prefix the infix leads
+    let value = Environment::new()
+        .with_struct_infix("InfixOnly")
+        .override_with("INFIXONLY_PORT", "9000")
+        .collect()
+        .unwrap();
+    assert_eq!(value["port"], 9000);
+
+    env::remove_var("INFIXAPP_SERVERCONFIG_MAX_POOL_SIZE");
+    env::remove_var("INFIXAPP_SERVERCONFIG_HOST");
+    env::remove_var("INFIXAPP_OTHER_HOST");
+    env::remove_var("INFIXAPP__SERVERCONFIG__LIMITS_MAX");
+    env::remove_var("infixapp_ServerConfig_port");
+}
+
+#[test]
+fn test_env_prefix_case_matches_uppercase_vars() {
+    env::set_var("PREFIXCASE_port", "8080");
+
+    let source = || {
+        Environment::new()
+            .with_prefix("prefixcase")
+            .case_sensitive(true)
+    };
+
+    // A case-sensitive source uses the prefix as configured
+    let value = ConfigBuilder::new()
+        .with_env_custom(source())
+        .build_value()
+        .unwrap();
+    assert!(value.get("port").is_none());
+
+    let value = ConfigBuilder::new()
+        .with_env_custom(source())
+        .with_env_prefix_case(Case::Upper)
+        .build_value()
+        .unwrap();
+    assert_eq!(value["port"], 8080);
+
+    env::remove_var("PREFIXCASE_port");
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "grpc")]
+
+use gonfig::grpc::proto::{GetConfigRequest, GetConfigResponse};
+use gonfig::grpc::{ConfigRequest, GrpcSource, GET_CONFIG_PATH, SERVICE_NAME};
+use gonfig::{ConfigBuilder, ConfigSource};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::env;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::task::{Context, Poll};
+use std::thread;
+use tonic::body::BoxBody;
+use tonic::codegen::http::{Request, Response};
+use tonic::codegen::{BoxFuture, Service};
+use tonic::server::{NamedService, UnaryService};
+use tonic::Status;
+
+/// In-process config service answering from `documents`, keyed by
+/// `name/profile`. Unknown documents are answered with `NOT_FOUND`.
+#[derive(Clone)]
+struct ConfigServer {
+    documents: Arc<HashMap<String, GetConfigResponse>>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ConfigServer {
+    /// Serve on a free local port from a thread of its own and return the
+    /// endpoint to connect to.
+    fn start(self) -> String {
+        let (address, bound) = mpsc::channel::<SocketAddr>();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                address.send(listener.local_addr().unwrap()).unwrap();
+                tonic::transport::Server::builder()
+                    .add_service(self)
+                    .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                    .await
+                    .unwrap();
+            });
+        });
+        format!("http://{}", bound.recv().unwrap())
+    }
+}
+
+impl NamedService for ConfigServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl Service<Request<BoxBody>> for ConfigServer {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Infallible>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<BoxBody>) -> Self::Future {
+        let server = self.clone();
+        Box::pin(async move {
+            if request.uri().path() != GET_CONFIG_PATH {
+                return Ok(Status::unimplemented(request.uri().path()).into_http());
+            }
+            let codec = tonic::codec::ProstCodec::<GetConfigResponse, GetConfigRequest>::default();
+            Ok(tonic::server::Grpc::new(codec)
+                .unary(GetConfig(server), request)
+                .await)
+        })
+    }
+}
+
+/// Handler of the `GetConfig` call.
+struct GetConfig(ConfigServer);
+
+impl UnaryService<GetConfigRequest> for GetConfig {
+    type Response = GetConfigResponse;
+    type Future = BoxFuture<tonic::Response<GetConfigResponse>, Status>;
+
+    fn call(&mut self, request: tonic::Request<GetConfigRequest>) -> Self::Future {
+        self.0.calls.fetch_add(1, Ordering::SeqCst);
+        let request = request.into_inner();
+        let profile = if request.profile.is_empty() {
+            "default"
+        } else {
+            &request.profile
+        };
+        let key = format!("{}/{}", request.name, profile);
+        let response = self
+            .0
+            .documents
+            .get(&key)
+            .cloned()
+            .map(tonic::Response::new)
+            .ok_or_else(|| Status::not_found(key));
+        Box::pin(async move { response })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceConfig {
+    port: u16,
+    database: DatabaseConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseConfig {
+    url: String,
+    pool: u32,
+}
+
+fn server() -> ConfigServer {
+    ConfigServer {
+        documents: Arc::new(HashMap::from([(
+            "billing/prod".to_string(),
+            GetConfigResponse {
+                content: b"port = 7000\n[database]\nurl = \"postgres://grpc\"\npool = 4\n".to_vec(),
+                format: "toml".to_string(),
+            },
+        )])),
+        calls: Arc::new(AtomicUsize::new(0)),
+    }
+}
+
+#[test]
+fn test_grpc_source_overrides_env() {
+    env::set_var("GRPCAPP_PORT", "5000");
+    env::set_var("GRPCAPP_DATABASE_POOL", "9");
+
+    let endpoint = server().start();
+    let config: ServiceConfig = ConfigBuilder::new()
+        .with_env_configured("GRPCAPP", |env| env.nested(true))
+        .with_grpc(endpoint, ConfigRequest::new("billing").profile("prod"))
+        .build()
+        .unwrap();
+    assert_eq!(config.port, 7000);
+    assert_eq!(config.database.url, "postgres://grpc");
+    assert_eq!(config.database.pool, 4);
+
+    env::remove_var("GRPCAPP_PORT");
+    env::remove_var("GRPCAPP_DATABASE_POOL");
+}
+
+#[test]
+fn test_grpc_source_failure_is_reported() {
+    let endpoint = server().start();
+    let err = ConfigBuilder::new()
+        .with_grpc(&endpoint, ConfigRequest::new("billing").profile("staging"))
+        .build_value()
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.contains("NotFound"),
+        "unexpected message: {message}"
+    );
+    assert!(
+        message.contains(&format!("grpc config billing (staging) from {endpoint}")),
+        "unexpected message: {message}"
+    );
+}
+
+#[test]
+fn test_grpc_source_resilient_fallback() {
+    let endpoint = server().start();
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 8080 }))
+        .unwrap()
+        .with_grpc(endpoint, ConfigRequest::new("unknown"))
+        .resilient(true)
+        .build_value()
+        .unwrap();
+    assert_eq!(value["port"], 8080);
+}
+
+#[test]
+fn test_grpc_source_reads_values_from_snapshot() {
+    let server = server();
+    let calls = Arc::clone(&server.calls);
+    let source = GrpcSource::connect(
+        server.start(),
+        ConfigRequest::new("billing").profile("prod"),
+    );
+
+    assert_eq!(source.get_value("port"), Some(serde_json::json!(7000)));
+    assert!(source.has_value("database.url"));
+    assert!(!source.has_value("database.missing"));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Collecting fetches again and refreshes the snapshot
+    assert_eq!(source.collect().unwrap()["database"]["pool"], 4);
+    assert_eq!(
+        source.get_value("database.pool"),
+        Some(serde_json::json!(4))
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,136 @@
+use gonfig::inspect::{
+    redact_secrets, use_color, write_effective_config, write_effective_config_colored, REDACTED,
+};
+use gonfig::{print_effective_config, ConfigBuilder, ConfigFormat};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::process::Command;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ServiceConfig {
+    port: u16,
+    api_token: String,
+    database: DatabaseConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DatabaseConfig {
+    url: String,
+    password: String,
+}
+
+fn builder() -> ConfigBuilder {
+    ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "port": 8080,
+            "api_token": "tok-123",
+            "database": { "url": "postgres://db", "password": "hunter2" }
+        }))
+        .unwrap()
+}
+
+#[test]
+fn test_write_effective_config_redacts_secrets() {
+    let mut out = Vec::new();
+    write_effective_config::<ServiceConfig, _>(builder(), ConfigFormat::Json, &mut out).unwrap();
+    let output = String::from_utf8(out).unwrap();
+
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(value["port"], 8080);
+    assert_eq!(value["database"]["url"], "postgres://db");
+    assert_eq!(value["api_token"], REDACTED);
+    assert_eq!(value["database"]["password"], REDACTED);
+    assert!(!output.contains("hunter2"));
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReplicaConfig {
+    port: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replica_of: Option<String>,
+}
+
+#[test]
+fn test_write_effective_config_uses_serde_attributes() {
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 8080, "legacy_mode": true }))
+        .unwrap();
+
+    let mut out = Vec::new();
+    write_effective_config::<ReplicaConfig, _>(builder, ConfigFormat::Toml, &mut out).unwrap();
+    let output = String::from_utf8(out).unwrap();
+
+    // Skipped `None` fields and keys `T` doesn't know are left out
+    assert_eq!(output, "port = 8080\n");
+}
+
+#[test]
+fn test_redact_secrets_walks_arrays() {
+    let mut value = serde_json::json!({
+        "replicas": [{ "host": "a", "secret_key": "s1" }],
+        "name": "svc"
+    });
+    redact_secrets(&mut value);
+    assert_eq!(value["replicas"][0]["secret_key"], REDACTED);
+    assert_eq!(value["replicas"][0]["host"], "a");
+    assert_eq!(value["name"], "svc");
+}
+
+/// Child half of `test_print_effective_config_stdout`: only prints when spawned by it.
+#[test]
+fn print_effective_config_child() {
+    if env::var("GONFIG_INSPECT_CHILD").is_ok() {
+        print_effective_config::<ServiceConfig>(builder(), ConfigFormat::Yaml).unwrap();
+    }
+}
+
+#[test]
+fn test_print_effective_config_stdout() {
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["--exact", "print_effective_config_child", "--nocapture"])
+        .env("GONFIG_INSPECT_CHILD", "1")
+        .env_remove("NO_COLOR")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("url: postgres://db"), "stdout: {stdout}");
+    assert!(stdout.contains("password: '***'"), "stdout: {stdout}");
+    assert!(!stdout.contains("hunter2"), "stdout: {stdout}");
+    assert!(!stdout.contains("tok-123"), "stdout: {stdout}");
+    // Captured stdout is not a terminal, so no ANSI codes are written
+    assert!(!stdout.contains('\x1b'), "stdout: {stdout:?}");
+}
+
+#[test]
+fn test_write_effective_config_colored() {
+    let mut out = Vec::new();
+    write_effective_config_colored::<ServiceConfig, _>(
+        builder(),
+        ConfigFormat::Toml,
+        &mut out,
+        true,
+    )
+    .unwrap();
+    let output = String::from_utf8(out).unwrap();
+    assert!(output.contains("\x1b[36mport\x1b[0m = 8080"), "{output:?}");
+    assert!(output.contains("\x1b[1m[database]\x1b[0m"), "{output:?}");
+
+    let mut out = Vec::new();
+    write_effective_config_colored::<ServiceConfig, _>(
+        builder(),
+        ConfigFormat::Yaml,
+        &mut out,
+        false,
+    )
+    .unwrap();
+    assert!(!String::from_utf8(out).unwrap().contains('\x1b'));
+}
+
+#[test]
+fn test_use_color_respects_no_color() {
+    env::set_var("NO_COLOR", "1");
+    assert!(!use_color(&std::io::stdout()));
+    env::remove_var("NO_COLOR");
+}
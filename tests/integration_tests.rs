@@ -1,4 +1,4 @@
-use gonfig::{ConfigBuilder, Gonfig};
+use gonfig::{ConfigBuilder, ConfigSource, Gonfig};
 use serde::{Deserialize, Serialize};
 use std::env;
 
@@ -139,3 +139,844 @@ fn test_config_file_support() {
     env::remove_var("CFG_NAME");
     env::remove_var("CFG_VALUE");
 }
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct PathDatabaseSection {
+    url: String,
+    pool_size: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+#[Gonfig(env_prefix = "PATHAPP")]
+struct PathMappedConfig {
+    #[gonfig(env_name = "PATHAPP_DB_URL", path = "database.url")]
+    database: PathDatabaseSection,
+}
+
+#[test]
+fn test_derive_env_name_with_nested_path() {
+    env::set_var("PATHAPP_DB_URL", "postgres://from-env");
+
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "database": { "url": "postgres://default", "pool_size": 5 }
+        }))
+        .unwrap();
+    let config = PathMappedConfig::from_gonfig_with_builder(builder).unwrap();
+
+    assert_eq!(config.database.url, "postgres://from-env");
+    assert_eq!(config.database.pool_size, 5);
+
+    env::remove_var("PATHAPP_DB_URL");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig, PartialEq)]
+#[Gonfig(env_prefix = "CLIONLY", sources = "cli")]
+struct CliOnlyConfig {
+    #[gonfig(default = "info")]
+    log_level: String,
+}
+
+#[test]
+fn test_derive_sources_cli_ignores_env() {
+    env::set_var("CLIONLY_LOG_LEVEL", "debug");
+
+    let config = CliOnlyConfig::from_gonfig().unwrap();
+    assert_eq!(config.log_level, "info");
+
+    let source_types: Vec<_> = CliOnlyConfig::gonfig_builder()
+        .sources()
+        .iter()
+        .map(|s| s.source_type())
+        .collect();
+    assert_eq!(source_types, vec![gonfig::Source::Cli]);
+
+    env::remove_var("CLIONLY_LOG_LEVEL");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "SECRETAPP", allow_cli)]
+struct SensitiveConfig {
+    #[gonfig(sensitive)]
+    api_token: String,
+    log_level: String,
+}
+
+#[test]
+fn test_derive_sensitive_field_ignores_cli_flag() {
+    env::set_var("SECRETAPP_API_TOKEN", "from-env");
+    env::set_var("SECRETAPP_LOG_LEVEL", "info");
+
+    let args: Vec<String> = ["app", "--api-token", "from-cli", "--log-level", "debug"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let cli = SensitiveConfig::gonfig_cli(args);
+    assert!(!cli.has_value("api_token"));
+    assert!(!cli.has_value("api-token"));
+    assert_eq!(cli.get_value("log-level"), Some(serde_json::json!("debug")));
+
+    let config: SensitiveConfig = ConfigBuilder::new()
+        .with_env_flat("SECRETAPP")
+        .with_cli_custom(cli)
+        .build()
+        .unwrap();
+    assert_eq!(config.api_token, "from-env");
+    assert_eq!(config.log_level, "debug");
+
+    let fields = SensitiveConfig::gonfig_fields();
+    assert_eq!(fields[0].cli_key, None);
+    assert!(!SensitiveConfig::gonfig_help().contains("--api-token"));
+
+    env::remove_var("SECRETAPP_API_TOKEN");
+    env::remove_var("SECRETAPP_LOG_LEVEL");
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "u16")]
+struct NonZeroPort(u16);
+
+impl TryFrom<u16> for NonZeroPort {
+    type Error = String;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value == 0 {
+            Err("port must be non-zero".to_string())
+        } else {
+            Ok(NonZeroPort(value))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "TRYFROM")]
+struct TryFromConfig {
+    #[gonfig(try_from = "u16")]
+    port: NonZeroPort,
+}
+
+#[test]
+fn test_derive_try_from_rejects_invalid_newtype() {
+    env::set_var("TRYFROM_PORT", "0");
+    let err = TryFromConfig::from_gonfig().unwrap_err();
+    match err {
+        gonfig::Error::Validation(msg) => {
+            assert!(msg.contains("port"), "unexpected message: {msg}");
+            assert!(
+                msg.contains("port must be non-zero"),
+                "unexpected message: {msg}"
+            );
+        }
+        other => panic!("expected validation error, got {other:?}"),
+    }
+
+    // A value that is not even the raw type reports where it was found
+    env::set_var("TRYFROM_PORT", "eighty");
+    let err = TryFromConfig::from_gonfig().unwrap_err();
+    match err {
+        gonfig::Error::Deserialize { path, .. } => assert_eq!(path, "/port"),
+        other => panic!("expected deserialize error, got {other:?}"),
+    }
+
+    env::set_var("TRYFROM_PORT", "8080");
+    let config = TryFromConfig::from_gonfig().unwrap();
+    assert_eq!(config.port, NonZeroPort(8080));
+
+    env::remove_var("TRYFROM_PORT");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "CONFLICTAPP", allow_cli)]
+struct ConflictConfig {
+    #[gonfig(conflicts_with = "verbose")]
+    #[serde(default)]
+    quiet: bool,
+    #[serde(default)]
+    verbose: bool,
+}
+
+#[test]
+fn test_derive_conflicting_cli_flags_rejected() {
+    let args = |flags: &[&str]| -> Vec<String> {
+        std::iter::once("app")
+            .chain(flags.iter().copied())
+            .map(str::to_string)
+            .collect()
+    };
+
+    let err = ConfigBuilder::new()
+        .with_cli_custom(ConflictConfig::gonfig_cli(args(&["--quiet", "--verbose"])))
+        .build::<ConflictConfig>()
+        .unwrap_err();
+    match err {
+        gonfig::Error::Cli(msg) => {
+            assert!(msg.contains("--quiet"), "unexpected message: {msg}");
+            assert!(msg.contains("--verbose"), "unexpected message: {msg}");
+        }
+        other => panic!("expected CLI error, got {other:?}"),
+    }
+
+    let config: ConflictConfig = ConfigBuilder::new()
+        .with_cli_custom(ConflictConfig::gonfig_cli(args(&["--verbose"])))
+        .build()
+        .unwrap();
+    assert!(config.verbose);
+    assert!(!config.quiet);
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(allow_cli)]
+struct ShortFlagConfig {
+    #[gonfig(cli_short = 'p')]
+    port: u16,
+    #[gonfig(cli_short = 'n', cli_name = "app-name")]
+    name: String,
+}
+
+#[test]
+fn test_derive_cli_short_flags() {
+    let build = |args: &[&str]| -> ShortFlagConfig {
+        let args = std::iter::once("app")
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect();
+        ConfigBuilder::new()
+            .with_cli_custom(ShortFlagConfig::gonfig_cli(args))
+            .build()
+            .unwrap()
+    };
+
+    let config = build(&["-p", "8080", "-n", "short"]);
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "short");
+
+    let config = build(&["--port", "9090", "--app-name", "long"]);
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.name, "long");
+
+    // The long form wins when both spellings are passed
+    let config = build(&["-p", "1", "--port", "2", "-n", "x"]);
+    assert_eq!(config.port, 2);
+
+    let help = ShortFlagConfig::gonfig_help();
+    assert!(help.contains("-p, --port"), "unexpected help: {help}");
+    assert!(help.contains("-n, --app-name"), "unexpected help: {help}");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "FLATCATCH")]
+struct CatchAllConfig {
+    port: u16,
+    #[serde(flatten)]
+    extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[test]
+fn test_derive_serde_flatten_catch_all() {
+    env::set_var("FLATCATCH_PORT", "8080");
+    env::set_var("FLATCATCH_FEATURE_FLAG", "on");
+
+    let config = CatchAllConfig::from_gonfig().unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(
+        config.extra.get("feature_flag"),
+        Some(&serde_json::json!("on"))
+    );
+    assert!(!config.extra.contains_key("port"));
+
+    // The catch-all has no variable or flag of its own
+    let help = CatchAllConfig::gonfig_help();
+    assert!(!help.contains("FLATCATCH_EXTRA"), "unexpected help: {help}");
+
+    env::remove_var("FLATCATCH_PORT");
+    env::remove_var("FLATCATCH_FEATURE_FLAG");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "PRESENCE")]
+struct PresenceConfig {
+    #[gonfig(presence, default = "false")]
+    feature: bool,
+    #[gonfig(presence, default = "false")]
+    other_feature: bool,
+}
+
+#[test]
+fn test_derive_presence_flag() {
+    env::set_var("PRESENCE_FEATURE", "");
+    env::remove_var("PRESENCE_OTHER_FEATURE");
+
+    let config = PresenceConfig::from_gonfig().unwrap();
+    assert!(config.feature);
+    assert!(!config.other_feature);
+
+    // gonfig_builder() skips defaults, so both flags need a variable here
+    env::set_var("PRESENCE_OTHER_FEATURE", "false");
+    let config = PresenceConfig::gonfig_builder()
+        .build::<PresenceConfig>()
+        .unwrap();
+    assert!(config.feature);
+    assert!(config.other_feature);
+
+    env::remove_var("PRESENCE_FEATURE");
+    env::remove_var("PRESENCE_OTHER_FEATURE");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "WRAPSCALAR", wrap_scalars)]
+struct WrapScalarConfig {
+    hosts: Vec<String>,
+    ports: Vec<u16>,
+    #[gonfig(default = "[]")]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_derive_wrap_scalars_into_vec() {
+    env::set_var("WRAPSCALAR_HOSTS", "localhost");
+    env::set_var("WRAPSCALAR_PORTS", "[80, 443]");
+
+    let config = WrapScalarConfig::from_gonfig().unwrap();
+    assert_eq!(config.hosts, vec!["localhost"]);
+    assert_eq!(config.ports, vec![80, 443]);
+    assert!(config.tags.is_empty());
+
+    env::remove_var("WRAPSCALAR_HOSTS");
+    env::remove_var("WRAPSCALAR_PORTS");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+struct StageNamedConfig {
+    #[gonfig(env_name = "{gonfig_placeholder_stage}_DB_URL")]
+    db_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+struct UnresolvedStageConfig {
+    #[gonfig(env_name = "{unset_placeholder_stage}_DB_URL")]
+    db_url: String,
+}
+
+#[test]
+fn test_derive_env_name_placeholder() {
+    env::set_var("GONFIG_PLACEHOLDER_STAGE", "eu-prod");
+    env::set_var("EU_PROD_DB_URL", "postgres://prod");
+
+    // `-` in the substituted value becomes `_`
+    let config = StageNamedConfig::from_gonfig().unwrap();
+    assert_eq!(config.db_url, "postgres://prod");
+    assert_eq!(
+        StageNamedConfig::gonfig_fields()[0].env_key,
+        "EU_PROD_DB_URL"
+    );
+
+    env::remove_var("UNSET_PLACEHOLDER_STAGE");
+    match UnresolvedStageConfig::from_gonfig() {
+        Err(gonfig::Error::Environment(msg)) => {
+            assert!(
+                msg.contains("UNSET_PLACEHOLDER_STAGE"),
+                "unexpected message: {msg}"
+            );
+        }
+        other => panic!("expected an environment error, got {other:?}"),
+    }
+
+    // `gonfig_builder` can't fail, so the unset placeholder fails the build
+    match UnresolvedStageConfig::gonfig_builder().build::<UnresolvedStageConfig>() {
+        Err(gonfig::Error::Environment(msg)) => {
+            assert!(
+                msg.contains("UNSET_PLACEHOLDER_STAGE"),
+                "unexpected message: {msg}"
+            );
+        }
+        other => panic!("expected an environment error, got {other:?}"),
+    }
+
+    env::remove_var("GONFIG_PLACEHOLDER_STAGE");
+    env::remove_var("EU_PROD_DB_URL");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+struct FlatDatabaseConfig {
+    host: String,
+    #[gonfig(default = "5432")]
+    port: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "FLATAPP")]
+struct FlatAppConfig {
+    name: String,
+    #[gonfig(flatten)]
+    database: FlatDatabaseConfig,
+}
+
+#[test]
+fn test_derive_flatten_fields_listed_in_help() {
+    let fields = FlatAppConfig::gonfig_fields();
+    let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "database.host", "database.port"]);
+
+    let port = &fields[2];
+    assert_eq!(port.env_key, "FLATAPP_PORT");
+    assert_eq!(port.cli_key.as_deref(), Some("port"));
+    assert_eq!(port.default.as_deref(), Some("5432"));
+
+    let env_example = FlatAppConfig::gonfig_env_example();
+    assert!(env_example.contains("FLATAPP_HOST"), "{env_example}");
+    let help = FlatAppConfig::gonfig_help();
+    assert!(help.contains("--port"), "{help}");
+}
+
+#[test]
+fn test_derive_flatten_uses_parent_namespace() {
+    env::set_var("FLATAPP_NAME", "svc");
+    env::set_var("FLATAPP_HOST", "db.internal");
+    env::set_var("FLATAPP_DATABASE_HOST", "ignored");
+
+    let config = FlatAppConfig::from_gonfig().unwrap();
+    assert_eq!(config.name, "svc");
+    assert_eq!(config.database.host, "db.internal");
+    assert_eq!(config.database.port, 5432);
+
+    let cli = FlatAppConfig::gonfig_cli(vec!["app".into(), "--port".into(), "6543".into()]);
+    let value = ConfigBuilder::new()
+        .with_cli_custom(cli)
+        .build_value()
+        .unwrap();
+    assert_eq!(value["database"]["port"], 6543);
+
+    env::remove_var("FLATAPP_NAME");
+    env::remove_var("FLATAPP_HOST");
+    env::remove_var("FLATAPP_DATABASE_HOST");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "RENAMED", rename_all = "camelCase")]
+struct CamelCaseConfig {
+    max_connections: u32,
+    #[gonfig(cli_name = "log")]
+    log_level: String,
+}
+
+#[test]
+fn test_derive_rename_all_camel_case() {
+    let fields = CamelCaseConfig::gonfig_fields();
+    assert_eq!(fields[0].cli_key.as_deref(), Some("maxConnections"));
+    assert_eq!(fields[0].env_key, "RENAMED_MAX_CONNECTIONS");
+    assert_eq!(fields[1].cli_key.as_deref(), Some("log"));
+
+    let cli = CamelCaseConfig::gonfig_cli(vec![
+        "app".into(),
+        "--maxConnections".into(),
+        "25".into(),
+        "--log".into(),
+        "debug".into(),
+    ]);
+    let config: CamelCaseConfig =
+        CamelCaseConfig::from_gonfig_with_builder(ConfigBuilder::new().with_cli_custom(cli))
+            .unwrap();
+    assert_eq!(config.max_connections, 25);
+    assert_eq!(config.log_level, "debug");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "KEBABRENAMED", rename_all = "kebab-case")]
+struct KebabCaseConfig {
+    max_connections: u32,
+    #[gonfig(required)]
+    worker_count: u32,
+}
+
+#[test]
+fn test_derive_rename_all_keeps_snake_case_env_names() {
+    env::set_var("KEBABRENAMED_MAX_CONNECTIONS", "30");
+    env::set_var("KEBABRENAMED_WORKER_COUNT", "2");
+    env::set_var("RENAMED_MAX_CONNECTIONS", "40");
+    env::set_var("RENAMED_LOG_LEVEL", "warn");
+
+    let fields = KebabCaseConfig::gonfig_fields();
+    assert_eq!(fields[0].cli_key.as_deref(), Some("max-connections"));
+    assert_eq!(fields[0].env_key, "KEBABRENAMED_MAX_CONNECTIONS");
+
+    let config = KebabCaseConfig::from_gonfig_with_builder(ConfigBuilder::new()).unwrap();
+    assert_eq!(config.max_connections, 30);
+    assert_eq!(config.worker_count, 2);
+
+    let config = CamelCaseConfig::from_gonfig_with_builder(ConfigBuilder::new()).unwrap();
+    assert_eq!(config.max_connections, 40);
+    assert_eq!(config.log_level, "warn");
+
+    // A missing required field names its snake_case variable
+    env::remove_var("KEBABRENAMED_WORKER_COUNT");
+    let err = KebabCaseConfig::from_gonfig_with_builder(ConfigBuilder::new()).unwrap_err();
+    assert!(
+        err.to_string().contains("KEBABRENAMED_WORKER_COUNT"),
+        "{err}"
+    );
+
+    env::remove_var("KEBABRENAMED_MAX_CONNECTIONS");
+    env::remove_var("RENAMED_MAX_CONNECTIONS");
+    env::remove_var("RENAMED_LOG_LEVEL");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "SERDERENAME", allow_cli)]
+#[serde(rename_all = "camelCase")]
+struct SerdeRenamedConfig {
+    database_url: String,
+    #[serde(rename = "workers")]
+    worker_count: u32,
+    #[serde(default)]
+    log_level: Option<String>,
+}
+
+#[test]
+fn test_derive_follows_serde_rename() {
+    env::set_var("SERDERENAME_DATABASE_URL", "postgres://renamed");
+    env::set_var("SERDERENAME_WORKER_COUNT", "4");
+
+    let config = SerdeRenamedConfig::from_gonfig().unwrap();
+    assert_eq!(config.database_url, "postgres://renamed");
+    assert_eq!(config.worker_count, 4);
+    assert_eq!(config.log_level, None);
+
+    // Flags keep the field names too, and land under serde's keys
+    let cli = SerdeRenamedConfig::gonfig_cli(vec![
+        "app".into(),
+        "--worker-count".into(),
+        "8".into(),
+        "--log-level".into(),
+        "debug".into(),
+    ]);
+    let config =
+        SerdeRenamedConfig::from_gonfig_with_builder(ConfigBuilder::new().with_cli_custom(cli))
+            .unwrap();
+    assert_eq!(config.worker_count, 8);
+    assert_eq!(config.log_level.as_deref(), Some("debug"));
+
+    let fields = SerdeRenamedConfig::gonfig_fields();
+    let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(names, ["databaseUrl", "workers", "logLevel"]);
+
+    env::remove_var("SERDERENAME_DATABASE_URL");
+    env::remove_var("SERDERENAME_WORKER_COUNT");
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DB", env_separator = "__")]
+#[serde(default)]
+struct SeparatedDatabaseConfig {
+    connection_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "SEPAPP", env_separator = "__")]
+struct SeparatedAppConfig {
+    max_connections: u32,
+    #[gonfig(nested)]
+    #[serde(default)]
+    database: SeparatedDatabaseConfig,
+}
+
+#[test]
+fn test_derive_env_separator() {
+    env::set_var("SEPAPP__MAX_CONNECTIONS", "12");
+    env::set_var("SEPAPP__DB__CONNECTION_URL", "postgres://sep");
+
+    let config = SeparatedAppConfig::from_gonfig().unwrap();
+    assert_eq!(config.max_connections, 12);
+    assert_eq!(config.database.connection_url, "postgres://sep");
+    assert_eq!(
+        SeparatedAppConfig::gonfig_fields()[0].env_key,
+        "SEPAPP__MAX_CONNECTIONS"
+    );
+
+    env::remove_var("SEPAPP__MAX_CONNECTIONS");
+    env::remove_var("SEPAPP__DB__CONNECTION_URL");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "SEPKEY", separator = "__")]
+struct SeparatorKeyConfig {
+    worker_count: u32,
+    #[gonfig(env_name = "SEPKEY_OVERRIDE")]
+    named: Option<String>,
+}
+
+#[test]
+fn test_derive_separator_composes_env_keys() {
+    env::set_var("SEPKEY__WORKER_COUNT", "3");
+    env::set_var("SEPKEY_WORKER_COUNT", "99");
+
+    let fields = SeparatorKeyConfig::gonfig_fields();
+    assert_eq!(fields[0].env_key, "SEPKEY__WORKER_COUNT");
+    assert_eq!(fields[1].env_key, "SEPKEY_OVERRIDE");
+    // The env source reads exactly the key the macro reports
+    assert_eq!(SeparatorKeyConfig::from_gonfig().unwrap().worker_count, 3);
+
+    env::remove_var("SEPKEY__WORKER_COUNT");
+    env::remove_var("SEPKEY_WORKER_COUNT");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "ALIASED")]
+struct AliasedConfig {
+    #[gonfig(env_alias = "ALIASED_LEGACY_URL")]
+    db_url: String,
+}
+
+#[test]
+fn test_derive_env_alias_fallback() {
+    env::remove_var("ALIASED_DB_URL");
+    env::set_var("ALIASED_LEGACY_URL", "postgres://legacy");
+    assert_eq!(
+        AliasedConfig::from_gonfig().unwrap().db_url,
+        "postgres://legacy"
+    );
+    assert_eq!(
+        AliasedConfig::gonfig_builder()
+            .build::<AliasedConfig>()
+            .unwrap()
+            .db_url,
+        "postgres://legacy"
+    );
+
+    env::set_var("ALIASED_DB_URL", "postgres://current");
+    assert_eq!(
+        AliasedConfig::from_gonfig().unwrap().db_url,
+        "postgres://current"
+    );
+
+    env::remove_var("ALIASED_DB_URL");
+    env::remove_var("ALIASED_LEGACY_URL");
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DB")]
+struct RequiredDbConfig {
+    #[gonfig(required)]
+    url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "REQAPP")]
+struct RequiredAppConfig {
+    #[gonfig(required)]
+    name: String,
+    #[gonfig(nested)]
+    #[serde(default)]
+    database: RequiredDbConfig,
+}
+
+#[test]
+fn test_derive_required_field_names_env_var() {
+    env::set_var("REQAPP_NAME", "svc");
+    env::remove_var("REQAPP_DB_URL");
+
+    match RequiredAppConfig::from_gonfig() {
+        Err(gonfig::Error::MissingRequired { field, env_key, .. }) => {
+            assert_eq!(field, "url");
+            assert_eq!(env_key, "REQAPP_DB_URL");
+        }
+        other => panic!("expected a missing required field, got {other:?}"),
+    }
+
+    env::remove_var("REQAPP_NAME");
+    env::set_var("REQAPP_DB_URL", "postgres://localhost");
+    let err = RequiredAppConfig::from_gonfig().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "during transformation: Missing required field `name`; set REQAPP_NAME"
+    );
+
+    env::set_var("REQAPP_NAME", "svc");
+    let config = RequiredAppConfig::from_gonfig().unwrap();
+    assert_eq!(config.database.url, "postgres://localhost");
+
+    env::remove_var("REQAPP_NAME");
+    env::remove_var("REQAPP_DB_URL");
+}
+
+fn parse_seconds(text: &str) -> Result<serde_json::Value, String> {
+    let secs: u64 = text
+        .strip_suffix('s')
+        .ok_or_else(|| format!("expected a duration like `30s`, got `{text}`"))?
+        .parse()
+        .map_err(|e| format!("{e}"))?;
+    Ok(serde_json::json!({ "secs": secs, "nanos": 0 }))
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "PARSEWITH")]
+struct ParseWithConfig {
+    #[gonfig(parse_with = "parse_seconds", default = "30s")]
+    timeout: std::time::Duration,
+}
+
+#[test]
+fn test_derive_parse_with() {
+    env::remove_var("PARSEWITH_TIMEOUT");
+    assert_eq!(
+        ParseWithConfig::from_gonfig().unwrap().timeout,
+        std::time::Duration::from_secs(30)
+    );
+
+    env::set_var("PARSEWITH_TIMEOUT", "90s");
+    assert_eq!(
+        ParseWithConfig::from_gonfig().unwrap().timeout,
+        std::time::Duration::from_secs(90)
+    );
+
+    env::set_var("PARSEWITH_TIMEOUT", "90");
+    match ParseWithConfig::from_gonfig() {
+        Err(gonfig::Error::Parse { path, message, .. }) => {
+            assert_eq!(path, "timeout");
+            assert_eq!(message, "expected a duration like `30s`, got `90`");
+        }
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+
+    env::remove_var("PARSEWITH_TIMEOUT");
+}
+
+fn parse_verbatim(text: &str) -> Result<serde_json::Value, String> {
+    Ok(serde_json::json!(format!("raw:{text}")))
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "PARSERAW", allow_cli)]
+struct ParseRawConfig {
+    #[gonfig(parse_with = "parse_verbatim")]
+    code: String,
+    #[gonfig(parse_with = "parse_verbatim", default = "1e3")]
+    limit: String,
+}
+
+#[test]
+fn test_derive_parse_with_reads_raw_text() {
+    env::set_var("PARSERAW_CODE", "007");
+    env::remove_var("PARSERAW_LIMIT");
+
+    // Neither value is coerced into a number before the parser sees it,
+    // and each source's value is parsed exactly once
+    let config = ParseRawConfig::from_gonfig().unwrap();
+    assert_eq!(config.code, "raw:007");
+    assert_eq!(config.limit, "raw:1e3");
+
+    env::set_var("PARSERAW_LIMIT", "1e3");
+    let args = ["app", "--code", "0x10"].map(String::from).to_vec();
+    let config = ParseRawConfig::from_gonfig_with_builder(
+        ConfigBuilder::new().with_cli_custom(ParseRawConfig::gonfig_cli(args)),
+    )
+    .unwrap();
+    assert_eq!(config.code, "raw:0x10");
+    assert_eq!(config.limit, "raw:1e3");
+
+    env::remove_var("PARSERAW_CODE");
+    env::remove_var("PARSERAW_LIMIT");
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "NEWPFX", fallback_prefixes = "OLDPFX")]
+struct FallbackPrefixConfig {
+    host: String,
+    port: u16,
+    #[gonfig(nested)]
+    #[serde(default)]
+    database: FallbackPrefixDatabase,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DB", fallback_prefixes = "DATABASE")]
+#[serde(default)]
+struct FallbackPrefixDatabase {
+    url: String,
+}
+
+#[test]
+fn test_derive_fallback_prefixes() {
+    env::set_var("NEWPFX_HOST", "new.example");
+    env::set_var("OLDPFX_HOST", "old.example");
+    env::set_var("OLDPFX_PORT", "8080");
+    env::set_var("NEWPFX_DATABASE_URL", "postgres://legacy");
+
+    let config = FallbackPrefixConfig::from_gonfig().unwrap();
+    assert_eq!(config.host, "new.example");
+    assert_eq!(config.port, 8080);
+    // A nested fallback is composed with the parent's prefix
+    assert_eq!(config.database.url, "postgres://legacy");
+
+    for var in [
+        "NEWPFX_HOST",
+        "OLDPFX_HOST",
+        "OLDPFX_PORT",
+        "NEWPFX_DATABASE_URL",
+    ] {
+        env::remove_var(var);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DENYUNK")]
+struct DenyUnknownConfig {
+    #[gonfig(default = "8080")]
+    port: u16,
+    #[gonfig(path = "database.url", default = "postgres://localhost")]
+    database_url: String,
+}
+
+#[test]
+fn test_derive_deny_unknown_fields() {
+    let builder = || {
+        ConfigBuilder::new()
+            .with_defaults(serde_json::json!({ "prot": 9090, "database": { "pool": 5 } }))
+            .unwrap()
+    };
+
+    // Extra keys are ignored unless unknown fields are denied
+    let config = DenyUnknownConfig::from_gonfig_with_builder(builder()).unwrap();
+    assert_eq!(config.port, 8080);
+
+    match DenyUnknownConfig::from_gonfig_with_builder(builder().deny_unknown_fields(true)) {
+        Err(gonfig::Error::Validation(msg)) => {
+            assert!(
+                msg.ends_with("Unknown configuration keys: database.pool, prot"),
+                "{msg}"
+            );
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DENYATTR", deny_unknown_fields)]
+struct DenyUnknownAttrConfig {
+    #[gonfig(default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn test_derive_deny_unknown_fields_attribute() {
+    let builder = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "prot": 9090 }))
+        .unwrap();
+    match DenyUnknownAttrConfig::from_gonfig_with_builder(builder) {
+        Err(gonfig::Error::Validation(msg)) => assert!(msg.ends_with("prot"), "{msg}"),
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_derive_deny_unknown_fields_rejects_catch_all() {
+    env::set_var("FLATCATCH_PORT", "8080");
+
+    // A catch-all accepts every key, so denying unknown keys is an error
+    // rather than silently switched off
+    match CatchAllConfig::from_gonfig_with_builder(ConfigBuilder::new().deny_unknown_fields(true)) {
+        Err(gonfig::Error::Config(msg)) => assert!(msg.contains("CatchAllConfig"), "{msg}"),
+        other => panic!("expected a config error, got {other:?}"),
+    }
+
+    env::remove_var("FLATCATCH_PORT");
+}
@@ -286,3 +286,53 @@ database:
 
     Ok(())
 }
+
+#[test]
+fn test_issue_18_env_prefix_auto_nesting() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, Deserialize)]
+    struct LimitsConfig {
+        http: HttpSettings,
+        limits: Limits,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Limits {
+        max_connections: u32,
+        idle_timeout: u32,
+    }
+
+    let mut file = NamedTempFile::new()?;
+    writeln!(
+        file,
+        r#"
+http:
+  host: "127.0.0.1"
+  port: 3000
+  timeout: 30
+limits:
+  max_connections: 100
+  idle_timeout: 60
+"#
+    )?;
+    file.flush()?;
+
+    env::set_var("AUTONEST_HTTP_PORT", "9000");
+    env::set_var("AUTONEST_LIMITS_MAX_CONNECTIONS", "500");
+    env::set_var("AUTONEST_UNRELATED_SETTING", "ignored");
+
+    let config: LimitsConfig = ConfigBuilder::new()
+        .with_file_format(file.path(), ConfigFormat::Yaml)?
+        .with_env_prefix_auto_nesting::<LimitsConfig>("AUTONEST")
+        .build()?;
+
+    assert_eq!(config.http.port, 9000);
+    assert_eq!(config.http.host, "127.0.0.1");
+    // Field names containing the separator land at their exact path
+    assert_eq!(config.limits.max_connections, 500);
+    assert_eq!(config.limits.idle_timeout, 60);
+
+    env::remove_var("AUTONEST_HTTP_PORT");
+    env::remove_var("AUTONEST_LIMITS_MAX_CONNECTIONS");
+    env::remove_var("AUTONEST_UNRELATED_SETTING");
+    Ok(())
+}
@@ -34,6 +34,41 @@ fn test_deep_merge() {
     assert_eq!(result["logging"]["format"], "json");
 }
 
+#[test]
+fn test_merge_fn_deep_vs_replace() {
+    let values = vec![
+        json!({ "database": { "host": "localhost", "port": 5432 }, "debug": false }),
+        json!({ "database": { "port": 6543 } }),
+        json!({ "database": { "user": "admin" } }),
+    ];
+
+    assert_eq!(
+        gonfig::merge(&values, MergeStrategy::Deep),
+        json!({
+            "database": { "host": "localhost", "port": 6543, "user": "admin" },
+            "debug": false
+        })
+    );
+    // Replace keeps only the last value
+    assert_eq!(
+        gonfig::merge(&values, MergeStrategy::Replace),
+        json!({ "database": { "user": "admin" } })
+    );
+    assert_eq!(gonfig::merge(&[], MergeStrategy::Deep), json!({}));
+    assert_eq!(
+        gonfig::merge(&values[1..], MergeStrategy::Deep),
+        json!({ "database": { "port": 6543, "user": "admin" } })
+    );
+
+    // Same result as the builder-facing merger with ascending priorities
+    let merger = ConfigMerger::new(MergeStrategy::Deep);
+    let prioritized = values.iter().cloned().zip([1, 2, 3]).collect();
+    assert_eq!(
+        merger.merge_sources(prioritized),
+        gonfig::merge(&values, MergeStrategy::Deep)
+    );
+}
+
 #[test]
 fn test_replace_merge() {
     let merger = ConfigMerger::new(MergeStrategy::Replace);
@@ -64,6 +99,33 @@ fn test_replace_merge() {
     assert!(result.get("logging").is_none());
 }
 
+#[test]
+fn test_shallow_merge() {
+    let merger = ConfigMerger::new(MergeStrategy::Shallow);
+
+    let base = json!({
+        "database": {
+            "host": "localhost",
+            "port": 5432
+        },
+        "logging": {
+            "level": "info"
+        }
+    });
+
+    let incoming = json!({
+        "database": {
+            "host": "db.internal"
+        }
+    });
+
+    let result = merger.merge_sources(vec![(base, 1), (incoming, 2)]);
+
+    // The whole `database` section is replaced, other sections are kept
+    assert_eq!(result["database"], json!({ "host": "db.internal" }));
+    assert_eq!(result["logging"]["level"], "info");
+}
+
 #[test]
 fn test_append_merge_arrays() {
     let merger = ConfigMerger::new(MergeStrategy::Append);
@@ -148,3 +210,83 @@ fn test_null_value_handling() {
     assert_eq!(result["field2"], "value2");
     assert_eq!(result["field3"], "value3");
 }
+
+#[test]
+fn test_deep_prefer_existing_merge() {
+    let merger = ConfigMerger::new(MergeStrategy::DeepPreferExisting);
+
+    let base = json!({
+        "database": {
+            "host": "baked-in",
+            "port": 5432
+        }
+    });
+
+    let incoming = json!({
+        "database": {
+            "host": "from-env",
+            "username": "admin"
+        },
+        "debug": true
+    });
+
+    let result = merger.merge_sources(vec![(incoming, 2), (base, 1)]);
+
+    assert_eq!(result["database"]["host"], "baked-in");
+    assert_eq!(result["database"]["port"], 5432);
+    assert_eq!(result["database"]["username"], "admin");
+    assert_eq!(result["debug"], true);
+}
+
+#[test]
+fn test_deep_append_arrays_merge() {
+    let merger = ConfigMerger::new(MergeStrategy::DeepAppendArrays { dedup: false });
+
+    let base = json!({
+        "server": { "allowed_hosts": ["a.example", "b.example"], "port": 80 }
+    });
+    let incoming = json!({
+        "server": { "allowed_hosts": ["b.example", "c.example"], "port": 443 }
+    });
+
+    let result = merger
+        .try_merge_sources(vec![(incoming.clone(), 2), (base.clone(), 1)])
+        .unwrap();
+    assert_eq!(
+        result["server"]["allowed_hosts"],
+        json!(["a.example", "b.example", "b.example", "c.example"])
+    );
+    assert_eq!(result["server"]["port"], 443);
+
+    let dedup = ConfigMerger::new(MergeStrategy::DeepAppendArrays { dedup: true });
+    let result = dedup
+        .try_merge_sources(vec![(base, 1), (incoming, 2)])
+        .unwrap();
+    assert_eq!(
+        result["server"]["allowed_hosts"],
+        json!(["a.example", "b.example", "c.example"])
+    );
+}
+
+#[test]
+fn test_deep_append_arrays_type_conflict() {
+    let merger = ConfigMerger::new(MergeStrategy::DeepAppendArrays { dedup: false });
+
+    let err = merger
+        .try_merge_sources(vec![
+            (json!({ "server": { "allowed_hosts": ["a.example"] } }), 1),
+            (json!({ "server": { "allowed_hosts": "b.example" } }), 2),
+        ])
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("`server.allowed_hosts`"), "{message}");
+    assert!(message.contains("an array"), "{message}");
+    assert!(message.contains("a string"), "{message}");
+
+    // null never conflicts, and the infallible merge lets the incoming value win
+    let result = merger.merge_sources(vec![
+        (json!({ "hosts": ["a"], "tags": null }), 1),
+        (json!({ "hosts": "b", "tags": ["x"] }), 2),
+    ]);
+    assert_eq!(result, json!({ "hosts": "b", "tags": ["x"] }));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "metrics")]
+
+use gonfig::{ConfigBuilder, Environment, Error};
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::MetricKind;
+
+type Snapshot = Vec<(
+    metrics_util::CompositeKey,
+    Option<metrics::Unit>,
+    Option<metrics::SharedString>,
+    DebugValue,
+)>;
+
+fn counter(snapshot: &Snapshot, name: &str, source: Option<&str>) -> u64 {
+    snapshot
+        .iter()
+        .filter(|(key, _, _, _)| {
+            key.kind() == MetricKind::Counter
+                && key.key().name() == name
+                && match source {
+                    Some(source) => key
+                        .key()
+                        .labels()
+                        .any(|label| label.key() == "source" && label.value() == source),
+                    None => true,
+                }
+        })
+        .map(|(_, _, _, value)| match value {
+            DebugValue::Counter(count) => *count,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[test]
+fn test_metrics_count_sources_keys_and_overrides() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    metrics::with_local_recorder(&recorder, || {
+        ConfigBuilder::new()
+            .with_defaults(serde_json::json!({ "port": 8080, "host": "localhost" }))
+            .unwrap()
+            .with_env_custom(Environment::new().override_with("PORT", "9000"))
+            .build_value()
+            .unwrap();
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    assert_eq!(
+        counter(&snapshot, gonfig::metrics::SOURCES_LOADED, Some("Default")),
+        1
+    );
+    assert_eq!(
+        counter(
+            &snapshot,
+            gonfig::metrics::SOURCES_LOADED,
+            Some("Environment")
+        ),
+        1
+    );
+    assert_eq!(
+        counter(&snapshot, gonfig::metrics::KEYS_COLLECTED, Some("Default")),
+        2
+    );
+    assert_eq!(
+        counter(&snapshot, gonfig::metrics::OVERRIDES_APPLIED, None),
+        1
+    );
+}
+
+#[test]
+fn test_metrics_count_validation_failures() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    metrics::with_local_recorder(&recorder, || {
+        let result = ConfigBuilder::new()
+            .validate_with(|_| Err(Error::Validation("rejected".to_string())))
+            .build_value();
+        assert!(result.is_err());
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    assert_eq!(
+        counter(&snapshot, gonfig::metrics::VALIDATION_FAILURES, None),
+        1
+    );
+}
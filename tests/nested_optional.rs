@@ -0,0 +1,65 @@
+// Test `#[gonfig(nested)]` on `Option<SubStruct>` fields
+// Uses unique env vars to avoid test interference
+
+use gonfig::Gonfig;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Gonfig, PartialEq)]
+#[Gonfig(env_prefix = "CACHE")]
+pub struct OptionalCacheConfig {
+    pub url: String,
+
+    #[gonfig(default = "60")]
+    pub ttl: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "OPTAPP")]
+pub struct OptionalSectionConfig {
+    #[gonfig(nested)]
+    #[serde(default)]
+    pub cache: Option<OptionalCacheConfig>,
+
+    #[gonfig(default = "\"svc\"")]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "OPTNONE")]
+pub struct AbsentSectionConfig {
+    #[gonfig(nested)]
+    #[serde(default)]
+    pub cache: Option<OptionalCacheConfig>,
+
+    #[gonfig(default = "\"svc\"")]
+    pub name: String,
+}
+
+#[test]
+fn test_optional_nested_section_loaded_when_vars_set() {
+    env::set_var("OPTAPP_CACHE_URL", "redis://localhost");
+
+    let config = OptionalSectionConfig::from_gonfig().unwrap();
+    assert_eq!(
+        config.cache,
+        Some(OptionalCacheConfig {
+            url: "redis://localhost".to_string(),
+            ttl: 60,
+        })
+    );
+    assert_eq!(config.name, "svc");
+
+    env::remove_var("OPTAPP_CACHE_URL");
+}
+
+#[test]
+fn test_optional_nested_section_none_without_vars() {
+    env::remove_var("OPTNONE_CACHE_URL");
+    env::remove_var("OPTNONE_CACHE_TTL");
+
+    // Defaults alone don't make the section present
+    let config = AbsentSectionConfig::from_gonfig().unwrap();
+    assert_eq!(config.cache, None);
+    assert_eq!(config.name, "svc");
+}
@@ -0,0 +1,192 @@
+#![cfg(feature = "remote")]
+
+use gonfig::remote::{HttpJsonSource, RemoteRequest, RemoteSource};
+use gonfig::{ConfigBuilder, ConfigSource, Error, Source};
+use std::env;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// Serve `response` to a single request, returning the server's URL and a
+/// receiver for the raw request head.
+fn serve_once(response: &'static str) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, requests) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).unwrap();
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+        sender
+            .send(String::from_utf8_lossy(&request).into_owned())
+            .unwrap();
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    (url, requests)
+}
+
+#[test]
+fn test_http_json_source_fetches_secret_path() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve_once(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n\
+         {\"data\": {\"data\": {\"database\": {\"password\": \"hunter2\"}}}}",
+    );
+
+    let source = HttpJsonSource::new(format!("{url}/v1/secret/data/app"))
+        .with_bearer_token("s.token")
+        .with_timeout(Duration::from_secs(5))
+        .with_path("data.data");
+    let value = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "database": { "host": "localhost" } }))?
+        .add_source(Box::new(source))
+        .build_value()?;
+    assert_eq!(
+        value,
+        serde_json::json!({ "database": { "host": "localhost", "password": "hunter2" } })
+    );
+
+    let request = requests.recv()?;
+    assert!(
+        request.starts_with("GET /v1/secret/data/app HTTP/1.1\r\n"),
+        "{request}"
+    );
+    assert!(
+        request.contains("Authorization: Bearer s.token\r\n"),
+        "{request}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_http_json_source_decodes_chunked_body() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _requests) = serve_once(
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+         7\r\n{\"port\"\r\n6\r\n: 8080\r\n1\r\n}\r\n0\r\n\r\n",
+    );
+
+    let value = ConfigBuilder::new().with_remote(url).build_value()?;
+    assert_eq!(value["port"], 8080);
+    Ok(())
+}
+
+#[test]
+fn test_http_json_source_failures_are_source_errors() {
+    let (url, _requests) = serve_once("HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n");
+    let err = HttpJsonSource::new(url).collect().unwrap_err();
+    match err {
+        Error::Source { message, .. } => assert!(message.contains("403"), "{message}"),
+        other => panic!("expected a source error, got {other:?}"),
+    }
+
+    // A server that never answers runs into the timeout
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let err = HttpJsonSource::new(url)
+        .with_timeout(Duration::from_millis(100))
+        .collect()
+        .unwrap_err();
+    assert!(matches!(err, Error::Source { .. }), "{err}");
+    drop(listener);
+
+    // A token never goes to a remote host in clear text
+    let err = HttpJsonSource::new("http://vault.invalid:8200/v1/secret")
+        .with_bearer_token("s.token")
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("https://"), "{err}");
+
+    // A resilient builder skips the failing remote source
+    let value = ConfigBuilder::new()
+        .resilient(true)
+        .with_remote("https://vault.invalid:8200/v1/secret")
+        .build_value()
+        .unwrap();
+    assert_eq!(value, serde_json::json!({}));
+}
+
+#[test]
+fn test_http_json_source_limits_body_size() {
+    let (url, _requests) = serve_once(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n\
+         {\"name\": \"a document longer than the limit\"}",
+    );
+    let err = HttpJsonSource::new(url)
+        .with_max_body_size(16)
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("exceeds 16 bytes"), "{err}");
+}
+
+#[test]
+fn test_builder_with_remote_at() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _requests) = serve_once(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n\
+         {\"port\": 1, \"host\": \"remote\"}",
+    );
+
+    env::set_var("REMOTEAT_PORT", "2");
+    let value = ConfigBuilder::new()
+        .with_env("REMOTEAT")
+        .with_remote_at(url, Source::Default)
+        .build_value();
+    env::remove_var("REMOTEAT_PORT");
+    let value = value?;
+    // Below the environment, the remote document only fills in gaps
+    assert_eq!(value["port"], 2);
+    assert_eq!(value["host"], "remote");
+    Ok(())
+}
+
+struct Fixed(&'static str);
+
+impl RemoteSource for Fixed {
+    fn fetch(&self, _request: &RemoteRequest) -> gonfig::Result<Vec<u8>> {
+        Ok(self.0.as_bytes().to_vec())
+    }
+}
+
+#[test]
+fn test_http_json_source_custom_client_and_priority() -> Result<(), Box<dyn std::error::Error>> {
+    let source = HttpJsonSource::with_client(Fixed(r#"{"port": 1, "host": "remote"}"#), "mock")
+        .at_priority(Source::Default);
+    assert_eq!(source.source_type(), Source::Default);
+
+    env::set_var("REMOTEPRIO_PORT", "2");
+    let value = ConfigBuilder::new()
+        .with_env_flat("REMOTEPRIO")
+        .add_source(Box::new(source))
+        .build_value()?;
+    env::remove_var("REMOTEPRIO_PORT");
+    // At default priority, the environment wins
+    assert_eq!(value["port"], 2);
+    assert_eq!(value["host"], "remote");
+
+    let err = HttpJsonSource::with_client(Fixed(r#"{"data": {}}"#), "mock")
+        .with_path("data.data")
+        .collect()
+        .unwrap_err();
+    assert!(matches!(err, Error::Source { .. }), "{err}");
+
+    let err = HttpJsonSource::with_client(Fixed("[1, 2]"), "mock")
+        .collect()
+        .unwrap_err();
+    assert!(err.to_string().contains("JSON object"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_remote_request_debug_hides_token() {
+    let mut request = RemoteRequest::new("http://vault");
+    request.bearer_token = Some("s.secret".to_string());
+    let debug = format!("{request:?}");
+    assert!(!debug.contains("s.secret"), "{debug}");
+}
@@ -0,0 +1,214 @@
+use gonfig::Gonfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "HELPAPP")]
+pub struct HelpConfig {
+    /// Database connection string
+    #[gonfig(example = "postgres://user:pass@db:5432/app")]
+    pub database_url: String,
+
+    #[gonfig(default = "8080", example = "9000", value_name = "NUM")]
+    pub port: u16,
+
+    #[skip]
+    #[serde(skip)]
+    pub runtime: Option<String>,
+}
+
+#[test]
+fn test_example_in_field_metadata() {
+    let fields = HelpConfig::gonfig_fields();
+    assert_eq!(fields.len(), 2);
+
+    let db = &fields[0];
+    assert_eq!(db.name, "database_url");
+    assert_eq!(db.env_key, "HELPAPP_DATABASE_URL");
+    assert_eq!(db.cli_key.as_deref(), Some("database-url"));
+    assert_eq!(
+        db.example.as_deref(),
+        Some("postgres://user:pass@db:5432/app")
+    );
+    assert_eq!(db.doc.as_deref(), Some("Database connection string"));
+    assert_eq!(db.default, None);
+
+    let port = &fields[1];
+    assert_eq!(port.default.as_deref(), Some("8080"));
+    assert_eq!(port.example.as_deref(), Some("9000"));
+}
+
+#[test]
+fn test_example_in_generated_help() {
+    let help = HelpConfig::gonfig_help();
+
+    assert!(help.contains("--database-url <DATABASE_URL>"));
+    assert!(help.contains("--port <NUM>"));
+    assert!(help.contains("[env: HELPAPP_DATABASE_URL]"));
+    assert!(help.contains("[example: postgres://user:pass@db:5432/app]"));
+    assert!(help.contains("[default: 8080] [example: 9000]"));
+    assert!(!help.contains("runtime"));
+}
+
+#[test]
+fn test_generated_env_example() {
+    let example = HelpConfig::gonfig_env_example();
+
+    assert!(example.contains(
+        "# Database connection string\nHELPAPP_DATABASE_URL=postgres://user:pass@db:5432/app\n"
+    ));
+    assert!(example.contains("HELPAPP_PORT=8080\n"));
+    assert!(!example.contains("RUNTIME"));
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "DB")]
+pub struct ExampleDatabase {
+    /// Connection string
+    pub url: String,
+    #[gonfig(default = "5")]
+    pub pool_size: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "EXAMPLEAPP")]
+pub struct ExampleConfig {
+    #[gonfig(default = "8080")]
+    pub port: u16,
+    #[gonfig(nested)]
+    #[serde(default)]
+    pub database: ExampleDatabase,
+}
+
+impl Default for ExampleDatabase {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            pool_size: 5,
+        }
+    }
+}
+
+#[test]
+fn test_generated_config_example() {
+    std::env::set_var("EXAMPLEAPP_PORT", "9999");
+
+    assert_eq!(
+        ExampleConfig::gonfig_example(gonfig::ConfigFormat::Toml),
+        "# env: EXAMPLEAPP_PORT\n\
+         port = 8080\n\
+         \n\
+         [database]\n\
+         # Connection string\n\
+         # env: EXAMPLEAPP_DB_URL\n\
+         url = \"<URL>\"\n\
+         # env: EXAMPLEAPP_DB_POOL_SIZE\n\
+         pool_size = 5\n"
+    );
+    assert_eq!(
+        ExampleConfig::gonfig_example(gonfig::ConfigFormat::Yaml),
+        "# env: EXAMPLEAPP_PORT\n\
+         port: 8080\n\
+         database:\n  \
+           # Connection string\n  \
+           # env: EXAMPLEAPP_DB_URL\n  \
+           url: <URL>\n  \
+           # env: EXAMPLEAPP_DB_POOL_SIZE\n  \
+           pool_size: 5\n"
+    );
+
+    // Every rendering parses back in its own format
+    for format in [
+        gonfig::ConfigFormat::Toml,
+        gonfig::ConfigFormat::Yaml,
+        gonfig::ConfigFormat::Json,
+        gonfig::ConfigFormat::Ini,
+    ] {
+        let text = ExampleConfig::gonfig_example(format.clone());
+        let value = format.parse(&text).unwrap();
+        assert_eq!(value["port"], 8080, "{text}");
+        assert_eq!(value["database"]["pool_size"], 5, "{text}");
+    }
+
+    std::env::remove_var("EXAMPLEAPP_PORT");
+}
+
+#[test]
+fn test_field_paths_walk_nested_types() {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Mode {
+        Fast,
+        Safe,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "camelCase")]
+    struct Pool {
+        max_size: u32,
+        idle_timeout: Duration,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        mode: Mode,
+        pool: Option<Pool>,
+        hosts: Vec<String>,
+        labels: HashMap<String, String>,
+        debug: bool,
+    }
+
+    assert_eq!(
+        gonfig::schema::field_paths::<Config>(),
+        [
+            "mode",
+            "pool.maxSize",
+            "pool.idleTimeout",
+            "hosts",
+            "labels",
+            "debug"
+        ]
+    );
+}
+
+#[test]
+fn test_field_paths_continue_past_rejected_placeholders() {
+    use std::num::NonZeroU16;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Http {
+        port: NonZeroU16,
+        host: String,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        http: Http,
+        workers: u32,
+    }
+
+    // `NonZeroU16` rejects the placeholder zero, which must not hide the
+    // fields declared after it
+    assert_eq!(
+        gonfig::schema::field_paths::<Config>(),
+        ["http.port", "http.host", "workers"]
+    );
+
+    std::env::set_var("PROBENZ_WORKERS", "8");
+    std::env::set_var("PROBENZ_HTTP_HOST", "example");
+    let value = gonfig::ConfigBuilder::new()
+        .with_env_prefix_auto_nesting::<Config>("PROBENZ")
+        .build_value();
+    std::env::remove_var("PROBENZ_WORKERS");
+    std::env::remove_var("PROBENZ_HTTP_HOST");
+    assert_eq!(
+        value.unwrap(),
+        serde_json::json!({ "http": { "host": "example" }, "workers": 8 })
+    );
+}
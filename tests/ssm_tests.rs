@@ -0,0 +1,260 @@
+#![cfg(feature = "aws")]
+
+use gonfig::ssm::{Parameter, ParameterStore, ParameterType};
+use gonfig::{ConfigBuilder, Error};
+use serde::Deserialize;
+use std::env;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// In-memory parameter store recording the requests it receives.
+struct MockStore {
+    parameters: Vec<Parameter>,
+    requests: Arc<Mutex<Vec<(String, bool)>>>,
+}
+
+impl MockStore {
+    fn new(parameters: Vec<Parameter>) -> Self {
+        Self {
+            parameters,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl ParameterStore for MockStore {
+    fn parameters_by_path(
+        &self,
+        path: &str,
+        with_decryption: bool,
+    ) -> gonfig::Result<Vec<Parameter>> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((path.to_string(), with_decryption));
+        Ok(self
+            .parameters
+            .iter()
+            .filter(|p| p.name.starts_with(path))
+            .cloned()
+            .collect())
+    }
+}
+
+struct FailingStore;
+
+impl ParameterStore for FailingStore {
+    fn parameters_by_path(
+        &self,
+        _path: &str,
+        _with_decryption: bool,
+    ) -> gonfig::Result<Vec<Parameter>> {
+        Err(Error::Config("SSM unavailable".into()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseConfig {
+    url: String,
+    password: String,
+    replicas: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppConfig {
+    port: u16,
+    database: DatabaseConfig,
+}
+
+#[test]
+fn test_ssm_nests_parameters_by_path() {
+    let store = MockStore::new(vec![
+        Parameter::new("/myapp/prod/port", "8080"),
+        Parameter::new("/myapp/prod/database/url", "postgres://ssm"),
+        Parameter::new("/myapp/prod/database/password", "s3cret").kind(ParameterType::SecureString),
+        Parameter::new("/myapp/prod/database/replicas", "db1,db2").kind(ParameterType::StringList),
+        Parameter::new("/myapp/staging/port", "9999"),
+        Parameter::new("/myapp/production/url", "postgres://elsewhere"),
+    ]);
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_ssm_store(store, "/myapp/prod")
+        .build()
+        .unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.database.url, "postgres://ssm");
+    assert_eq!(config.database.password, "s3cret");
+    assert_eq!(config.database.replicas, vec!["db1", "db2"]);
+}
+
+#[test]
+fn test_ssm_prefix_matches_whole_segments() {
+    use gonfig::ssm::SsmSource;
+    use gonfig::ConfigSource;
+
+    let store = MockStore::new(vec![
+        Parameter::new("/myapp/prod/port", "8080"),
+        Parameter::new("/myapp/production/url", "postgres://elsewhere"),
+    ]);
+    let value = SsmSource::with_store(store, "/myapp/prod/")
+        .collect()
+        .unwrap();
+    assert_eq!(value, serde_json::json!({ "port": 8080 }));
+}
+
+/// Answer one request per entry of `pages`, returning the server's URL and a
+/// receiver for each raw request.
+fn serve_pages(pages: Vec<&'static str>) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let (sender, requests) = mpsc::channel();
+    thread::spawn(move || {
+        for page in pages {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            // Read the head, then as much body as Content-Length announces
+            loop {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if read == 0 {
+                    break;
+                }
+            }
+            sender
+                .send(String::from_utf8_lossy(&request).into_owned())
+                .unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-amz-json-1.1\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (url, requests)
+}
+
+#[test]
+fn test_aws_parameter_store_signs_and_paginates() {
+    use aws_config::{Region, SdkConfig};
+    use aws_credential_types::{provider::SharedCredentialsProvider, Credentials};
+    use gonfig::ssm::AwsParameterStore;
+
+    let (url, requests) = serve_pages(vec![
+        r#"{"Parameters": [{"Name": "/svc/db/url", "Type": "String", "Value": "postgres://aws"}], "NextToken": "page-2"}"#,
+        r#"{"Parameters": [{"Name": "/svc/db/password", "Type": "SecureString", "Value": "hunter2"}]}"#,
+    ]);
+    let config = SdkConfig::builder()
+        .region(Region::new("eu-west-1"))
+        .credentials_provider(SharedCredentialsProvider::new(Credentials::new(
+            "AKIDTEST", "secret", None, None, "test",
+        )))
+        .endpoint_url(url)
+        .build();
+
+    let value = ConfigBuilder::new()
+        .with_ssm_store(AwsParameterStore::from_config(config), "/svc")
+        .build_value()
+        .unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({ "db": { "url": "postgres://aws", "password": "hunter2" } })
+    );
+
+    let first = requests.recv().unwrap();
+    assert!(first.starts_with("POST / HTTP/1.1\r\n"), "{first}");
+    assert!(
+        first
+            .to_lowercase()
+            .contains("x-amz-target: amazonssm.getparametersbypath"),
+        "{first}"
+    );
+    assert!(
+        first.contains("AWS4-HMAC-SHA256 Credential=AKIDTEST/")
+            && first.contains("/eu-west-1/ssm/aws4_request"),
+        "{first}"
+    );
+    assert!(first.contains(r#""WithDecryption":true"#), "{first}");
+    let second = requests.recv().unwrap();
+    assert!(second.contains(r#""NextToken":"page-2""#), "{second}");
+}
+
+#[test]
+fn test_ssm_requests_decryption() {
+    use gonfig::ssm::SsmSource;
+    use gonfig::ConfigSource;
+
+    let store = MockStore::new(vec![]);
+    let requests = Arc::clone(&store.requests);
+
+    SsmSource::with_store(store, "/myapp/prod")
+        .collect()
+        .unwrap();
+    assert_eq!(
+        requests.lock().unwrap().as_slice(),
+        &[("/myapp/prod".to_string(), true)]
+    );
+}
+
+#[test]
+fn test_ssm_overrides_environment() {
+    env::set_var("SSMTEST_PORT", "3000");
+    env::set_var("SSMTEST_NAME", "from-env");
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        port: u16,
+        name: String,
+    }
+
+    let store = MockStore::new(vec![Parameter::new("/svc/port", "4000")]);
+    let config: Config = ConfigBuilder::new()
+        .with_ssm_store(store, "/svc")
+        .with_env("SSMTEST")
+        .build()
+        .unwrap();
+
+    assert_eq!(config.port, 4000);
+    assert_eq!(config.name, "from-env");
+
+    env::remove_var("SSMTEST_PORT");
+    env::remove_var("SSMTEST_NAME");
+}
+
+#[test]
+fn test_ssm_failure_respects_resilient_policy() {
+    let strict = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({ "port": 1 }))
+        .unwrap()
+        .with_ssm_store(FailingStore, "/svc")
+        .build_value();
+    assert!(strict.is_err());
+
+    let value = ConfigBuilder::new()
+        .resilient(true)
+        .with_defaults(serde_json::json!({ "port": 1 }))
+        .unwrap()
+        .with_ssm_store(FailingStore, "/svc")
+        .build_value()
+        .unwrap();
+    assert_eq!(value["port"], 1);
+}
@@ -0,0 +1,96 @@
+use gonfig::transform::combine_duration;
+use gonfig::{ConfigBuilder, Error};
+use serde::Deserialize;
+use std::env;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+struct ServerConfig {
+    host: String,
+    timeout: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppConfig {
+    server: ServerConfig,
+}
+
+#[test]
+fn test_duration_pair_combines_value_and_unit() -> Result<(), Box<dyn std::error::Error>> {
+    env::set_var("DURPAIR_SERVER_TIMEOUT_VALUE", "90");
+
+    let config: AppConfig = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({
+            "server": {
+                "host": "localhost",
+                "timeout_value": 30,
+                "timeout_unit": "s"
+            }
+        }))?
+        .with_env_configured("DURPAIR", |env| {
+            env.nested(true).with_known_paths(["server.timeout_value"])
+        })
+        .with_duration_pair(
+            "server.timeout_value",
+            "server.timeout_unit",
+            "server.timeout",
+        )
+        .build()?;
+
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.timeout, Duration::from_secs(90));
+
+    env::remove_var("DURPAIR_SERVER_TIMEOUT_VALUE");
+    Ok(())
+}
+
+#[test]
+fn test_combine_duration_units() {
+    let cases = [
+        (json_pair(250, "ms"), Duration::from_millis(250)),
+        (json_pair(2, "minutes"), Duration::from_secs(120)),
+        (json_pair(1, "h"), Duration::from_secs(3600)),
+        (json_pair(1.5, "s"), Duration::from_millis(1500)),
+    ];
+
+    for (mut value, expected) in cases {
+        combine_duration(&mut value, "value", "unit", "duration").unwrap();
+        assert!(value.get("value").is_none());
+        assert!(value.get("unit").is_none());
+        let duration: Duration = serde_json::from_value(value["duration"].clone()).unwrap();
+        assert_eq!(duration, expected);
+    }
+}
+
+#[test]
+fn test_combine_duration_rejects_bad_input() {
+    let mut unknown_unit = json_pair(5, "fortnights");
+    assert!(matches!(
+        combine_duration(&mut unknown_unit, "value", "unit", "duration"),
+        Err(Error::Validation(_))
+    ));
+
+    let mut missing_unit = serde_json::json!({ "value": 5 });
+    assert!(matches!(
+        combine_duration(&mut missing_unit, "value", "unit", "duration"),
+        Err(Error::Validation(_))
+    ));
+
+    let mut empty_unit = json_pair(5, "");
+    match combine_duration(&mut empty_unit, "value", "unit", "duration") {
+        Err(Error::Validation(message)) => {
+            assert!(message.contains("Missing duration unit"), "{message}");
+            assert!(message.contains("ns, us, ms, s, m, h, d"), "{message}");
+            assert!(!message.contains("``"), "{message}");
+        }
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+
+    let mut absent = serde_json::json!({ "other": 1 });
+    combine_duration(&mut absent, "value", "unit", "duration").unwrap();
+    assert_eq!(absent, serde_json::json!({ "other": 1 }));
+}
+
+fn json_pair(value: impl Into<serde_json::Value>, unit: &str) -> serde_json::Value {
+    serde_json::json!({ "value": value.into(), "unit": unit })
+}
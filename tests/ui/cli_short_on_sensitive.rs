@@ -0,0 +1,10 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Gonfig)]
+struct Config {
+    #[gonfig(sensitive, cli_short = 'k')]
+    api_key: String,
+}
+
+fn main() {}
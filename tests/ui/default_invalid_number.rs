@@ -0,0 +1,10 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Gonfig)]
+struct Config {
+    #[gonfig(default = "not-a-number")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Gonfig)]
+#[Gonfig(deny_unknown_fields)]
+struct Config {
+    port: u16,
+
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Gonfig)]
+struct Config {
+    #[gonfig(cli_short = 'p')]
+    port: u16,
+    #[gonfig(cli_short = 'p')]
+    path: String,
+}
+
+fn main() {}
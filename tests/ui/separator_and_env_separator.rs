@@ -0,0 +1,10 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Gonfig)]
+#[Gonfig(env_prefix = "APP", separator = "__", env_separator = "__")]
+struct Config {
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use gonfig::Gonfig;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Gonfig)]
+struct Config {
+    port: u16,
+
+    #[skip]
+    runtime_state: String,
+}
+
+fn main() {}
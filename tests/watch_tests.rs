@@ -0,0 +1,168 @@
+#![cfg(feature = "watch")]
+
+use gonfig::{ConfigBuilder, Error};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct ServiceConfig {
+    workers: u32,
+    name: String,
+}
+
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Replace `path` atomically, like editors and deploy tools do.
+fn replace(path: &Path, content: &str) -> std::io::Result<()> {
+    let staged = path.with_extension("staged");
+    fs::write(&staged, content)?;
+    fs::rename(staged, path)
+}
+
+#[test]
+fn test_watch_reloads_on_file_change() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("service.toml");
+    replace(&path, "workers = 2\nname = \"billing\"\n")?;
+
+    let file = path.clone();
+    let watched = ConfigBuilder::watch_with_debounce::<ServiceConfig, _>(
+        move || {
+            ConfigBuilder::new()
+                .with_defaults(serde_json::json!({ "name": "default" }))?
+                .with_file(&file)
+        },
+        Duration::from_millis(20),
+    )?;
+    assert_eq!(watched.current().workers, 2);
+
+    let updates = watched.subscribe();
+    replace(&path, "workers = 8\nname = \"billing\"\n")?;
+    let config = updates.recv_timeout(WAIT)?;
+    assert_eq!(config.workers, 8);
+    assert_eq!(*watched.current(), *config);
+    Ok(())
+}
+
+#[test]
+fn test_watch_keeps_last_good_value_on_error() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("service.toml");
+    replace(&path, "workers = 2\nname = \"billing\"\n")?;
+
+    let file = path.clone();
+    let watched = ConfigBuilder::watch_with_debounce::<ServiceConfig, _>(
+        move || {
+            // Shared through an `Rc`, so the validator is not `Send`
+            let minimum = Rc::new(1);
+            Ok(ConfigBuilder::new()
+                .with_file(&file)?
+                .validate_with(move |value| match value["workers"].as_u64() {
+                    Some(workers) if workers >= *minimum => Ok(()),
+                    _ => Err(Error::Validation("workers must be positive".to_string())),
+                }))
+        },
+        Duration::from_millis(20),
+    )?;
+    let updates = watched.subscribe();
+    let errors = watched.subscribe_errors();
+
+    // Unparseable file
+    replace(&path, "workers = = 3\n")?;
+    let error = errors.recv_timeout(WAIT)?;
+    assert!(error.to_string().contains("TOML"), "{error}");
+    assert_eq!(watched.current().workers, 2);
+
+    // Parses, but fails validation
+    replace(&path, "workers = 0\nname = \"billing\"\n")?;
+    let error = errors.recv_timeout(WAIT)?;
+    assert!(matches!(*error, Error::Validation(_)), "{error}");
+    assert_eq!(watched.current().workers, 2);
+
+    // Recovers with the next good file
+    replace(&path, "workers = 3\nname = \"billing\"\n")?;
+    assert_eq!(updates.recv_timeout(WAIT)?.workers, 3);
+    assert!(updates.try_recv().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_watch_reports_errors_of_make() {
+    let result = ConfigBuilder::watch_with::<ServiceConfig, _>(|| {
+        ConfigBuilder::new().with_file("/non/existent/service.toml")
+    });
+    assert!(matches!(result, Err(Error::Io { .. })));
+}
+
+#[test]
+fn test_watch_ignores_rewrites_with_identical_content() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("service.toml");
+    replace(&path, "workers = 2\nname = \"billing\"\n")?;
+
+    let file = path.clone();
+    let watched = ConfigBuilder::watch_with_debounce::<ServiceConfig, _>(
+        move || ConfigBuilder::new().with_file(&file),
+        Duration::from_millis(20),
+    )?;
+    let updates = watched.subscribe();
+
+    fs::write(&path, "workers = 2\nname = \"billing\"\n")?;
+    assert!(updates.recv_timeout(Duration::from_millis(500)).is_err());
+    replace(&path, "workers = 4\nname = \"billing\"\n")?;
+    assert_eq!(updates.recv_timeout(WAIT)?.workers, 4);
+    Ok(())
+}
+
+#[test]
+fn test_watch_channels_close_on_drop() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("service.json");
+    replace(&path, r#"{ "workers": 1, "name": "billing" }"#)?;
+
+    let file = path.clone();
+    let watched = ConfigBuilder::watch_with::<ServiceConfig, _>(move || {
+        ConfigBuilder::new().with_file(&file)
+    })?;
+    let updates = watched.subscribe();
+    drop(watched);
+    assert!(updates.recv_timeout(WAIT).is_err());
+    Ok(())
+}
+
+#[cfg(all(feature = "signal", unix))]
+#[test]
+fn test_watch_reloads_on_sighup() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("service.toml");
+    replace(&path, "workers = 2\nname = \"billing\"\n")?;
+    std::env::remove_var("SIGHUPAPP_WORKERS");
+
+    let file = path.clone();
+    let watched = ConfigBuilder::watch_with_debounce::<ServiceConfig, _>(
+        move || {
+            Ok(ConfigBuilder::new()
+                .with_file(&file)?
+                .with_env("SIGHUPAPP")
+                .reload_on_sighup())
+        },
+        Duration::from_millis(20),
+    )?;
+    let updates = watched.subscribe();
+
+    // The environment is not watched, so only the signal picks this up
+    std::env::set_var("SIGHUPAPP_WORKERS", "6");
+    let status = std::process::Command::new("kill")
+        .args(["-HUP", &std::process::id().to_string()])
+        .status()?;
+    assert!(status.success());
+
+    let config = updates.recv_timeout(WAIT)?;
+    assert_eq!(config.workers, 6);
+    assert_eq!(watched.current().workers, 6);
+    std::env::remove_var("SIGHUPAPP_WORKERS");
+    Ok(())
+}